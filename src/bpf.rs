@@ -0,0 +1,709 @@
+// decoding of the bpf syscall, whose arguments all live in `union bpf_attr`
+// the layout of each command's struct is taken from include/uapi/linux/bpf.h
+use crate::{syscall_object::SyscallObject, types::Bytes, utilities::BPF_DISASSEMBLY};
+use colored::{ColoredString, Colorize};
+use nix::unistd::Pid;
+
+const BPF_MAP_CREATE: u64 = 0;
+const BPF_MAP_LOOKUP_ELEM: u64 = 1;
+const BPF_MAP_UPDATE_ELEM: u64 = 2;
+const BPF_MAP_DELETE_ELEM: u64 = 3;
+const BPF_MAP_GET_NEXT_KEY: u64 = 4;
+const BPF_PROG_LOAD: u64 = 5;
+const BPF_OBJ_PIN: u64 = 6;
+const BPF_OBJ_GET: u64 = 7;
+const BPF_PROG_ATTACH: u64 = 8;
+const BPF_PROG_DETACH: u64 = 9;
+const BPF_PROG_TEST_RUN: u64 = 10;
+const BPF_PROG_GET_NEXT_ID: u64 = 11;
+const BPF_MAP_GET_NEXT_ID: u64 = 12;
+const BPF_PROG_GET_FD_BY_ID: u64 = 13;
+const BPF_MAP_GET_FD_BY_ID: u64 = 14;
+const BPF_OBJ_GET_INFO_BY_FD: u64 = 15;
+const BPF_RAW_TRACEPOINT_OPEN: u64 = 17;
+const BPF_BTF_LOAD: u64 = 18;
+const BPF_BTF_GET_FD_BY_ID: u64 = 19;
+const BPF_MAP_LOOKUP_AND_DELETE_ELEM: u64 = 21;
+const BPF_MAP_FREEZE: u64 = 22;
+const BPF_BTF_GET_NEXT_ID: u64 = 23;
+const BPF_LINK_CREATE: u64 = 28;
+const BPF_LINK_UPDATE: u64 = 29;
+const BPF_LINK_GET_FD_BY_ID: u64 = 30;
+const BPF_LINK_GET_NEXT_ID: u64 = 31;
+const BPF_ITER_CREATE: u64 = 33;
+const BPF_LINK_DETACH: u64 = 34;
+
+const COMMANDS: [&str; 37] = [
+    "BPF_MAP_CREATE",
+    "BPF_MAP_LOOKUP_ELEM",
+    "BPF_MAP_UPDATE_ELEM",
+    "BPF_MAP_DELETE_ELEM",
+    "BPF_MAP_GET_NEXT_KEY",
+    "BPF_PROG_LOAD",
+    "BPF_OBJ_PIN",
+    "BPF_OBJ_GET",
+    "BPF_PROG_ATTACH",
+    "BPF_PROG_DETACH",
+    "BPF_PROG_TEST_RUN",
+    "BPF_PROG_GET_NEXT_ID",
+    "BPF_MAP_GET_NEXT_ID",
+    "BPF_PROG_GET_FD_BY_ID",
+    "BPF_MAP_GET_FD_BY_ID",
+    "BPF_OBJ_GET_INFO_BY_FD",
+    "BPF_PROG_QUERY",
+    "BPF_RAW_TRACEPOINT_OPEN",
+    "BPF_BTF_LOAD",
+    "BPF_BTF_GET_FD_BY_ID",
+    "BPF_TASK_FD_QUERY",
+    "BPF_MAP_LOOKUP_AND_DELETE_ELEM",
+    "BPF_MAP_FREEZE",
+    "BPF_BTF_GET_NEXT_ID",
+    "BPF_MAP_LOOKUP_BATCH",
+    "BPF_MAP_LOOKUP_AND_DELETE_BATCH",
+    "BPF_MAP_UPDATE_BATCH",
+    "BPF_MAP_DELETE_BATCH",
+    "BPF_LINK_CREATE",
+    "BPF_LINK_UPDATE",
+    "BPF_LINK_GET_FD_BY_ID",
+    "BPF_LINK_GET_NEXT_ID",
+    "BPF_ENABLE_STATS",
+    "BPF_ITER_CREATE",
+    "BPF_LINK_DETACH",
+    "BPF_PROG_BIND_MAP",
+    "BPF_TOKEN_CREATE",
+];
+
+const MAP_TYPES: [&str; 34] = [
+    "BPF_MAP_TYPE_UNSPEC",
+    "BPF_MAP_TYPE_HASH",
+    "BPF_MAP_TYPE_ARRAY",
+    "BPF_MAP_TYPE_PROG_ARRAY",
+    "BPF_MAP_TYPE_PERF_EVENT_ARRAY",
+    "BPF_MAP_TYPE_PERCPU_HASH",
+    "BPF_MAP_TYPE_PERCPU_ARRAY",
+    "BPF_MAP_TYPE_STACK_TRACE",
+    "BPF_MAP_TYPE_CGROUP_ARRAY",
+    "BPF_MAP_TYPE_LRU_HASH",
+    "BPF_MAP_TYPE_LRU_PERCPU_HASH",
+    "BPF_MAP_TYPE_LPM_TRIE",
+    "BPF_MAP_TYPE_ARRAY_OF_MAPS",
+    "BPF_MAP_TYPE_HASH_OF_MAPS",
+    "BPF_MAP_TYPE_DEVMAP",
+    "BPF_MAP_TYPE_SOCKMAP",
+    "BPF_MAP_TYPE_CPUMAP",
+    "BPF_MAP_TYPE_XSKMAP",
+    "BPF_MAP_TYPE_SOCKHASH",
+    "BPF_MAP_TYPE_CGROUP_STORAGE",
+    "BPF_MAP_TYPE_REUSEPORT_SOCKARRAY",
+    "BPF_MAP_TYPE_PERCPU_CGROUP_STORAGE",
+    "BPF_MAP_TYPE_QUEUE",
+    "BPF_MAP_TYPE_STACK",
+    "BPF_MAP_TYPE_SK_STORAGE",
+    "BPF_MAP_TYPE_DEVMAP_HASH",
+    "BPF_MAP_TYPE_STRUCT_OPS",
+    "BPF_MAP_TYPE_RINGBUF",
+    "BPF_MAP_TYPE_INODE_STORAGE",
+    "BPF_MAP_TYPE_TASK_STORAGE",
+    "BPF_MAP_TYPE_BLOOM_FILTER",
+    "BPF_MAP_TYPE_USER_RINGBUF",
+    "BPF_MAP_TYPE_CGRP_STORAGE",
+    "BPF_MAP_TYPE_ARENA",
+];
+
+const PROG_TYPES: [&str; 33] = [
+    "BPF_PROG_TYPE_UNSPEC",
+    "BPF_PROG_TYPE_SOCKET_FILTER",
+    "BPF_PROG_TYPE_KPROBE",
+    "BPF_PROG_TYPE_SCHED_CLS",
+    "BPF_PROG_TYPE_SCHED_ACT",
+    "BPF_PROG_TYPE_TRACEPOINT",
+    "BPF_PROG_TYPE_XDP",
+    "BPF_PROG_TYPE_PERF_EVENT",
+    "BPF_PROG_TYPE_CGROUP_SKB",
+    "BPF_PROG_TYPE_CGROUP_SOCK",
+    "BPF_PROG_TYPE_LWT_IN",
+    "BPF_PROG_TYPE_LWT_OUT",
+    "BPF_PROG_TYPE_LWT_XMIT",
+    "BPF_PROG_TYPE_SOCK_OPS",
+    "BPF_PROG_TYPE_SK_SKB",
+    "BPF_PROG_TYPE_CGROUP_DEVICE",
+    "BPF_PROG_TYPE_SK_MSG",
+    "BPF_PROG_TYPE_RAW_TRACEPOINT",
+    "BPF_PROG_TYPE_CGROUP_SOCK_ADDR",
+    "BPF_PROG_TYPE_LWT_SEG6LOCAL",
+    "BPF_PROG_TYPE_LIRC_MODE2",
+    "BPF_PROG_TYPE_SK_REUSEPORT",
+    "BPF_PROG_TYPE_FLOW_DISSECTOR",
+    "BPF_PROG_TYPE_CGROUP_SYSCTL",
+    "BPF_PROG_TYPE_RAW_TRACEPOINT_WRITABLE",
+    "BPF_PROG_TYPE_CGROUP_SOCKOPT",
+    "BPF_PROG_TYPE_TRACING",
+    "BPF_PROG_TYPE_STRUCT_OPS",
+    "BPF_PROG_TYPE_EXT",
+    "BPF_PROG_TYPE_LSM",
+    "BPF_PROG_TYPE_SK_LOOKUP",
+    "BPF_PROG_TYPE_SYSCALL",
+    "BPF_PROG_TYPE_NETFILTER",
+];
+
+const ATTACH_TYPES: [&str; 50] = [
+    "BPF_CGROUP_INET_INGRESS",
+    "BPF_CGROUP_INET_EGRESS",
+    "BPF_CGROUP_INET_SOCK_CREATE",
+    "BPF_CGROUP_SOCK_OPS",
+    "BPF_SK_SKB_STREAM_PARSER",
+    "BPF_SK_SKB_STREAM_VERDICT",
+    "BPF_CGROUP_DEVICE",
+    "BPF_SK_MSG_VERDICT",
+    "BPF_CGROUP_INET4_BIND",
+    "BPF_CGROUP_INET6_BIND",
+    "BPF_CGROUP_INET4_CONNECT",
+    "BPF_CGROUP_INET6_CONNECT",
+    "BPF_CGROUP_INET4_POST_BIND",
+    "BPF_CGROUP_INET6_POST_BIND",
+    "BPF_CGROUP_UDP4_SENDMSG",
+    "BPF_CGROUP_UDP6_SENDMSG",
+    "BPF_LIRC_MODE2",
+    "BPF_FLOW_DISSECTOR",
+    "BPF_CGROUP_SYSCTL",
+    "BPF_CGROUP_UDP4_RECVMSG",
+    "BPF_CGROUP_UDP6_RECVMSG",
+    "BPF_CGROUP_GETSOCKOPT",
+    "BPF_CGROUP_SETSOCKOPT",
+    "BPF_TRACE_RAW_TP",
+    "BPF_TRACE_FENTRY",
+    "BPF_TRACE_FEXIT",
+    "BPF_MODIFY_RETURN",
+    "BPF_LSM_MAC",
+    "BPF_TRACE_ITER",
+    "BPF_CGROUP_INET4_GETPEERNAME",
+    "BPF_CGROUP_INET6_GETPEERNAME",
+    "BPF_CGROUP_INET4_GETSOCKNAME",
+    "BPF_CGROUP_INET6_GETSOCKNAME",
+    "BPF_XDP_DEVMAP",
+    "BPF_CGROUP_INET_SOCK_RELEASE",
+    "BPF_XDP_CPUMAP",
+    "BPF_SK_LOOKUP",
+    "BPF_XDP",
+    "BPF_SK_SKB_VERDICT",
+    "BPF_SK_REUSEPORT_SELECT",
+    "BPF_SK_REUSEPORT_SELECT_OR_MIGRATE",
+    "BPF_PERF_EVENT",
+    "BPF_TRACE_KPROBE_MULTI",
+    "BPF_LSM_CGROUP",
+    "BPF_STRUCT_OPS",
+    "BPF_NETFILTER",
+    "BPF_TCX_INGRESS",
+    "BPF_TCX_EGRESS",
+    "BPF_TRACE_UPROBE_MULTI",
+    "BPF_CGROUP_UNIX_CONNECT",
+];
+
+// helper function ids commonly seen in `call` instructions
+const HELPERS: [(i32, &str); 24] = [
+    (1, "bpf_map_lookup_elem"),
+    (2, "bpf_map_update_elem"),
+    (3, "bpf_map_delete_elem"),
+    (4, "bpf_probe_read"),
+    (5, "bpf_ktime_get_ns"),
+    (6, "bpf_trace_printk"),
+    (7, "bpf_get_prandom_u32"),
+    (8, "bpf_get_smp_processor_id"),
+    (12, "bpf_tail_call"),
+    (14, "bpf_get_current_pid_tgid"),
+    (15, "bpf_get_current_uid_gid"),
+    (16, "bpf_get_current_comm"),
+    (23, "bpf_redirect"),
+    (25, "bpf_perf_event_output"),
+    (26, "bpf_skb_load_bytes"),
+    (35, "bpf_get_current_task"),
+    (45, "bpf_probe_read_str"),
+    (51, "bpf_redirect_map"),
+    (112, "bpf_probe_read_kernel"),
+    (113, "bpf_probe_read_user"),
+    (114, "bpf_probe_read_user_str"),
+    (130, "bpf_ringbuf_output"),
+    (131, "bpf_ringbuf_reserve"),
+    (132, "bpf_ringbuf_submit"),
+];
+
+// the largest bpf_attr member read, anything after it is not needed for decoding
+const ATTR_SIZE: usize = 72;
+
+// verifier logs can be very long, only their end is relevant to why the load failed
+const VERIFIER_LOG_LINES: usize = 40;
+
+// the instruction count is read from the tracee, the listing stops at the classic program size limit
+const BPF_MAXINSNS: usize = 4096;
+
+fn name_or_number(names: &[&str], value: u32) -> ColoredString {
+    match names.get(value as usize) {
+        Some(name) => name.yellow(),
+        None => format!("#{value}").yellow(),
+    }
+}
+
+fn u32_at(attr: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes(attr[offset..offset + 4].try_into().unwrap())
+}
+
+fn u64_at(attr: &[u8], offset: usize) -> u64 {
+    u64::from_ne_bytes(attr[offset..offset + 8].try_into().unwrap())
+}
+
+fn name_at(attr: &[u8], offset: usize) -> String {
+    let name = attr[offset..offset + 16]
+        .iter()
+        .take_while(|byte| **byte != 0)
+        .copied()
+        .collect::<Vec<u8>>();
+    String::from_utf8_lossy(&name).into_owned()
+}
+
+fn read_text(address: u64, child: Pid) -> String {
+    SyscallObject::read_bytes_until_null(address as usize, child)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
+
+fn register(register: u8, wide: bool) -> String {
+    if wide {
+        format!("r{register}")
+    } else {
+        format!("w{register}")
+    }
+}
+
+fn size_name(code: u8) -> &'static str {
+    match code & 0x18 {
+        0x00 => "u32",
+        0x08 => "u16",
+        0x10 => "u8",
+        _ => "u64",
+    }
+}
+
+fn memory_operand(size: &str, base: u8, offset: i16) -> String {
+    if offset < 0 {
+        format!("*({size} *)(r{base} - {})", -(offset as i32))
+    } else {
+        format!("*({size} *)(r{base} + {offset})")
+    }
+}
+
+// renders eBPF instructions in the syntax used by the kernel verifier and llvm-objdump
+fn disassemble(instructions: &[u8]) -> Vec<String> {
+    let mut lines = vec![];
+    let mut index = 0;
+    let count = instructions.len() / 8;
+    while index < count {
+        let instruction = &instructions[index * 8..index * 8 + 8];
+        let code = instruction[0];
+        let destination = instruction[1] & 0x0F;
+        let source = instruction[1] >> 4;
+        let offset = i16::from_ne_bytes([instruction[2], instruction[3]]);
+        let immediate = i32::from_ne_bytes(instruction[4..8].try_into().unwrap());
+        let uses_register = (code & 0x08) == 0x08;
+        let text = match code & 0x07 {
+            // BPF_ALU and BPF_ALU64
+            0x04 | 0x07 => {
+                let wide = (code & 0x07) == 0x07;
+                let target = register(destination, wide);
+                let operand = if uses_register {
+                    register(source, wide)
+                } else {
+                    format!("{immediate}")
+                };
+                match code & 0xF0 {
+                    0x80 => format!("{target} = -{target}"),
+                    // BPF_END, the source bit picks the byte order and ALU64 always swaps
+                    0xD0 => {
+                        let swap = match (wide, uses_register) {
+                            (true, _) => "bswap",
+                            (false, true) => "be",
+                            (false, false) => "le",
+                        };
+                        format!("r{destination} = {swap}{immediate} r{destination}")
+                    }
+                    operation => {
+                        let operator = match operation {
+                            0x00 => "+=",
+                            0x10 => "-=",
+                            0x20 => "*=",
+                            0x30 => "/=",
+                            0x40 => "|=",
+                            0x50 => "&=",
+                            0x60 => "<<=",
+                            0x70 => ">>=",
+                            0x90 => "%=",
+                            0xA0 => "^=",
+                            0xB0 => "=",
+                            0xC0 => "s>>=",
+                            _ => "?=",
+                        };
+                        format!("{target} {operator} {operand}")
+                    }
+                }
+            }
+            // BPF_JMP and BPF_JMP32
+            0x05 | 0x06 => {
+                let wide = (code & 0x07) == 0x05;
+                match code & 0xF0 {
+                    0x00 => format!("goto {offset:+}"),
+                    0x80 => match HELPERS.iter().find(|(id, _)| *id == immediate) {
+                        Some((_, helper)) => format!("call {helper}#{immediate}"),
+                        None => format!("call #{immediate}"),
+                    },
+                    0x90 => "exit".to_owned(),
+                    operation => {
+                        let operator = match operation {
+                            0x10 => "==",
+                            0x20 => ">",
+                            0x30 => ">=",
+                            0x40 => "&",
+                            0x50 => "!=",
+                            0x60 => "s>",
+                            0x70 => "s>=",
+                            0xA0 => "<",
+                            0xB0 => "<=",
+                            0xC0 => "s<",
+                            0xD0 => "s<=",
+                            _ => "?",
+                        };
+                        let operand = if uses_register {
+                            register(source, wide)
+                        } else {
+                            format!("{immediate:#x}")
+                        };
+                        format!(
+                            "if {} {operator} {operand} goto {offset:+}",
+                            register(destination, wide)
+                        )
+                    }
+                }
+            }
+            // BPF_LDX
+            0x01 => format!(
+                "r{destination} = {}",
+                memory_operand(size_name(code), source, offset)
+            ),
+            // BPF_ST
+            0x02 => format!(
+                "{} = {immediate}",
+                memory_operand(size_name(code), destination, offset)
+            ),
+            // BPF_STX, with BPF_ATOMIC as the mode for atomic operations
+            0x03 => {
+                let memory = memory_operand(size_name(code), destination, offset);
+                if (code & 0xE0) == 0xC0 {
+                    format!("lock {memory} += r{source}")
+                } else {
+                    format!("{memory} = r{source}")
+                }
+            }
+            // BPF_LD, only the 16 Byte immediate load is still in use
+            _ if code == 0x18 && index + 1 < count => {
+                let high = i32::from_ne_bytes(
+                    instructions[index * 8 + 12..index * 8 + 16]
+                        .try_into()
+                        .unwrap(),
+                );
+                let value = ((high as u64) << 32) | immediate as u32 as u64;
+                index += 1;
+                match source {
+                    1 => format!("r{destination} = map[fd {immediate}]"),
+                    2 => format!("r{destination} = map[fd {immediate}] value"),
+                    _ => format!("r{destination} = {value:#x} ll"),
+                }
+            }
+            _ => format!("unknown instruction {code:#04x}"),
+        };
+        lines.push(text);
+        index += 1;
+    }
+    lines
+}
+
+impl SyscallObject {
+    fn read_bpf_attr(&self) -> Option<Vec<u8>> {
+        let size = (self.args[2] as usize).min(ATTR_SIZE);
+        let mut attr =
+            SyscallObject::read_bytes_specific_length(self.args[1] as usize, self.child, size)?;
+        attr.resize(ATTR_SIZE, 0);
+        Some(attr)
+    }
+
+    fn bpf_fd(&self, fd: u32) -> ColoredString {
+        match SyscallObject::style_file_descriptor(fd as u64, self.child) {
+            Some(styled) => styled.normal(),
+            None => fd.to_string().yellow(),
+        }
+    }
+
+    pub(crate) fn bpf_command(&self) -> ColoredString {
+        name_or_number(&COMMANDS, self.args[0] as u32)
+    }
+
+    pub(crate) fn bpf_intent(&self) -> Vec<ColoredString> {
+        let Some(attr) = self.read_bpf_attr() else {
+            return vec!["perform the bpf command ".white(), self.bpf_command()];
+        };
+        match self.args[0] {
+            BPF_MAP_CREATE => {
+                let mut intent = vec!["create a ".white()];
+                intent.push(name_or_number(&MAP_TYPES, u32_at(&attr, 0)));
+                let name = name_at(&attr, 28);
+                if !name.is_empty() {
+                    intent.push(" named ".white());
+                    intent.push(name.blue());
+                }
+                intent.extend(vec![
+                    " with ".white(),
+                    u32_at(&attr, 12).to_string().yellow(),
+                    " entries of ".white(),
+                    Bytes::from(u32_at(&attr, 4) as usize).to_string().yellow(),
+                    " keys and ".white(),
+                    Bytes::from(u32_at(&attr, 8) as usize).to_string().yellow(),
+                    " values".white(),
+                ]);
+                intent
+            }
+            BPF_MAP_LOOKUP_ELEM | BPF_MAP_LOOKUP_AND_DELETE_ELEM => {
+                let mut intent = vec![if self.args[0] == BPF_MAP_LOOKUP_ELEM {
+                    "look up an element in the map: ".white()
+                } else {
+                    "look up and delete an element in the map: ".white()
+                }];
+                intent.push(self.bpf_fd(u32_at(&attr, 0)));
+                intent
+            }
+            BPF_MAP_UPDATE_ELEM => {
+                let mut intent = vec!["update an element in the map: ".white()];
+                intent.push(self.bpf_fd(u32_at(&attr, 0)));
+                match u64_at(&attr, 24) {
+                    1 => intent.push(" (only if it does not exist)".white()),
+                    2 => intent.push(" (only if it already exists)".white()),
+                    4 => intent.push(" (lock the element)".white()),
+                    _ => {}
+                }
+                intent
+            }
+            BPF_MAP_DELETE_ELEM => vec![
+                "delete an element from the map: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+            ],
+            BPF_MAP_GET_NEXT_KEY => vec![
+                "get the next key in the map: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+            ],
+            BPF_MAP_FREEZE => vec![
+                "make the map read-only for programs: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+            ],
+            BPF_PROG_LOAD => {
+                let mut intent = vec!["load a ".white()];
+                intent.push(name_or_number(&PROG_TYPES, u32_at(&attr, 0)));
+                intent.push(" program".white());
+                let name = name_at(&attr, 48);
+                if !name.is_empty() {
+                    intent.push(" named ".white());
+                    intent.push(name.blue());
+                }
+                intent.push(" of ".white());
+                intent.push(u32_at(&attr, 4).to_string().yellow());
+                intent.push(" instructions".white());
+                let license = read_text(u64_at(&attr, 16), self.child);
+                if !license.is_empty() {
+                    intent.push(" licensed ".white());
+                    intent.push(license.yellow());
+                }
+                let attach_type = u32_at(&attr, 68);
+                if attach_type != 0 {
+                    intent.push(" expected to attach as ".white());
+                    intent.push(name_or_number(&ATTACH_TYPES, attach_type));
+                }
+                intent
+            }
+            BPF_OBJ_PIN => vec![
+                "pin the bpf object: ".white(),
+                self.bpf_fd(u32_at(&attr, 8)),
+                " to the path: ".white(),
+                read_text(u64_at(&attr, 0), self.child).yellow(),
+            ],
+            BPF_OBJ_GET => vec![
+                "open the bpf object pinned at: ".white(),
+                read_text(u64_at(&attr, 0), self.child).yellow(),
+            ],
+            BPF_PROG_ATTACH | BPF_PROG_DETACH => vec![
+                if self.args[0] == BPF_PROG_ATTACH {
+                    "attach the program: ".white()
+                } else {
+                    "detach the program: ".white()
+                },
+                self.bpf_fd(u32_at(&attr, 4)),
+                " to: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+                " as ".white(),
+                name_or_number(&ATTACH_TYPES, u32_at(&attr, 8)),
+            ],
+            BPF_LINK_CREATE => vec![
+                "link the program: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+                " to: ".white(),
+                self.bpf_fd(u32_at(&attr, 4)),
+                " as ".white(),
+                name_or_number(&ATTACH_TYPES, u32_at(&attr, 8)),
+            ],
+            BPF_LINK_UPDATE => vec![
+                "replace the program of the link: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+                " with: ".white(),
+                self.bpf_fd(u32_at(&attr, 4)),
+            ],
+            BPF_LINK_DETACH => vec!["detach the link: ".white(), self.bpf_fd(u32_at(&attr, 0))],
+            BPF_ITER_CREATE => vec![
+                "create an iterator for the link: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+            ],
+            BPF_PROG_TEST_RUN => vec![
+                "test run the program: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+            ],
+            BPF_PROG_GET_NEXT_ID | BPF_MAP_GET_NEXT_ID | BPF_BTF_GET_NEXT_ID
+            | BPF_LINK_GET_NEXT_ID => vec![
+                "get the id following ".white(),
+                u32_at(&attr, 0).to_string().yellow(),
+                " (".white(),
+                self.bpf_command(),
+                ")".white(),
+            ],
+            BPF_PROG_GET_FD_BY_ID
+            | BPF_MAP_GET_FD_BY_ID
+            | BPF_BTF_GET_FD_BY_ID
+            | BPF_LINK_GET_FD_BY_ID => vec![
+                "open the bpf object with id ".white(),
+                u32_at(&attr, 0).to_string().yellow(),
+                " (".white(),
+                self.bpf_command(),
+                ")".white(),
+            ],
+            BPF_OBJ_GET_INFO_BY_FD => vec![
+                "get information about the bpf object: ".white(),
+                self.bpf_fd(u32_at(&attr, 0)),
+            ],
+            BPF_RAW_TRACEPOINT_OPEN => vec![
+                "attach the program: ".white(),
+                self.bpf_fd(u32_at(&attr, 8)),
+                " to the raw tracepoint: ".white(),
+                read_text(u64_at(&attr, 0), self.child).yellow(),
+            ],
+            BPF_BTF_LOAD => vec![
+                "load ".white(),
+                Bytes::from(u32_at(&attr, 16) as usize).to_string().yellow(),
+                " of BTF type information".white(),
+            ],
+            _ => vec!["perform the bpf command ".white(), self.bpf_command()],
+        }
+    }
+
+    pub(crate) fn bpf_result(&self) -> Vec<ColoredString> {
+        let result = self.result.0.unwrap_or(0);
+        match self.args[0] {
+            BPF_MAP_CREATE => vec!["map created: ".green(), result.to_string().yellow()],
+            BPF_PROG_LOAD => vec!["program loaded: ".green(), result.to_string().yellow()],
+            BPF_LINK_CREATE => vec!["link created: ".green(), result.to_string().yellow()],
+            BPF_BTF_LOAD => vec!["BTF loaded: ".green(), result.to_string().yellow()],
+            BPF_OBJ_GET
+            | BPF_PROG_GET_FD_BY_ID
+            | BPF_MAP_GET_FD_BY_ID
+            | BPF_BTF_GET_FD_BY_ID
+            | BPF_LINK_GET_FD_BY_ID
+            | BPF_RAW_TRACEPOINT_OPEN
+            | BPF_ITER_CREATE => {
+                vec!["opened: ".green(), result.to_string().yellow()]
+            }
+            BPF_PROG_GET_NEXT_ID | BPF_MAP_GET_NEXT_ID | BPF_BTF_GET_NEXT_ID
+            | BPF_LINK_GET_NEXT_ID => match self.read_bpf_attr() {
+                Some(attr) => vec!["next id: ".green(), u32_at(&attr, 4).to_string().yellow()],
+                None => vec!["successful".green()],
+            },
+            BPF_PROG_TEST_RUN => match self.read_bpf_attr() {
+                Some(attr) => vec![
+                    "program returned: ".green(),
+                    u32_at(&attr, 4).to_string().yellow(),
+                ],
+                None => vec!["successful".green()],
+            },
+            BPF_MAP_LOOKUP_ELEM | BPF_MAP_LOOKUP_AND_DELETE_ELEM => {
+                vec!["element found".green()]
+            }
+            _ => vec!["successful".green()],
+        }
+    }
+
+    // the instructions of a loaded program, printed below the syscall when asked for
+    pub(crate) fn bpf_disassembly(&self) -> Vec<ColoredString> {
+        if !BPF_DISASSEMBLY.get() || self.args[0] != BPF_PROG_LOAD {
+            return vec![];
+        }
+        let Some(attr) = self.read_bpf_attr() else {
+            return vec![];
+        };
+        let count = u32_at(&attr, 4) as usize;
+        let listed = count.min(BPF_MAXINSNS);
+        let Some(instructions) = SyscallObject::read_bytes_specific_length(
+            u64_at(&attr, 8) as usize,
+            self.child,
+            listed * 8,
+        ) else {
+            return vec![];
+        };
+        let mut disassembly = vec![];
+        for (index, line) in disassemble(&instructions).into_iter().enumerate() {
+            disassembly.push(format!("\n\t{index:>4}: ").dimmed());
+            disassembly.push(line.bright_white());
+        }
+        if count > listed {
+            disassembly.push(format!("\n\t... {} more instructions", count - listed).dimmed());
+        }
+        disassembly
+    }
+
+    // the kernel verifier explains why a program was rejected in the log buffer
+    pub(crate) fn bpf_verifier_log(&self) -> Vec<ColoredString> {
+        if self.args[0] != BPF_PROG_LOAD {
+            return vec![];
+        }
+        let Some(attr) = self.read_bpf_attr() else {
+            return vec![];
+        };
+        let (log_level, log_size, log_buf) =
+            (u32_at(&attr, 24), u32_at(&attr, 28), u64_at(&attr, 32));
+        if log_level == 0 || log_size == 0 || log_buf == 0 {
+            return vec![];
+        }
+        let Some(log) = SyscallObject::read_bytes_specific_length(
+            log_buf as usize,
+            self.child,
+            log_size as usize,
+        ) else {
+            return vec![];
+        };
+        let log = log.split(|byte| *byte == 0).next().unwrap_or_default();
+        let log = String::from_utf8_lossy(log);
+        let lines = log.lines().collect::<Vec<&str>>();
+        let mut verifier_log = vec!["\n\tverifier log:".red()];
+        if lines.len() > VERIFIER_LOG_LINES {
+            verifier_log.push(
+                format!("\n\t... {} earlier lines", lines.len() - VERIFIER_LOG_LINES).dimmed(),
+            );
+        }
+        for line in lines
+            .iter()
+            .skip(lines.len().saturating_sub(VERIFIER_LOG_LINES))
+        {
+            verifier_log.push(format!("\n\t{line}").white());
+        }
+        verifier_log
+    }
+}
@@ -0,0 +1,283 @@
+// rendering of the data buffers passed to read, write and friends
+// strings are cut at -s bytes and shown escaped or in hex (-x, -xx), --dump-read/--dump-write print them whole
+use crate::{
+    syscall_object::SyscallObject,
+    types::DumpTarget,
+    utilities::{DUMP_READ, DUMP_WRITE, HEX_MODE, STRING_LIMIT},
+};
+use colored::{ColoredString, Colorize};
+use nix::unistd::Pid;
+use std::path::Path;
+use syscalls::Sysno;
+
+// -x shows strings holding anything but printable text in hex, -xx shows every string in hex
+pub const HEX_NON_PRINTABLE: u8 = 1;
+pub const HEX_ALL: u8 = 2;
+
+// the kernel refuses more iovecs than this in a single call
+const IOV_MAX: usize = 1024;
+
+const DUMP_LINE_BYTES: usize = 16;
+
+// vectored calls with more segments than this only have the first ones listed
+const SHOWN_SEGMENTS: usize = 16;
+
+const RWF_FLAGS: [(u64, &str); 8] = [
+    (0x01, "RWF_HIPRI: polled high priority IO"),
+    (0x02, "RWF_DSYNC: data synced to disk like O_DSYNC"),
+    (0x04, "RWF_SYNC: synced to disk like O_SYNC"),
+    (0x08, "RWF_NOWAIT: fail instead of waiting"),
+    (0x10, "RWF_APPEND: at the end of the file like O_APPEND"),
+    (0x20, "RWF_NOAPPEND: at the offset even with O_APPEND"),
+    (0x40, "RWF_ATOMIC: all or nothing, never torn"),
+    (
+        0x80,
+        "RWF_DONTCACHE: dropped from the page cache afterwards",
+    ),
+];
+
+fn is_printable(byte: u8) -> bool {
+    byte.is_ascii_graphic() || matches!(byte, b' ' | b'\n' | b'\t' | b'\r')
+}
+
+fn escape(byte: u8) -> String {
+    match byte {
+        b'\n' => "\\n".to_owned(),
+        b'\t' => "\\t".to_owned(),
+        b'\r' => "\\r".to_owned(),
+        b'"' => "\\\"".to_owned(),
+        b'\\' => "\\\\".to_owned(),
+        byte if byte.is_ascii_graphic() || byte == b' ' => (byte as char).to_string(),
+        byte => format!("\\x{byte:02x}"),
+    }
+}
+
+// a quoted string of at most STRING_LIMIT bytes of a buffer that held total bytes
+pub(crate) fn format_buffer(bytes: &[u8], total: usize) -> String {
+    let shown = &bytes[..bytes.len().min(STRING_LIMIT.get())];
+    let hex = match HEX_MODE.get() {
+        HEX_ALL => true,
+        HEX_NON_PRINTABLE => !bytes.iter().all(|byte| is_printable(*byte)),
+        _ => false,
+    };
+    let mut string = String::from("\"");
+    for byte in shown {
+        if hex {
+            string.push_str(&format!("\\x{byte:02x}"));
+        } else {
+            string.push_str(&escape(*byte));
+        }
+    }
+    string.push('"');
+    if shown.len() < total {
+        string.push_str("...");
+    }
+    string
+}
+
+// hexdump -C style lines, offset, 16 bytes in hex and the same bytes as text
+fn hexdump(bytes: &[u8]) -> Vec<ColoredString> {
+    let mut dump = vec![];
+    for (line, chunk) in bytes.chunks(DUMP_LINE_BYTES).enumerate() {
+        let mut hex = String::new();
+        for index in 0..DUMP_LINE_BYTES {
+            match chunk.get(index) {
+                Some(byte) => hex.push_str(&format!("{byte:02x} ")),
+                None => hex.push_str("   "),
+            }
+            if index == DUMP_LINE_BYTES / 2 - 1 {
+                hex.push(' ');
+            }
+        }
+        let text = chunk
+            .iter()
+            .map(|byte| {
+                if byte.is_ascii_graphic() || *byte == b' ' {
+                    *byte as char
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        dump.push(format!("\n\t{:08x}  ", line * DUMP_LINE_BYTES).dimmed());
+        dump.push(hex.yellow());
+        dump.push(format!(" |{text}|").white());
+    }
+    dump
+}
+
+fn wanted(targets: &[DumpTarget], child: Pid, fd: i32) -> bool {
+    targets.iter().any(|target| match target {
+        DumpTarget::Fd(wanted) => *wanted == fd,
+        DumpTarget::Path(wanted) => std::fs::read_link(format!("/proc/{child}/fd/{fd}"))
+            .map(|path| path == *wanted)
+            .unwrap_or(false),
+    })
+}
+
+pub(crate) fn dump_target(target: &str) -> DumpTarget {
+    match target.parse::<i32>() {
+        Ok(fd) => DumpTarget::Fd(fd),
+        Err(_) => DumpTarget::Path(
+            std::fs::canonicalize(target).unwrap_or_else(|_| Path::new(target).to_path_buf()),
+        ),
+    }
+}
+
+impl SyscallObject {
+    // the base and length of each struct iovec in an array of them
+    fn iovecs(&self, address: u64, count: u64) -> Option<Vec<(u64, usize)>> {
+        let count = (count as usize).min(IOV_MAX);
+        let iovecs =
+            SyscallObject::read_bytes_specific_length(address as usize, self.child, count * 16)?;
+        iovecs
+            .chunks(16)
+            .map(|iovec| {
+                let base = u64::from_ne_bytes(iovec[..8].try_into().ok()?);
+                let length = u64::from_ne_bytes(iovec[8..].try_into().ok()?);
+                Some((base, length as usize))
+            })
+            .collect()
+    }
+
+    // the first limit bytes spread over an array of iovecs
+    pub(crate) fn read_iovecs(&self, address: u64, count: u64, limit: usize) -> Option<Vec<u8>> {
+        let mut data = vec![];
+        for (base, length) in self.iovecs(address, count)? {
+            let length = length.min(limit - data.len());
+            if length == 0 {
+                continue;
+            }
+            data.extend(SyscallObject::read_bytes_specific_length(
+                base as usize,
+                self.child,
+                length,
+            )?);
+        }
+        Some(data)
+    }
+
+    // each segment of a successful vectored read or write, with how much of the returned count landed in it
+    pub(crate) fn iovec_segments(&self) -> Vec<ColoredString> {
+        let verb = match self.sysno {
+            Sysno::readv | Sysno::preadv | Sysno::preadv2 => "filled",
+            Sysno::writev | Sysno::pwritev | Sysno::pwritev2 => "written",
+            _ => return vec![],
+        };
+        let (Some(moved), None) = (self.result.0, self.errno) else {
+            return vec![];
+        };
+        let Some(iovecs) = self.iovecs(self.args[1], self.args[2]) else {
+            return vec![];
+        };
+        let mut remaining = moved as usize;
+        let mut segments = vec![];
+        for (index, (base, length)) in iovecs.iter().enumerate().take(SHOWN_SEGMENTS) {
+            let landed = remaining.min(*length);
+            remaining -= landed;
+            segments.push(format!("\n\tsegment {index}: ").white());
+            if landed == *length {
+                segments.push(format!("{length} Bytes").yellow());
+            } else {
+                segments.push(format!("{landed} of {length} Bytes").yellow());
+                segments.push(format!(" {verb}").white());
+            }
+            if landed == 0 {
+                continue;
+            }
+            if let Some(bytes) = SyscallObject::read_bytes_specific_length(
+                *base as usize,
+                self.child,
+                landed.min(STRING_LIMIT.get()),
+            ) {
+                segments.push(": ".white());
+                segments.push(format_buffer(&bytes, landed).yellow());
+            }
+        }
+        if iovecs.len() > SHOWN_SEGMENTS {
+            segments.push(
+                format!(
+                    "\n\t... and {} more segments",
+                    iovecs.len() - SHOWN_SEGMENTS
+                )
+                .white(),
+            );
+        }
+        segments
+    }
+
+    // the RWF_* flags of preadv2 and pwritev2
+    pub(crate) fn rwf_flags(&self) -> Vec<ColoredString> {
+        let flags = self.args[5];
+        let mut described = RWF_FLAGS
+            .iter()
+            .filter(|(bit, _)| (flags & bit) != 0)
+            .map(|(_, description)| description.to_string())
+            .collect::<Vec<String>>();
+        let known = RWF_FLAGS.iter().fold(0, |known, (bit, _)| known | bit);
+        if (flags & !known) != 0 {
+            described.push(format!("unknown flags {:#x}", flags & !known));
+        }
+        if described.is_empty() {
+            return vec![];
+        }
+        vec![" (".white(), described.join(", ").yellow(), ")".white()]
+    }
+
+    // the buffer at address, quoted and cut according to -s and -x
+    pub(crate) fn buffer_text(&self, address: u64, length: u64) -> String {
+        let length = length as usize;
+        match SyscallObject::read_bytes_specific_length(
+            address as usize,
+            self.child,
+            length.min(STRING_LIMIT.get()),
+        ) {
+            Some(bytes) => format_buffer(&bytes, length),
+            None => "\"\"".to_owned(),
+        }
+    }
+
+    // the data moved by a successful read or write, when its fd was picked with --dump-read or --dump-write
+    pub(crate) fn data_dump(&self) -> Vec<ColoredString> {
+        let Some(moved) = self.result.0 else {
+            return vec![];
+        };
+        let targets = match self.sysno {
+            Sysno::read
+            | Sysno::pread64
+            | Sysno::recvfrom
+            | Sysno::readv
+            | Sysno::preadv
+            | Sysno::preadv2 => &DUMP_READ,
+            Sysno::write
+            | Sysno::pwrite64
+            | Sysno::sendto
+            | Sysno::writev
+            | Sysno::pwritev
+            | Sysno::pwritev2 => &DUMP_WRITE,
+            _ => return vec![],
+        };
+        if moved == 0
+            || !targets.with_borrow(|targets| wanted(targets, self.child, self.args[0] as i32))
+        {
+            return vec![];
+        }
+        let data = match self.sysno {
+            Sysno::readv
+            | Sysno::preadv
+            | Sysno::preadv2
+            | Sysno::writev
+            | Sysno::pwritev
+            | Sysno::pwritev2 => self.read_iovecs(self.args[1], self.args[2], moved as usize),
+            _ => SyscallObject::read_bytes_specific_length(
+                self.args[1] as usize,
+                self.child,
+                moved as usize,
+            ),
+        };
+        match data {
+            Some(data) => hexdump(&data),
+            None => vec![],
+        }
+    }
+}
@@ -0,0 +1,272 @@
+// decoding of clone and clone3, clone3's struct clone_args and the legacy arguments read into the same request
+// the child is classified as a thread, a process, or a container-like process in new namespaces
+use crate::{
+    namespaces::namespace_names,
+    one_line_formatter::{anding_handler, directives_handler},
+    syscall_object::SyscallObject,
+    utilities::x86_signal_to_string,
+};
+use colored::{ColoredString, Colorize};
+use syscalls::Sysno;
+
+// the exit signal takes the low byte of the legacy flags
+const CSIGNAL: u64 = 0xff;
+const SIGCHLD: u64 = 17;
+
+const CLONE_VM: u64 = 0x100;
+const CLONE_PIDFD: u64 = 0x1000;
+const CLONE_VFORK: u64 = 0x4000;
+const CLONE_THREAD: u64 = 0x10000;
+const CLONE_INTO_CGROUP: u64 = 0x2_0000_0000;
+
+// what the child shares with its parent instead of getting a copy
+const SHARED: [(u64, &str); 5] = [
+    (0x400, "the file descriptor table"),
+    (0x200, "filesystem information"),
+    (0x8000_0000, "I/O context"),
+    (0x800, "the table of signal handlers"),
+    (0x40000, "sem-adj values"),
+];
+
+const DIRECTIVES: [(u64, &str); 10] = [
+    (0x8000, "inherit the same parent"),
+    (0x100000, "store the child TID in the parent's memory"),
+    (0x2000, "allow ptracing if parent is ptraced"),
+    (0x80000, "modify the thread local storage descriptor"),
+    (0x800000, "prevent forcing of CLONE_PTRACE"),
+    (CLONE_VFORK, "suspend parent execution as with vFork"),
+    // CLONE_CHILD_CLEARTID is what pthread_join waits on, the tid is zeroed and its futex woken
+    (
+        0x200000,
+        "clear TID on the child's memory on exit and wake the associated futex",
+    ),
+    (0x1000000, "store the child TID in child's memory"),
+    (0x1_0000_0000, "default all inherited signal handlers"),
+    (CLONE_PIDFD, "return a PIDFD for the child"),
+];
+
+// struct clone_args grew from 64 bytes, set_tid came with 80 and cgroup with 88
+const CLONE_ARGS_SIZE_VER0: usize = 64;
+const CLONE_ARGS_SIZE_DECODED: usize = 88;
+
+// the kernel refuses set_tid arrays longer than the pid namespace nesting limit
+const MAX_PID_NS_LEVEL: usize = 32;
+
+struct CloneRequest {
+    flags: u64,
+    exit_signal: u64,
+    pidfd: u64,
+    stack: u64,
+    // only clone3 says how large the stack is, the legacy stack argument points at its top
+    stack_size: Option<u64>,
+    set_tid: Vec<i32>,
+    cgroup: Option<i32>,
+}
+
+enum CloneKind {
+    Thread,
+    Vfork,
+    SharedMemory,
+    Container(String),
+    Process,
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    bytes
+        .get(offset..offset + 8)
+        .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+        .unwrap_or(0)
+}
+
+fn signal_name(signal: u64) -> String {
+    x86_signal_to_string(signal)
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("signal {signal}"))
+}
+
+fn clone_kind(flags: u64) -> CloneKind {
+    if (flags & CLONE_THREAD) != 0 {
+        CloneKind::Thread
+    } else if let Some(namespaces) = namespace_names(flags) {
+        CloneKind::Container(namespaces)
+    } else if (flags & CLONE_VFORK) != 0 {
+        CloneKind::Vfork
+    } else if (flags & CLONE_VM) != 0 {
+        CloneKind::SharedMemory
+    } else {
+        CloneKind::Process
+    }
+}
+
+impl SyscallObject {
+    fn clone_request(&self) -> Option<CloneRequest> {
+        if self.sysno == Sysno::clone {
+            // clone(flags, stack, parent_tid, child_tid, tls) on x86_64
+            let flags = self.args[0];
+            return Some(CloneRequest {
+                flags: flags & !CSIGNAL,
+                exit_signal: flags & CSIGNAL,
+                pidfd: self.args[2],
+                stack: self.args[1],
+                stack_size: None,
+                set_tid: vec![],
+                cgroup: None,
+            });
+        }
+        let size = (self.args[1] as usize).clamp(CLONE_ARGS_SIZE_VER0, CLONE_ARGS_SIZE_DECODED);
+        let bytes =
+            SyscallObject::read_bytes_specific_length(self.args[0] as usize, self.child, size)?;
+        let flags = u64_at(&bytes, 0);
+        let set_tid_size = (u64_at(&bytes, 72) as usize).min(MAX_PID_NS_LEVEL);
+        let set_tid = match u64_at(&bytes, 64) {
+            0 => vec![],
+            address => SyscallObject::read_bytes_specific_length(
+                address as usize,
+                self.child,
+                set_tid_size * 4,
+            )
+            .map(|tids| {
+                tids.chunks(4)
+                    .map(|tid| i32::from_ne_bytes(tid.try_into().unwrap()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        };
+        Some(CloneRequest {
+            flags,
+            exit_signal: u64_at(&bytes, 32),
+            pidfd: u64_at(&bytes, 8),
+            stack: u64_at(&bytes, 40),
+            stack_size: Some(u64_at(&bytes, 48)),
+            set_tid,
+            cgroup: ((flags & CLONE_INTO_CGROUP) != 0).then(|| u64_at(&bytes, 80) as i32),
+        })
+    }
+
+    pub(crate) fn clone_intent(&self) -> Vec<ColoredString> {
+        let Some(request) = self.clone_request() else {
+            return vec!["spawn a new child".white()];
+        };
+        let flags = request.flags;
+        let mut intent = vec![];
+        match clone_kind(flags) {
+            CloneKind::Thread => intent.push("spawn a new thread".white()),
+            CloneKind::Vfork => intent.push("spawn a new child process".white()),
+            CloneKind::SharedMemory => {
+                intent.push("spawn a new child process sharing the parent's memory".white())
+            }
+            CloneKind::Container(namespaces) => {
+                intent.push("spawn a container-like child process in a new ".white());
+                intent.push(namespaces.yellow());
+                intent.push(" namespace".white());
+            }
+            CloneKind::Process => intent.push("spawn a new child process".white()),
+        }
+        match (request.stack, request.stack_size) {
+            (0, _) => {}
+            (stack, Some(size)) => {
+                intent.push(" with a ".white());
+                intent.push(SyscallObject::style_bytes_page_aligned_ceil(size).yellow());
+                intent.push(" stack starting at ".white());
+                intent.push(format!("0x{stack:x}").yellow());
+            }
+            (stack, None) => {
+                intent.push(" with its stack top at ".white());
+                intent.push(format!("0x{stack:x}").yellow());
+            }
+        }
+
+        let shares = SHARED
+            .iter()
+            .filter(|(flag, _)| (flags & flag) != 0)
+            .map(|(_, shared)| shared.yellow())
+            .collect::<Vec<ColoredString>>();
+        if !shares.is_empty() {
+            intent.push(" (".white());
+            intent.push("share ".white());
+            anding_handler(shares, &mut intent);
+            intent.push(")".white());
+        }
+
+        let mut directives = vec![];
+        if (flags & CLONE_THREAD) != 0 {
+            directives.push("place in the same thread group".yellow());
+        } else {
+            directives.push("place in a new thread group".yellow());
+            match request.exit_signal {
+                0 => directives.push("send no signal to the parent on exit".yellow()),
+                SIGCHLD => {}
+                signal => directives.push(
+                    format!(
+                        "send {} to the parent on exit instead of SIGCHLD",
+                        signal_name(signal)
+                    )
+                    .yellow(),
+                ),
+            }
+        }
+        for (flag, directive) in DIRECTIVES {
+            if (flags & flag) != 0 {
+                directives.push(directive.yellow());
+            }
+        }
+        match request.set_tid.as_slice() {
+            [] => {}
+            [tid] => directives.push(format!("request the thread id {tid}").yellow()),
+            tids => directives.push(
+                format!(
+                    "request the thread ids {} from the innermost pid namespace outwards",
+                    tids.iter()
+                        .map(|tid| tid.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+                .yellow(),
+            ),
+        }
+        if let Some(cgroup) = request.cgroup {
+            let cgroup = SyscallObject::style_file_descriptor(cgroup as u64, self.child)
+                .filter(|cgroup| !cgroup.is_empty())
+                .unwrap_or_else(|| cgroup.to_string());
+            directives.push(format!("start in the cgroup {cgroup}").yellow());
+        }
+        directives_handler(directives, &mut intent);
+        intent
+    }
+
+    // the kind of child that was created, and the pidfd returned for it
+    pub(crate) fn clone_result(&self) -> Vec<ColoredString> {
+        let Some(request) = self.clone_request() else {
+            return vec![];
+        };
+        let mut result = vec![];
+        match clone_kind(request.flags) {
+            CloneKind::Thread => result.push(" (a thread of this process)".green()),
+            CloneKind::Vfork => {
+                result.push(" (a process borrowing this process until it execs or exits)".green())
+            }
+            CloneKind::SharedMemory => {
+                result.push(" (a process sharing the memory of this process)".green())
+            }
+            CloneKind::Container(namespaces) => {
+                result.push(" (a container-like process in a new ".green());
+                result.push(namespaces.yellow());
+                result.push(" namespace)".green());
+            }
+            CloneKind::Process => result.push(" (a new process)".green()),
+        }
+        if (request.flags & CLONE_PIDFD) != 0 {
+            if let Some(pidfd) = SyscallObject::read_bytes::<4>(request.pidfd as usize, self.child)
+            {
+                result.push(", pidfd: ".green());
+                result.push(i32::from_ne_bytes(pidfd).to_string().yellow());
+            }
+        }
+        result
+    }
+
+    pub(crate) fn creates_thread(&self) -> bool {
+        self.clone_request()
+            .is_some_and(|request| (request.flags & CLONE_THREAD) != 0)
+    }
+}
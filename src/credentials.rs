@@ -0,0 +1,440 @@
+// decoding of the syscalls changing user and group ids, supplementary groups and capabilities
+// the credentials are taken before the syscall runs so the output can show what changed
+use crate::{
+    error_explainer::{group_name, user_name},
+    syscall_object::SyscallObject,
+    types::Credentials,
+    utilities::{capability_to_string, CREDENTIALS},
+};
+use colored::{ColoredString, Colorize};
+use nix::{errno::Errno, unistd::Pid};
+use syscalls::Sysno;
+
+const CAP_SETGID: u64 = 6;
+const CAP_SETUID: u64 = 7;
+
+// the header versions of capget and capset, only the first one uses a single set of 32 bit masks
+const LINUX_CAPABILITY_VERSION_1: u32 = 0x1998_0330;
+
+// capability sets with more members than this are shown as what they lack
+const LISTED_CAPABILITIES: u32 = 20;
+
+// -1 leaves an id unchanged in setreuid, setresuid and their group variants
+const UNCHANGED: u32 = u32::MAX;
+
+fn read_credentials(pid: Pid) -> Option<Credentials> {
+    let status = procfs::process::Process::new(pid.as_raw())
+        .and_then(|process| process.status())
+        .ok()?;
+    Some(Credentials {
+        uids: [status.ruid, status.euid, status.suid, status.fuid],
+        gids: [status.rgid, status.egid, status.sgid, status.fgid],
+        groups: status.groups.iter().map(|group| *group as u32).collect(),
+        effective: status.capeff,
+        permitted: status.capprm,
+        inheritable: status.capinh,
+    })
+}
+
+fn is_credentials_syscall(sysno: Sysno) -> bool {
+    matches!(
+        sysno,
+        Sysno::setuid
+            | Sysno::setgid
+            | Sysno::setreuid
+            | Sysno::setregid
+            | Sysno::setresuid
+            | Sysno::setresgid
+            | Sysno::setfsuid
+            | Sysno::setfsgid
+            | Sysno::setgroups
+            | Sysno::capset
+    )
+}
+
+// taken when the syscall is entered, the exit compares against it
+pub fn snapshot_credentials(syscall: &SyscallObject) {
+    if !is_credentials_syscall(syscall.sysno) {
+        return;
+    }
+    if let Some(credentials) = read_credentials(syscall.child) {
+        CREDENTIALS.with_borrow_mut(|snapshots| snapshots.insert(syscall.child, credentials));
+    }
+}
+
+fn all_capabilities() -> u64 {
+    let last = std::fs::read_to_string("/proc/sys/kernel/cap_last_cap")
+        .ok()
+        .and_then(|last| last.trim().parse::<u32>().ok())
+        .unwrap_or(40);
+    1u64.checked_shl(last + 1).map_or(u64::MAX, |bit| bit - 1)
+}
+
+fn capability_names(set: u64) -> Vec<String> {
+    (0..64)
+        .filter(|capability| (set & (1 << capability)) != 0)
+        .map(|capability| match capability_to_string(capability) {
+            Some(name) => name.to_owned(),
+            None => format!("CAP_{capability}"),
+        })
+        .collect()
+}
+
+pub fn describe_capabilities(set: u64) -> String {
+    let all = all_capabilities();
+    if set == 0 {
+        "none".to_owned()
+    } else if (set & all) == all {
+        "all".to_owned()
+    } else if set.count_ones() > LISTED_CAPABILITIES {
+        format!("all except {}", capability_names(all & !set).join(", "))
+    } else {
+        capability_names(set).join(", ")
+    }
+}
+
+fn name_id(id: u32, group: bool) -> String {
+    if group {
+        group_name(id)
+    } else {
+        user_name(id)
+    }
+}
+
+fn id_list(ids: &[u32], group: bool) -> String {
+    ids.iter()
+        .map(|id| name_id(*id, group))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// an id argument of the set*id syscalls, -1 meaning unchanged
+fn requested_id(id: u64, group: bool) -> Option<String> {
+    if id as u32 == UNCHANGED {
+        None
+    } else {
+        Some(name_id(id as u32, group))
+    }
+}
+
+fn has_capability(credentials: &Credentials, capability: u64) -> bool {
+    (credentials.effective & (1 << capability)) != 0
+}
+
+// whether the change gives the process more or less power, only root ids and capabilities count
+fn privilege_verdict(before: &Credentials, after: &Credentials) -> Vec<ColoredString> {
+    let became_root = |ids_before: &[u32; 4], ids_after: &[u32; 4]| {
+        (0..3).any(|index| ids_before[index] != 0 && ids_after[index] == 0)
+    };
+    let gained = became_root(&before.uids, &after.uids)
+        || became_root(&before.gids, &after.gids)
+        || (after.effective & !before.effective) != 0
+        || (after.permitted & !before.permitted) != 0;
+    let dropped = became_root(&after.uids, &before.uids)
+        || became_root(&after.gids, &before.gids)
+        || (before.effective & !after.effective) != 0
+        || (before.permitted & !after.permitted) != 0;
+    let mut verdict = vec![];
+    if gained || dropped {
+        verdict.push(" ".white());
+    }
+    if gained {
+        verdict.push(" PRIVILEGES GAINED ".on_red());
+    }
+    if dropped {
+        // root stays in reach as long as it remains the real or saved user id
+        let regainable = after.uids[1] != 0 && (after.uids[0] == 0 || after.uids[2] == 0);
+        if regainable {
+            verdict.push(
+                " privileges dropped temporarily, root remains the real or saved user id "
+                    .on_yellow(),
+            );
+        } else {
+            verdict.push(" privileges dropped ".on_green());
+        }
+    }
+    verdict
+}
+
+fn describe_changes(before: &Credentials, after: &Credentials) -> Vec<ColoredString> {
+    let mut changes = vec![];
+    if before.uids[..3] != after.uids[..3] {
+        changes.push("\n\tuser ids (real, effective, saved): ".white());
+        changes.push(id_list(&before.uids[..3], false).yellow());
+        changes.push(" -> ".white());
+        changes.push(id_list(&after.uids[..3], false).yellow());
+    }
+    if before.gids[..3] != after.gids[..3] {
+        changes.push("\n\tgroup ids (real, effective, saved): ".white());
+        changes.push(id_list(&before.gids[..3], true).yellow());
+        changes.push(" -> ".white());
+        changes.push(id_list(&after.gids[..3], true).yellow());
+    }
+    if before.uids[3] != after.uids[3] {
+        changes.push("\n\tfilesystem user id: ".white());
+        changes.push(user_name(before.uids[3]).yellow());
+        changes.push(" -> ".white());
+        changes.push(user_name(after.uids[3]).yellow());
+    }
+    if before.gids[3] != after.gids[3] {
+        changes.push("\n\tfilesystem group id: ".white());
+        changes.push(group_name(before.gids[3]).yellow());
+        changes.push(" -> ".white());
+        changes.push(group_name(after.gids[3]).yellow());
+    }
+    if before.groups != after.groups {
+        let groups = |groups: &[u32]| {
+            if groups.is_empty() {
+                "none".to_owned()
+            } else {
+                id_list(groups, true)
+            }
+        };
+        changes.push("\n\tsupplementary groups: ".white());
+        changes.push(groups(&before.groups).yellow());
+        changes.push(" -> ".white());
+        changes.push(groups(&after.groups).yellow());
+    }
+    for (name, set_before, set_after) in [
+        ("effective", before.effective, after.effective),
+        ("permitted", before.permitted, after.permitted),
+        ("inheritable", before.inheritable, after.inheritable),
+    ] {
+        if set_before == set_after {
+            continue;
+        }
+        if set_before == 0 || set_after == 0 {
+            changes.push(format!("\n\t{name} capabilities: ").white());
+            changes.push(describe_capabilities(set_before).yellow());
+            changes.push(" -> ".white());
+            changes.push(describe_capabilities(set_after).yellow());
+            continue;
+        }
+        // large sets differ in a few capabilities, which are easier to read on their own
+        let lost = set_before & !set_after;
+        let gained = set_after & !set_before;
+        if lost != 0 {
+            changes.push(format!("\n\t{name} capabilities lost: ").white());
+            changes.push(capability_names(lost).join(", ").yellow());
+        }
+        if gained != 0 {
+            changes.push(format!("\n\t{name} capabilities gained: ").white());
+            changes.push(capability_names(gained).join(", ").yellow());
+        }
+    }
+    changes
+}
+
+impl SyscallObject {
+    fn credentials_before(&self) -> Option<Credentials> {
+        CREDENTIALS.with_borrow(|snapshots| snapshots.get(&self.child).cloned())
+    }
+
+    // the three capability sets of a capget or capset data argument
+    fn read_capability_sets(&self, header: u64, data: u64) -> Option<(u64, u64, u64)> {
+        let header = SyscallObject::read_bytes::<8>(header as usize, self.child)?;
+        let version = u32::from_ne_bytes(header[0..4].try_into().unwrap());
+        let length = if version == LINUX_CAPABILITY_VERSION_1 {
+            12
+        } else {
+            24
+        };
+        let data = SyscallObject::read_bytes_specific_length(data as usize, self.child, length)?;
+        let word = |index: usize| {
+            u32::from_ne_bytes(data[index * 4..index * 4 + 4].try_into().unwrap()) as u64
+        };
+        let set = |index: usize| {
+            if length == 24 {
+                word(index) | (word(index + 3) << 32)
+            } else {
+                word(index)
+            }
+        };
+        Some((set(0), set(1), set(2)))
+    }
+
+    fn capability_target(&self) -> String {
+        SyscallObject::read_bytes::<8>(self.args[0] as usize, self.child)
+            .map(|header| i32::from_ne_bytes(header[4..8].try_into().unwrap()))
+            .filter(|pid| *pid != 0)
+            .map(|pid| format!("the process {pid}"))
+            .unwrap_or("the calling thread".to_owned())
+    }
+
+    pub(crate) fn credentials_intent(&self) -> Vec<ColoredString> {
+        let before = self.credentials_before();
+        let group = matches!(
+            self.sysno,
+            Sysno::setgid | Sysno::setregid | Sysno::setresgid | Sysno::setfsgid
+        );
+        let kind = if group { "group" } else { "user" };
+        match self.sysno {
+            Sysno::setuid | Sysno::setgid => {
+                let capability = if group { CAP_SETGID } else { CAP_SETUID };
+                let privileged = before
+                    .as_ref()
+                    .is_some_and(|before| has_capability(before, capability));
+                let mut intent = vec![
+                    format!("set the effective {kind} id to ").white(),
+                    name_id(self.args[0] as u32, group).yellow(),
+                ];
+                if privileged {
+                    intent.push(" (and the real and saved ones, the process has ".white());
+                    intent.push(
+                        capability_to_string(capability)
+                            .unwrap_or_default()
+                            .yellow(),
+                    );
+                    intent.push(")".white());
+                }
+                intent
+            }
+            Sysno::setreuid | Sysno::setregid | Sysno::setresuid | Sysno::setresgid => {
+                let mut parts = vec![];
+                for (index, name) in ["real", "effective", "saved"].iter().enumerate() {
+                    if index == 2 && matches!(self.sysno, Sysno::setreuid | Sysno::setregid) {
+                        continue;
+                    }
+                    if let Some(id) = requested_id(self.args[index], group) {
+                        parts.push((*name, id));
+                    }
+                }
+                if parts.is_empty() {
+                    return vec![format!("leave the {kind} ids unchanged").white()];
+                }
+                let mut intent = vec![format!("set the {kind} ids: ").white()];
+                for (index, (name, id)) in parts.into_iter().enumerate() {
+                    if index > 0 {
+                        intent.push(", ".white());
+                    }
+                    intent.push(format!("{name} to ").white());
+                    intent.push(id.yellow());
+                }
+                intent
+            }
+            Sysno::setfsuid | Sysno::setfsgid => vec![
+                format!("set the {kind} id used for filesystem checks to ").white(),
+                name_id(self.args[0] as u32, group).yellow(),
+            ],
+            Sysno::setgroups => {
+                let count = self.args[0] as usize;
+                if count == 0 {
+                    return vec!["drop all supplementary groups".white()];
+                }
+                let groups = SyscallObject::read_bytes_specific_length(
+                    self.args[1] as usize,
+                    self.child,
+                    count.min(64) * 4,
+                )
+                .map(|bytes| {
+                    bytes
+                        .chunks_exact(4)
+                        .map(|gid| u32::from_ne_bytes(gid.try_into().unwrap()))
+                        .collect::<Vec<u32>>()
+                })
+                .unwrap_or_default();
+                let mut list = id_list(&groups, true);
+                if count > 64 {
+                    list.push_str(&format!(" and {} more", count - 64));
+                }
+                vec!["set the supplementary groups to: ".white(), list.yellow()]
+            }
+            Sysno::capget => vec![
+                "get the capabilities of ".white(),
+                self.capability_target().yellow(),
+            ],
+            Sysno::capset => match self.read_capability_sets(self.args[0], self.args[1]) {
+                Some((effective, permitted, inheritable)) => vec![
+                    "set the capabilities of ".white(),
+                    self.capability_target().yellow(),
+                    " to effective: ".white(),
+                    describe_capabilities(effective).yellow(),
+                    ", permitted: ".white(),
+                    describe_capabilities(permitted).yellow(),
+                    ", inheritable: ".white(),
+                    describe_capabilities(inheritable).yellow(),
+                ],
+                None => vec![
+                    "set the capabilities of ".white(),
+                    self.capability_target().yellow(),
+                ],
+            },
+            _ => vec![],
+        }
+    }
+
+    pub(crate) fn credentials_result(&self) -> Vec<ColoredString> {
+        if self.sysno == Sysno::capget {
+            return match self.read_capability_sets(self.args[0], self.args[1]) {
+                Some((effective, permitted, inheritable)) => vec![
+                    "effective: ".green(),
+                    describe_capabilities(effective).yellow(),
+                    ", permitted: ".green(),
+                    describe_capabilities(permitted).yellow(),
+                    ", inheritable: ".green(),
+                    describe_capabilities(inheritable).yellow(),
+                ],
+                None => vec!["capabilities retrieved".green()],
+            };
+        }
+        let (Some(before), Some(after)) = (self.credentials_before(), read_credentials(self.child))
+        else {
+            return vec!["successful".green()];
+        };
+        let mut result = vec![];
+        if matches!(self.sysno, Sysno::setfsuid | Sysno::setfsgid) {
+            // these never fail, they return the previous id and only show failure by not changing it
+            let (ids, group) = if self.sysno == Sysno::setfsuid {
+                (after.uids, false)
+            } else {
+                (after.gids, true)
+            };
+            if ids[3] == self.args[0] as u32 {
+                result.push("changed".green());
+            } else {
+                result.push("not changed, the id is not one of the process's ids and it lacks the capability".red());
+            }
+            result.push(", previous id: ".green());
+            result.push(name_id(self.result.0.unwrap_or(0) as u32, group).yellow());
+        } else if before == after {
+            result.push("successful, nothing changed".green());
+        } else {
+            result.push("successful".green());
+        }
+        result.extend(privilege_verdict(&before, &after));
+        result.extend(describe_changes(&before, &after));
+        result
+    }
+
+    // an unprivileged process can only switch between the ids it already has
+    pub(crate) fn credentials_denial(&self) -> Option<Vec<ColoredString>> {
+        if self.errno != Some(Errno::EPERM) || !is_credentials_syscall(self.sysno) {
+            return None;
+        }
+        let before = self.credentials_before()?;
+        let (capability, ids, group) = match self.sysno {
+            Sysno::setuid | Sysno::setreuid | Sysno::setresuid => (CAP_SETUID, before.uids, false),
+            Sysno::setgid | Sysno::setregid | Sysno::setresgid | Sysno::setgroups => {
+                (CAP_SETGID, before.gids, true)
+            }
+            Sysno::capset => {
+                return Some(vec![
+                    " (capabilities can only be added to the effective and permitted sets if already permitted, and to the inheritable set if in the bounding set or with CAP_SETPCAP)".white(),
+                ])
+            }
+            _ => return None,
+        };
+        let mut denial = vec![
+            " (the process lacks ".white(),
+            capability_to_string(capability)
+                .unwrap_or_default()
+                .yellow(),
+        ];
+        if self.sysno != Sysno::setgroups {
+            denial.push(" and can only switch between its real, effective and saved ids: ".white());
+            denial.push(id_list(&ids[..3], group).yellow());
+        }
+        denial.push(")".white());
+        Some(denial)
+    }
+}
@@ -0,0 +1,326 @@
+// the interest set of every epoll instance, kept from epoll_create, epoll_ctl and close
+// used to warn about registrations that are likely bugs and for the --epoll-state report at exit
+use crate::{
+    readiness::{describe_events, epoll_interest, EPOLL_EVENT_SIZE},
+    syscall_object::SyscallObject,
+    threads::thread_group,
+    types::EpollRegistration,
+    utilities::{EPOLL_INTEREST, EPOLL_STATE},
+};
+use colored::{ColoredString, Colorize};
+use nix::{errno::Errno, libc::O_NONBLOCK, unistd::Pid};
+use std::{collections::BTreeMap, os::unix::fs::MetadataExt};
+use syscalls::Sysno;
+
+const EPOLL_CTL_ADD: u64 = 1;
+const EPOLL_CTL_DEL: u64 = 2;
+const EPOLL_CTL_MOD: u64 = 3;
+
+const EPOLLET: u32 = 1 << 31;
+
+const INPUT_FLAGS: [(u32, &str); 4] = [
+    (1 << 28, "exclusive wakeups"),
+    (1 << 29, "keeping the system awake"),
+    (1 << 30, "one shot"),
+    (EPOLLET, "edge triggered"),
+];
+
+// the events a registration asks for, and how they are delivered
+pub(crate) fn describe_interest(events: u32) -> String {
+    let mut described = vec![describe_events(events)];
+    for (bit, description) in INPUT_FLAGS {
+        if (events & bit) != 0 {
+            described.push(description.to_owned());
+        }
+    }
+    described.retain(|description| !description.is_empty());
+    if described.is_empty() {
+        "no events".to_owned()
+    } else {
+        described.join(", ")
+    }
+}
+
+fn read_event(address: u64, child: Pid) -> Option<(u32, u64)> {
+    let event =
+        SyscallObject::read_bytes_specific_length(address as usize, child, EPOLL_EVENT_SIZE)?;
+    let events = u32::from_ne_bytes(event[..4].try_into().ok()?);
+    let data = u64::from_ne_bytes(event[EPOLL_EVENT_SIZE - 8..].try_into().ok()?);
+    Some((events, data))
+}
+
+fn is_blocking(child: Pid, fd: i32) -> bool {
+    let Ok(fdinfo) = std::fs::read_to_string(format!("/proc/{child}/fdinfo/{fd}")) else {
+        return false;
+    };
+    fdinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("flags:"))
+        .and_then(|flags| i32::from_str_radix(flags.trim(), 8).ok())
+        .is_some_and(|flags| (flags & O_NONBLOCK) == 0)
+}
+
+fn file_identity(child: Pid, fd: i32) -> Option<(u64, u64)> {
+    let metadata = std::fs::metadata(format!("/proc/{child}/fd/{fd}")).ok()?;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+// the fd table, and with it every epoll instance, is shared by the threads of a process
+fn registration(child: Pid, epfd: i32, fd: i32) -> Option<EpollRegistration> {
+    EPOLL_INTEREST.with_borrow(|instances| {
+        instances
+            .get(&(thread_group(child), epfd))?
+            .get(&fd)
+            .cloned()
+    })
+}
+
+pub fn track_epoll(syscall: &SyscallObject) {
+    if syscall.errno.is_some() {
+        return;
+    }
+    let child = syscall.child;
+    let process = thread_group(child);
+    match syscall.sysno {
+        Sysno::epoll_create | Sysno::epoll_create1 => {
+            let Some(epfd) = syscall.result.0 else {
+                return;
+            };
+            EPOLL_INTEREST.with_borrow_mut(|instances| {
+                instances.insert((process, epfd as i32), BTreeMap::new())
+            });
+        }
+        // a new process gets a copy of the fd table, and with it the interest sets
+        Sysno::fork | Sysno::vfork | Sysno::clone | Sysno::clone3 => {
+            let Some(forked) = syscall.result.0 else {
+                return;
+            };
+            if forked == 0
+                || (syscall.sysno != Sysno::fork
+                    && syscall.sysno != Sysno::vfork
+                    && syscall.creates_thread())
+            {
+                return;
+            }
+            let forked = Pid::from_raw(forked as i32);
+            EPOLL_INTEREST.with_borrow_mut(|instances| {
+                let inherited = instances
+                    .iter()
+                    .filter(|((pid, _), _)| *pid == process)
+                    .map(|((_, epfd), interest)| ((forked, *epfd), interest.clone()))
+                    .collect::<Vec<_>>();
+                // registrations the child made before the return of the parent was seen are kept
+                for (key, interest) in inherited {
+                    let copy = instances.entry(key).or_default();
+                    for (fd, registration) in interest {
+                        copy.entry(fd).or_insert(registration);
+                    }
+                }
+            });
+        }
+        Sysno::epoll_ctl => {
+            let epfd = syscall.args[0] as i32;
+            let fd = syscall.args[2] as i32;
+            let event = read_event(syscall.args[3], child);
+            EPOLL_INTEREST.with_borrow_mut(|instances| {
+                let interest = instances.entry((process, epfd)).or_default();
+                match (syscall.args[1], event) {
+                    (EPOLL_CTL_ADD, Some((events, data))) => {
+                        let target = SyscallObject::style_file_descriptor(fd as u64, child)
+                            .filter(|target| !target.is_empty())
+                            .unwrap_or_else(|| fd.to_string());
+                        interest.insert(
+                            fd,
+                            EpollRegistration {
+                                target,
+                                events,
+                                data,
+                                closed: false,
+                            },
+                        );
+                    }
+                    (EPOLL_CTL_MOD, Some((events, data))) => {
+                        if let Some(registration) = interest.get_mut(&fd) {
+                            registration.events = events;
+                            registration.data = data;
+                        }
+                    }
+                    (EPOLL_CTL_DEL, _) => {
+                        interest.remove(&fd);
+                    }
+                    _ => {}
+                }
+            });
+        }
+        // the kernel drops a registration once the file is closed everywhere,
+        // one that is still in the fdinfo of the instance lives on through a duplicate of the fd
+        Sysno::close => {
+            let fd = syscall.args[0] as i32;
+            EPOLL_INTEREST.with_borrow_mut(|instances| {
+                instances.remove(&(process, fd));
+                for ((pid, epfd), interest) in instances.iter_mut() {
+                    if *pid != process || !interest.contains_key(&fd) {
+                        continue;
+                    }
+                    let survives = epoll_interest(*epfd, child)
+                        .iter()
+                        .any(|(registered, _)| *registered == fd);
+                    if survives {
+                        if let Some(registration) = interest.get_mut(&fd) {
+                            registration.closed = true;
+                        }
+                    } else {
+                        interest.remove(&fd);
+                    }
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+impl SyscallObject {
+    // what epoll_ctl asks for, "for readable, edge triggered events (data 0x7)"
+    pub(crate) fn epoll_ctl_interest(&self) -> Vec<ColoredString> {
+        if self.args[1] == EPOLL_CTL_DEL {
+            return vec![];
+        }
+        let Some((events, data)) = read_event(self.args[3], self.child) else {
+            return vec![];
+        };
+        vec![
+            " for ".white(),
+            describe_interest(events).yellow(),
+            " events (data ".white(),
+            format!("{data:#x}").yellow(),
+            ")".white(),
+        ]
+    }
+
+    // likely event loop bugs, on the epoll_ctl or close that introduced them
+    pub(crate) fn epoll_warnings(&self) -> Vec<ColoredString> {
+        let mut warnings = vec![];
+        let mut warn = |text: String| {
+            warnings.push("\n\twarning: ".bright_yellow());
+            warnings.push(text.white());
+        };
+        match self.sysno {
+            Sysno::epoll_ctl if self.args[1] != EPOLL_CTL_DEL => {
+                let epfd = self.args[0] as i32;
+                let fd = self.args[2] as i32;
+                let Some(registration) = registration(self.child, epfd, fd) else {
+                    return vec![];
+                };
+                if (registration.events & EPOLLET) != 0 && is_blocking(self.child, fd) {
+                    warn(format!(
+                        "fd {fd} is edge triggered but blocking, draining it until EAGAIN will block instead"
+                    ));
+                }
+                if self.args[1] == EPOLL_CTL_ADD {
+                    let identity = file_identity(self.child, fd);
+                    let duplicates = EPOLL_INTEREST.with_borrow(|instances| {
+                        instances
+                            .get(&(thread_group(self.child), epfd))
+                            .into_iter()
+                            .flatten()
+                            .filter(|(other, registration)| {
+                                **other != fd
+                                    && !registration.closed
+                                    && identity.is_some()
+                                    && file_identity(self.child, **other) == identity
+                            })
+                            .map(|(other, _)| other.to_string())
+                            .collect::<Vec<String>>()
+                    });
+                    if !duplicates.is_empty() {
+                        warn(format!(
+                            "the same file is already registered in epoll instance {epfd} as fd {}, its events will be reported for each",
+                            duplicates.join(", fd ")
+                        ));
+                    }
+                }
+            }
+            Sysno::close => {
+                let fd = self.args[0] as i32;
+                let process = thread_group(self.child);
+                EPOLL_INTEREST.with_borrow(|instances| {
+                    for ((pid, epfd), interest) in instances {
+                        if *pid == process
+                            && interest.get(&fd).is_some_and(|registration| registration.closed)
+                        {
+                            warn(format!(
+                                "fd {fd} is still registered in epoll instance {epfd} through a duplicate of it, its events keep arriving and it can no longer be removed with EPOLL_CTL_DEL"
+                            ));
+                        }
+                    }
+                });
+            }
+            _ => {}
+        }
+        warnings
+    }
+
+    pub(crate) fn epoll_denial(&self) -> Option<Vec<ColoredString>> {
+        if self.sysno != Sysno::epoll_ctl {
+            return None;
+        }
+        let epfd = self.args[0] as i32;
+        let fd = self.args[2] as i32;
+        let explain = |text: String| Some(vec![" (".white(), text.yellow(), ")".white()]);
+        match self.errno? {
+            Errno::EEXIST => match registration(self.child, epfd, fd) {
+                Some(registration) => explain(format!(
+                    "fd {fd} is already registered in epoll instance {epfd} for {} events, EPOLL_CTL_MOD changes them",
+                    describe_interest(registration.events)
+                )),
+                None => explain(format!(
+                    "fd {fd} is already registered in epoll instance {epfd}, EPOLL_CTL_MOD changes its events"
+                )),
+            },
+            Errno::ENOENT => explain(format!(
+                "fd {fd} is not registered in epoll instance {epfd}, it has to be added with EPOLL_CTL_ADD first"
+            )),
+            Errno::EPERM => explain(format!(
+                "fd {fd} does not support polling, regular files and directories are always ready"
+            )),
+            Errno::EINVAL if fd == epfd => explain("an epoll instance cannot watch itself".to_owned()),
+            _ => None,
+        }
+    }
+}
+
+pub fn emit_epoll_state() {
+    if !EPOLL_STATE.get() {
+        return;
+    }
+    EPOLL_INTEREST.with_borrow(|instances| {
+        if instances.is_empty() {
+            println!("\n\nno epoll instances were used");
+            return;
+        }
+        println!("\n\nepoll interest sets:");
+        let mut keys = instances.keys().collect::<Vec<_>>();
+        keys.sort();
+        for key @ (pid, epfd) in keys {
+            let interest = &instances[key];
+            if interest.is_empty() {
+                println!("  process {pid}, epoll instance {epfd}: empty");
+                continue;
+            }
+            println!("  process {pid}, epoll instance {epfd}:");
+            for registration in interest.values() {
+                let closed = if registration.closed {
+                    ", closed but still registered through a duplicate"
+                } else {
+                    ""
+                };
+                println!(
+                    "    fd {}: {} (data {:#x}){closed}",
+                    registration.target,
+                    describe_interest(registration.events),
+                    registration.data
+                );
+            }
+        }
+    });
+}
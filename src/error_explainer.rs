@@ -0,0 +1,577 @@
+// errno alone rarely tells what to fix,
+// this module works out the actual reason behind some failures of path-based syscalls
+use crate::{
+    syscall_object::{SyscallObject, SyscallState},
+    threads::thread_label,
+    types::ProbeRun,
+    utilities::{
+        capability_to_string, errno_to_string, resolve_child_path, FAILED_ONLY, PROBE_RUN,
+    },
+};
+use colored::{ColoredString, Colorize};
+use nix::{
+    errno::Errno,
+    libc::{
+        AT_FDCWD, AT_REMOVEDIR, O_ACCMODE, O_CREAT, O_PATH, O_RDONLY, O_RDWR, O_TRUNC, O_WRONLY,
+    },
+};
+use procfs::process::Status;
+use std::{
+    fs::{self, Metadata},
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+use syscalls::Sysno;
+
+const CAP_CHOWN: u64 = 0;
+const CAP_DAC_OVERRIDE: u64 = 1;
+const CAP_DAC_READ_SEARCH: u64 = 2;
+const CAP_FOWNER: u64 = 3;
+
+const READ: u32 = 4;
+const WRITE: u32 = 2;
+const EXECUTE: u32 = 1;
+
+// what a syscall needs from the path it operates on
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum PathAccess {
+    // only the ancestors need to be searchable
+    Lookup,
+    // permission bits needed on the path itself
+    Permissions(u32),
+    // an entry is created inside the parent directory
+    CreateEntry,
+    // an entry is removed from the parent directory
+    RemoveEntry,
+    // only the owner can do this, unless the process holds the capability
+    Owner(u64),
+}
+
+impl SyscallObject {
+    pub(crate) fn child_path(&self, dirfd_index: Option<usize>, path_index: usize) -> PathBuf {
+        let dirfd = match dirfd_index {
+            Some(index) => self.args[index] as i32,
+            None => AT_FDCWD,
+        };
+        let path = if self.sysno == Sysno::execve {
+            // execve's pathname is skipped when gathering precall data
+            SyscallObject::read_bytes_until_null(self.args[path_index] as usize, self.child)
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default()
+        } else {
+            self.pavfol(path_index)
+        };
+        resolve_child_path(self.child, dirfd, &path)
+    }
+
+    fn open_access(&self, path: PathBuf, flags: i32) -> (PathBuf, PathAccess) {
+        if (flags & O_PATH) == O_PATH {
+            return (path, PathAccess::Lookup);
+        }
+        if (flags & O_CREAT) == O_CREAT && fs::symlink_metadata(&path).is_err() {
+            return (path, PathAccess::CreateEntry);
+        }
+        let mut wanted = match flags & O_ACCMODE {
+            O_RDONLY => READ,
+            O_WRONLY => WRITE,
+            O_RDWR => READ | WRITE,
+            _ => 0,
+        };
+        if (flags & O_TRUNC) == O_TRUNC {
+            wanted |= WRITE;
+        }
+        (path, PathAccess::Permissions(wanted))
+    }
+
+    // the paths a syscall touches, and what it needs from each of them
+    pub(crate) fn path_accesses(&self) -> Vec<(PathBuf, PathAccess)> {
+        use PathAccess::*;
+        let path = |index: usize| self.child_path(None, index);
+        let path_at = |dirfd_index: usize, index: usize| self.child_path(Some(dirfd_index), index);
+        match self.sysno {
+            Sysno::open => vec![self.open_access(path(0), self.args[1] as i32)],
+            Sysno::openat => vec![self.open_access(path_at(0, 1), self.args[2] as i32)],
+            Sysno::openat2 => {
+                let flags = SyscallObject::read_bytes_as_struct::<8, u64>(
+                    self.args[2] as usize,
+                    self.child,
+                )
+                .unwrap_or(0);
+                vec![self.open_access(path_at(0, 1), flags as i32)]
+            }
+            Sysno::creat => vec![self.open_access(path(0), O_CREAT | O_WRONLY | O_TRUNC)],
+            Sysno::execve => vec![(path(0), Permissions(EXECUTE))],
+            Sysno::chdir => vec![(path(0), Permissions(EXECUTE))],
+            Sysno::truncate => vec![(path(0), Permissions(WRITE))],
+            Sysno::access => vec![(path(0), Permissions(self.args[1] as u32 & 7))],
+            Sysno::faccessat | Sysno::faccessat2 => {
+                vec![(path_at(0, 1), Permissions(self.args[2] as u32 & 7))]
+            }
+            Sysno::stat | Sysno::lstat | Sysno::statfs | Sysno::readlink => {
+                vec![(path(0), Lookup)]
+            }
+            Sysno::newfstatat | Sysno::statx | Sysno::readlinkat => vec![(path_at(0, 1), Lookup)],
+            Sysno::mkdir => vec![(path(0), CreateEntry)],
+            Sysno::mkdirat => vec![(path_at(0, 1), CreateEntry)],
+            Sysno::rmdir | Sysno::unlink => vec![(path(0), RemoveEntry)],
+            Sysno::unlinkat => vec![(path_at(0, 1), RemoveEntry)],
+            Sysno::symlink => vec![(path(1), CreateEntry)],
+            Sysno::symlinkat => vec![(path_at(1, 2), CreateEntry)],
+            Sysno::link => vec![(path(0), Lookup), (path(1), CreateEntry)],
+            Sysno::linkat => vec![(path_at(0, 1), Lookup), (path_at(2, 3), CreateEntry)],
+            Sysno::rename => vec![(path(0), RemoveEntry), (path(1), CreateEntry)],
+            Sysno::renameat | Sysno::renameat2 => {
+                vec![(path_at(0, 1), RemoveEntry), (path_at(2, 3), CreateEntry)]
+            }
+            Sysno::chmod => vec![(path(0), Owner(CAP_FOWNER))],
+            Sysno::fchmodat => vec![(path_at(0, 1), Owner(CAP_FOWNER))],
+            Sysno::chown | Sysno::lchown => vec![(path(0), Owner(CAP_CHOWN))],
+            Sysno::fchownat => vec![(path_at(0, 1), Owner(CAP_CHOWN))],
+            _ => vec![],
+        }
+    }
+
+    // the return value parsing only takes some errnos for failures,
+    // so an explained line gets its outcome replaced by the errno before the explanation is attached
+    pub(crate) fn attach_error_explanation(&mut self) {
+        let explanation = self.explain_error();
+        if explanation.is_empty() {
+            return;
+        }
+        if let (Ok(_), Some(errno)) = (self.parse_return_value_one_line(), self.errno) {
+            if let Some(outcome) = self.one_line.iter().rposition(|part| &**part == " |=> ") {
+                self.one_line.truncate(outcome + 1);
+                self.one_line.push(errno_to_string(errno).red());
+            }
+        }
+        self.one_line.extend(explanation);
+    }
+
+    pub(crate) fn explain_error(&self) -> Vec<ColoredString> {
+        if let Some(denial) = self.seccomp_denial() {
+            return denial;
+        }
+        if let Some(denial) = self.mount_denial() {
+            return denial;
+        }
+        if let Some(denial) = self.credentials_denial() {
+            return denial;
+        }
+        if let Some(denial) = self.perf_denial() {
+            return denial;
+        }
+        if let Some(denial) = self.epoll_denial() {
+            return denial;
+        }
+        match self.errno {
+            Some(Errno::EACCES) | Some(Errno::EPERM) => self.explain_permission_error(),
+            Some(Errno::ENOENT) => self.explain_missing_path(),
+            _ => vec![],
+        }
+    }
+
+    fn explain_missing_path(&self) -> Vec<ColoredString> {
+        for (path, _) in self.path_accesses() {
+            if let Some(explanation) = find_missing_component(&path) {
+                return explanation;
+            }
+        }
+        vec![]
+    }
+
+    // the path looked up by syscalls that programs use to probe for files
+    fn probed_path(&self) -> Option<PathBuf> {
+        match self.sysno {
+            Sysno::open
+            | Sysno::openat
+            | Sysno::stat
+            | Sysno::lstat
+            | Sysno::newfstatat
+            | Sysno::statx
+            | Sysno::access
+            | Sysno::faccessat
+            | Sysno::faccessat2
+            | Sysno::readlink
+            | Sysno::readlinkat
+            | Sysno::execve => self
+                .path_accesses()
+                .into_iter()
+                .next()
+                .map(|(path, _)| path),
+            _ => None,
+        }
+    }
+
+    // renders the whole one line output of the syscall without printing it
+    fn render_one_line(&mut self) -> Option<String> {
+        let mut string = String::new();
+        for state in [SyscallState::Entering, SyscallState::Exiting] {
+            self.state = state;
+            self.one_line_formatter().ok()?;
+            for colored in &self.one_line {
+                string.push_str(&colored.to_string());
+            }
+        }
+        Some(string)
+    }
+
+    // holds back failed lookups of the same file name, and prints them as a single line
+    // returns true when the syscall was taken care of and should not be printed
+    pub(crate) fn collapse_probe(&mut self) -> bool {
+        let Some(path) = self.probed_path() else {
+            flush_probe_run();
+            return false;
+        };
+        let file_name = match path.file_name() {
+            Some(file_name) => file_name.to_string_lossy().into_owned(),
+            None => {
+                flush_probe_run();
+                return false;
+            }
+        };
+        let same_file =
+            PROBE_RUN.with_borrow(|run| run.as_ref().is_some_and(|run| run.file_name == file_name));
+        if self.errno == Some(Errno::ENOENT) {
+            // the dynamic loader also checks the directory it just searched, that check belongs to the run
+            let searched_directory = PROBE_RUN.with_borrow(|run| {
+                run.as_ref().is_some_and(|run| {
+                    run.locations
+                        .last()
+                        .is_some_and(|location| location.parent() == Some(path.as_path()))
+                })
+            });
+            if searched_directory {
+                return true;
+            }
+            if same_file {
+                PROBE_RUN.with_borrow_mut(|run| run.as_mut().unwrap().locations.push(path));
+                return true;
+            }
+            flush_probe_run();
+            let Some(first_line) = self.render_one_line() else {
+                return false;
+            };
+            PROBE_RUN.set(Some(ProbeRun {
+                pid: self.child,
+                sysno: self.sysno,
+                category: self.category,
+                file_name,
+                locations: vec![path],
+                first_line,
+            }));
+            return true;
+        }
+        if !same_file || self.errno.is_some() {
+            flush_probe_run();
+            return false;
+        }
+        let run = PROBE_RUN.take().unwrap();
+        let directory = path
+            .parent()
+            .map(|parent| parent.display().to_string())
+            .unwrap_or_default();
+        let found = vec![
+            "searched ".white(),
+            (run.locations.len() + 1).to_string().yellow(),
+            " locations for ".white(),
+            run.file_name.blue(),
+            "; found in ".white(),
+            directory.yellow(),
+        ];
+        if FAILED_ONLY.get() {
+            // the lookup that succeeded is not printed, so the run stands on its own
+            let (id, name) = thread_label(self.child);
+            let mut line = vec![
+                "\n".white(),
+                id.blue(),
+                tagged(name).cyan(),
+                " ".dimmed(),
+                SyscallObject::colorize_syscall_name(&self.sysno, &self.category),
+                " - ".dimmed(),
+            ];
+            line.extend(found);
+            print!(
+                "{}",
+                String::from_iter(line.into_iter().map(|x| x.to_string()))
+            );
+            return true;
+        }
+        let Some(line) = self.render_one_line() else {
+            return false;
+        };
+        print!(
+            "{line}{}{}{}",
+            " (".white(),
+            String::from_iter(found.into_iter().map(|x| x.to_string())),
+            ")".white()
+        );
+        true
+    }
+
+    fn explain_permission_error(&self) -> Vec<ColoredString> {
+        let accesses = self.path_accesses();
+        if accesses.is_empty() {
+            return vec![];
+        }
+        let status = match procfs::process::Process::new(self.child.as_raw())
+            .and_then(|process| process.status())
+        {
+            Ok(status) => status,
+            Err(_) => return vec![],
+        };
+        for (path, access) in accesses {
+            if let Some(explanation) = find_denial(&path, access, &status) {
+                return explanation;
+            }
+        }
+        vec![
+            " (".white(),
+            "file modes and owners allow this".yellow(),
+            ", the denial comes from elsewhere: an LSM, a read-only or noexec mount, or a landlock or seccomp policy"
+                .white(),
+            ")".white(),
+        ]
+    }
+}
+
+fn has_capability(status: &Status, capability: u64) -> bool {
+    status.capeff & (1 << capability) != 0
+}
+
+fn in_group(status: &Status, gid: u32) -> bool {
+    status.fgid == gid || status.groups.iter().any(|group| *group as u32 == gid)
+}
+
+// mirrors the kernel's generic_permission()
+fn dac_allows(metadata: &Metadata, wanted: u32, status: &Status) -> bool {
+    let mode = metadata.mode();
+    let executable = metadata.is_dir() || (mode & 0o111) != 0;
+    if has_capability(status, CAP_DAC_OVERRIDE) && ((wanted & EXECUTE) == 0 || executable) {
+        return true;
+    }
+    if has_capability(status, CAP_DAC_READ_SEARCH)
+        && (wanted & WRITE) == 0
+        && ((wanted & EXECUTE) == 0 || metadata.is_dir())
+    {
+        return true;
+    }
+    let bits = if status.fuid == metadata.uid() {
+        (mode >> 6) & 7
+    } else if in_group(status, metadata.gid()) {
+        (mode >> 3) & 7
+    } else {
+        mode & 7
+    };
+    (bits & wanted) == wanted
+}
+
+fn find_denial(path: &Path, access: PathAccess, status: &Status) -> Option<Vec<ColoredString>> {
+    // every directory on the way must be searchable
+    let mut ancestors = path.ancestors().skip(1).collect::<Vec<&Path>>();
+    ancestors.reverse();
+    for ancestor in ancestors {
+        if let Ok(metadata) = fs::metadata(ancestor) {
+            if !dac_allows(&metadata, EXECUTE, status) {
+                return Some(describe_denial(ancestor, &metadata, EXECUTE, status));
+            }
+        }
+    }
+    match access {
+        PathAccess::Lookup => None,
+        PathAccess::Permissions(wanted) => {
+            let metadata = fs::metadata(path).ok()?;
+            if !dac_allows(&metadata, wanted, status) {
+                Some(describe_denial(path, &metadata, wanted, status))
+            } else {
+                None
+            }
+        }
+        PathAccess::CreateEntry | PathAccess::RemoveEntry => {
+            let parent = path.parent()?;
+            let parent_metadata = fs::metadata(parent).ok()?;
+            if !dac_allows(&parent_metadata, WRITE | EXECUTE, status) {
+                return Some(describe_denial(parent, &parent_metadata, WRITE, status));
+            }
+            // in sticky directories (like /tmp) only owners can remove entries
+            let sticky = (parent_metadata.permissions().mode() & 0o1000) != 0;
+            if matches!(access, PathAccess::RemoveEntry) && sticky {
+                let metadata = fs::symlink_metadata(path).ok()?;
+                if status.fuid != metadata.uid()
+                    && status.fuid != parent_metadata.uid()
+                    && !has_capability(status, CAP_FOWNER)
+                {
+                    let mut explanation = vec![" (".white()];
+                    explanation.push(parent.display().to_string().yellow());
+                    explanation.push(" is sticky, and ".white());
+                    explanation.push(path.display().to_string().yellow());
+                    explanation.push(" is owned by ".white());
+                    explanation.push(user_name(metadata.uid()).yellow());
+                    explain_process(status, CAP_FOWNER, &mut explanation);
+                    explanation.push(")".white());
+                    return Some(explanation);
+                }
+            }
+            None
+        }
+        PathAccess::Owner(capability) => {
+            let metadata = fs::symlink_metadata(path).ok()?;
+            if status.fuid != metadata.uid() && !has_capability(status, capability) {
+                let mut explanation = vec![" (".white()];
+                explanation.push(path.display().to_string().yellow());
+                explanation.push(" is owned by ".white());
+                explanation.push(user_name(metadata.uid()).yellow());
+                explain_process(status, capability, &mut explanation);
+                explanation.push(")".white());
+                Some(explanation)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+pub(crate) fn user_name(uid: u32) -> String {
+    match uzers::get_user_by_uid(uid) {
+        Some(user) => user.name().to_string_lossy().into_owned(),
+        None => format!("uid {uid}"),
+    }
+}
+
+pub(crate) fn group_name(gid: u32) -> String {
+    match uzers::get_group_by_gid(gid) {
+        Some(group) => group.name().to_string_lossy().into_owned(),
+        None => format!("gid {gid}"),
+    }
+}
+
+fn explain_process(status: &Status, capability: u64, explanation: &mut Vec<ColoredString>) {
+    explanation.push("; process runs as uid ".white());
+    explanation.push(status.fuid.to_string().yellow());
+    explanation.push(" gid ".white());
+    explanation.push(status.fgid.to_string().yellow());
+    explanation.push(" without ".white());
+    explanation.push(
+        capability_to_string(capability)
+            .unwrap_or_default()
+            .yellow(),
+    );
+}
+
+fn describe_denial(
+    path: &Path,
+    metadata: &Metadata,
+    wanted: u32,
+    status: &Status,
+) -> Vec<ColoredString> {
+    let mut needed = vec![];
+    if (wanted & READ) == READ {
+        needed.push("read");
+    }
+    if (wanted & WRITE) == WRITE {
+        needed.push("write");
+    }
+    if (wanted & EXECUTE) == EXECUTE {
+        needed.push(if metadata.is_dir() {
+            "search"
+        } else {
+            "execute"
+        });
+    }
+    // searching and reading can be granted by CAP_DAC_READ_SEARCH, everything else by CAP_DAC_OVERRIDE
+    let capability = if (wanted & WRITE) == 0 && ((wanted & EXECUTE) == 0 || metadata.is_dir()) {
+        CAP_DAC_READ_SEARCH
+    } else {
+        CAP_DAC_OVERRIDE
+    };
+    let mut explanation = vec![" (".white()];
+    explanation.push(path.display().to_string().yellow());
+    explanation.push(" is ".white());
+    explanation.push(format!("{:04o}", metadata.mode() & 0o7777).yellow());
+    explanation.push(" owned by ".white());
+    explanation.push(user_name(metadata.uid()).yellow());
+    explanation.push(":".white());
+    explanation.push(group_name(metadata.gid()).yellow());
+    explanation.push(", ".white());
+    explanation.push(needed.join(" and ").yellow());
+    explanation.push(" permission is needed".white());
+    explain_process(status, capability, &mut explanation);
+    explanation.push(")".white());
+    explanation
+}
+
+// names the first component of the path that does not exist
+fn find_missing_component(path: &Path) -> Option<Vec<ColoredString>> {
+    let mut ancestors = path.ancestors().collect::<Vec<&Path>>();
+    ancestors.reverse();
+    for ancestor in ancestors {
+        if let Ok(metadata) = fs::symlink_metadata(ancestor) {
+            if metadata.is_symlink() && fs::metadata(ancestor).is_err() {
+                let target = fs::read_link(ancestor).unwrap_or_default();
+                return Some(vec![
+                    " (".white(),
+                    ancestor.display().to_string().yellow(),
+                    " is a dangling symlink to ".white(),
+                    target.display().to_string().yellow(),
+                    ")".white(),
+                ]);
+            }
+            continue;
+        }
+        let parent = ancestor.parent()?;
+        let name = ancestor.file_name()?.to_string_lossy().into_owned();
+        return Some(vec![
+            " (".white(),
+            "no ".white(),
+            name.blue(),
+            " in ".white(),
+            parent.display().to_string().yellow(),
+            ")".white(),
+        ]);
+    }
+    None
+}
+
+// " name" after the id column, nothing when the name is not known
+fn tagged(name: String) -> String {
+    if name.is_empty() {
+        name
+    } else {
+        format!(" {name}")
+    }
+}
+
+// prints the held back lookups, collapsed into one line when there are several
+pub fn flush_probe_run() {
+    let Some(run) = PROBE_RUN.take() else {
+        return;
+    };
+    if run.locations.len() == 1 {
+        print!("{}", run.first_line);
+        return;
+    }
+    let last_directory = run.locations[run.locations.len() - 1]
+        .parent()
+        .map(|parent| parent.display().to_string())
+        .unwrap_or_default();
+    let (id, name) = thread_label(run.pid);
+    let line = [
+        "\n".white(),
+        id.red(),
+        tagged(name).cyan(),
+        " ".dimmed(),
+        SyscallObject::colorize_syscall_name(&run.sysno, &run.category),
+        " - ".dimmed(),
+        "searched ".white(),
+        run.locations.len().to_string().yellow(),
+        " locations for ".white(),
+        run.file_name.blue(),
+        ", the last one being ".white(),
+        last_directory.yellow(),
+        " |=> ".white(),
+        "not found".red(),
+    ];
+    print!(
+        "{}",
+        String::from_iter(line.into_iter().map(|x| x.to_string()))
+    );
+}
@@ -0,0 +1,647 @@
+// io_uring moves the actual I/O out of syscalls and into rings shared with the kernel,
+// this module follows the rings of each instance so that the operations can be read back from the tracee
+use crate::{
+    syscall_object::SyscallObject,
+    types::{Bytes, IoUringParams, IoUringRings},
+    utilities::{errno_to_string, IO_URINGS},
+};
+use colored::{ColoredString, Colorize};
+use nix::{errno::Errno, unistd::Pid};
+use syscalls::Sysno;
+
+const IORING_OFF_SQ_RING: u64 = 0;
+const IORING_OFF_CQ_RING: u64 = 0x8000000;
+const IORING_OFF_SQES: u64 = 0x10000000;
+
+const IORING_SETUP_SQPOLL: u32 = 1 << 1;
+const IORING_SETUP_SQE128: u32 = 1 << 10;
+const IORING_SETUP_CQE32: u32 = 1 << 11;
+const IORING_SETUP_NO_SQARRAY: u32 = 1 << 16;
+const IORING_FEAT_SINGLE_MMAP: u32 = 1;
+
+const IORING_ENTER_GETEVENTS: u64 = 1;
+const IORING_ENTER_REGISTERED_RING: u64 = 1 << 4;
+
+const IOSQE_FIXED_FILE: u8 = 1;
+const IOSQE_IO_LINK: u8 = 1 << 2;
+
+// submissions and completions printed for a single io_uring_enter
+const MAX_REPORTED_SUBMISSIONS: u32 = 16;
+const MAX_REPORTED_COMPLETIONS: u32 = 16;
+
+const IORING_SETUP_FLAGS: [(u32, &str); 17] = [
+    (1, "busy-poll for I/O completions"),
+    (1 << 1, "let a kernel thread poll the submission queue"),
+    (1 << 2, "pin the polling kernel thread to a cpu"),
+    (1 << 3, "use a custom completion queue size"),
+    (1 << 4, "clamp the queue sizes to the maximum"),
+    (1 << 5, "share the async worker threads of another io_uring"),
+    (1 << 6, "start the rings disabled"),
+    (1 << 7, "keep submitting after an error"),
+    (1 << 8, "only run completion work when entering the kernel"),
+    (1 << 9, "flag pending completion work in the ring"),
+    (1 << 10, "use 128 Byte submission entries"),
+    (1 << 11, "use 32 Byte completion entries"),
+    (1 << 12, "only a single task submits"),
+    (1 << 13, "defer completion work until the next wait"),
+    (1 << 14, "rings are allocated by the process"),
+    (1 << 15, "only hand out a registered ring file descriptor"),
+    (1 << 16, "index submission entries without an array"),
+];
+
+const OPCODES: [&str; 58] = [
+    "NOP",
+    "READV",
+    "WRITEV",
+    "FSYNC",
+    "READ_FIXED",
+    "WRITE_FIXED",
+    "POLL_ADD",
+    "POLL_REMOVE",
+    "SYNC_FILE_RANGE",
+    "SENDMSG",
+    "RECVMSG",
+    "TIMEOUT",
+    "TIMEOUT_REMOVE",
+    "ACCEPT",
+    "ASYNC_CANCEL",
+    "LINK_TIMEOUT",
+    "CONNECT",
+    "FALLOCATE",
+    "OPENAT",
+    "CLOSE",
+    "FILES_UPDATE",
+    "STATX",
+    "READ",
+    "WRITE",
+    "FADVISE",
+    "MADVISE",
+    "SEND",
+    "RECV",
+    "OPENAT2",
+    "EPOLL_CTL",
+    "SPLICE",
+    "PROVIDE_BUFFERS",
+    "REMOVE_BUFFERS",
+    "TEE",
+    "SHUTDOWN",
+    "RENAMEAT",
+    "UNLINKAT",
+    "MKDIRAT",
+    "SYMLINKAT",
+    "LINKAT",
+    "MSG_RING",
+    "FSETXATTR",
+    "SETXATTR",
+    "FGETXATTR",
+    "GETXATTR",
+    "SOCKET",
+    "URING_CMD",
+    "SEND_ZC",
+    "SENDMSG_ZC",
+    "READ_MULTISHOT",
+    "WAITID",
+    "FUTEX_WAIT",
+    "FUTEX_WAKE",
+    "FUTEX_WAITV",
+    "FIXED_FD_INSTALL",
+    "FTRUNCATE",
+    "BIND",
+    "LISTEN",
+];
+
+const REGISTER_OPCODES: [(&str, &str); 26] = [
+    (
+        "IORING_REGISTER_BUFFERS",
+        "register buffers for fixed reads and writes with",
+    ),
+    (
+        "IORING_UNREGISTER_BUFFERS",
+        "unregister the fixed buffers of",
+    ),
+    (
+        "IORING_REGISTER_FILES",
+        "register files for fixed file operations with",
+    ),
+    ("IORING_UNREGISTER_FILES", "unregister the fixed files of"),
+    (
+        "IORING_REGISTER_EVENTFD",
+        "register an eventfd to be notified of completions of",
+    ),
+    ("IORING_UNREGISTER_EVENTFD", "unregister the eventfd of"),
+    ("IORING_REGISTER_FILES_UPDATE", "update the fixed files of"),
+    (
+        "IORING_REGISTER_EVENTFD_ASYNC",
+        "register an eventfd to be notified of async completions of",
+    ),
+    ("IORING_REGISTER_PROBE", "probe the supported operations of"),
+    (
+        "IORING_REGISTER_PERSONALITY",
+        "register the current credentials with",
+    ),
+    (
+        "IORING_UNREGISTER_PERSONALITY",
+        "unregister a set of credentials of",
+    ),
+    (
+        "IORING_REGISTER_RESTRICTIONS",
+        "restrict the allowed operations of",
+    ),
+    ("IORING_REGISTER_ENABLE_RINGS", "enable the rings of"),
+    (
+        "IORING_REGISTER_FILES2",
+        "register files for fixed file operations with",
+    ),
+    ("IORING_REGISTER_FILES_UPDATE2", "update the fixed files of"),
+    (
+        "IORING_REGISTER_BUFFERS2",
+        "register buffers for fixed reads and writes with",
+    ),
+    (
+        "IORING_REGISTER_BUFFERS_UPDATE",
+        "update the fixed buffers of",
+    ),
+    (
+        "IORING_REGISTER_IOWQ_AFF",
+        "set the cpu affinity of the async workers of",
+    ),
+    (
+        "IORING_UNREGISTER_IOWQ_AFF",
+        "reset the cpu affinity of the async workers of",
+    ),
+    (
+        "IORING_REGISTER_IOWQ_MAX_WORKERS",
+        "limit the number of async workers of",
+    ),
+    (
+        "IORING_REGISTER_RING_FDS",
+        "register the ring file descriptor of",
+    ),
+    (
+        "IORING_UNREGISTER_RING_FDS",
+        "unregister the ring file descriptor of",
+    ),
+    (
+        "IORING_REGISTER_PBUF_RING",
+        "register a provided buffer ring with",
+    ),
+    (
+        "IORING_UNREGISTER_PBUF_RING",
+        "unregister a provided buffer ring of",
+    ),
+    (
+        "IORING_REGISTER_SYNC_CANCEL",
+        "cancel requests synchronously on",
+    ),
+    (
+        "IORING_REGISTER_FILE_ALLOC_RANGE",
+        "set the range for allocated fixed files of",
+    ),
+];
+
+// struct io_uring_sqe
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    op_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    addr3: u64,
+    pad: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+// records io_uring instances and the mappings of their rings
+pub fn track_io_uring(syscall: &SyscallObject) {
+    if syscall.errno.is_some() {
+        return;
+    }
+    let child = syscall.child;
+    match syscall.sysno {
+        Sysno::io_uring_setup => {
+            let params = SyscallObject::read_bytes_as_struct::<120, IoUringParams>(
+                syscall.args[1] as usize,
+                child,
+            );
+            if let (Some(params), Some(fd)) = (params, syscall.result.0) {
+                IO_URINGS.with_borrow_mut(|rings| {
+                    rings.insert(
+                        (child, fd as i32),
+                        IoUringRings {
+                            params,
+                            ..Default::default()
+                        },
+                    )
+                });
+            }
+        }
+        Sysno::mmap => {
+            let fd = syscall.args[4] as i32;
+            let Some(address) = syscall.result.0 else {
+                return;
+            };
+            IO_URINGS.with_borrow_mut(|rings| {
+                if let Some(ring) = rings.get_mut(&(child, fd)) {
+                    match syscall.args[5] {
+                        IORING_OFF_SQ_RING => {
+                            ring.sq_ring = Some(address);
+                            // both rings live in a single mapping
+                            if (ring.params.features & IORING_FEAT_SINGLE_MMAP) != 0 {
+                                ring.cq_ring = Some(address);
+                            }
+                        }
+                        IORING_OFF_CQ_RING => ring.cq_ring = Some(address),
+                        IORING_OFF_SQES => ring.sqes = Some(address),
+                        _ => {}
+                    }
+                }
+            });
+        }
+        // the completions the kernel posted since the last io_uring_enter, for the formatter to report
+        Sysno::io_uring_enter => {
+            let registered = (syscall.args[3] & IORING_ENTER_REGISTERED_RING) != 0;
+            let entered_fd = syscall.args[0] as i32;
+            let Some(ring) = find_ring(child, entered_fd, registered) else {
+                return;
+            };
+            let Some((head, tail)) = completion_queue_bounds(&ring, child) else {
+                return;
+            };
+            // completions the process consumed on its own are not reported
+            let start = if ring.reported_cq_tail.wrapping_sub(head) <= tail.wrapping_sub(head) {
+                ring.reported_cq_tail
+            } else {
+                head
+            };
+            IO_URINGS.with_borrow_mut(|rings| {
+                for ((pid, fd), tracked) in rings.iter_mut() {
+                    if *pid == child && (registered || *fd == entered_fd) {
+                        tracked.unreported_cq_head = start;
+                        tracked.reported_cq_tail = tail;
+                    }
+                }
+            });
+        }
+        Sysno::close => {
+            IO_URINGS.with_borrow_mut(|rings| rings.remove(&(child, syscall.args[0] as i32)));
+        }
+        _ => {}
+    }
+}
+
+// registered ring file descriptors are indices, not real file descriptors
+fn find_ring(child: Pid, fd: i32, registered: bool) -> Option<IoUringRings> {
+    IO_URINGS.with_borrow(|rings| {
+        if !registered {
+            return rings.get(&(child, fd)).copied();
+        }
+        let mut owned = rings.iter().filter(|((pid, _), _)| *pid == child);
+        match (owned.next(), owned.next()) {
+            (Some((_, ring)), None) => Some(*ring),
+            _ => None,
+        }
+    })
+}
+
+fn read_u32(address: u64, child: Pid) -> Option<u32> {
+    SyscallObject::read_bytes_as_struct::<4, u32>(address as usize, child)
+}
+
+fn completion_queue_bounds(ring: &IoUringRings, child: Pid) -> Option<(u32, u32)> {
+    let cq_ring = ring.cq_ring?;
+    let offsets = ring.params.cq_off;
+    Some((
+        read_u32(cq_ring + offsets.head as u64, child)?,
+        read_u32(cq_ring + offsets.tail as u64, child)?,
+    ))
+}
+
+fn describe_fd(sqe: &Sqe, child: Pid) -> ColoredString {
+    if (sqe.flags & IOSQE_FIXED_FILE) == IOSQE_FIXED_FILE {
+        return format!("fixed file #{}", sqe.fd).yellow();
+    }
+    match SyscallObject::style_file_descriptor(sqe.fd as u64, child) {
+        Some(styled) => styled.normal(),
+        None => sqe.fd.to_string().yellow(),
+    }
+}
+
+fn describe_offset(offset: u64) -> Vec<ColoredString> {
+    if offset == u64::MAX {
+        vec![" at the current offset".white()]
+    } else {
+        vec![" at offset ".white(), offset.to_string().yellow()]
+    }
+}
+
+fn read_path(address: u64, child: Pid) -> ColoredString {
+    match SyscallObject::read_bytes_until_null(address as usize, child) {
+        Some(bytes) => String::from_utf8_lossy(&bytes).into_owned().yellow(),
+        None => "?".yellow(),
+    }
+}
+
+fn describe_sqe(sqe: &Sqe, child: Pid) -> Vec<ColoredString> {
+    let opcode = OPCODES
+        .get(sqe.opcode as usize)
+        .copied()
+        .unwrap_or("UNKNOWN");
+    let length = Bytes::from(sqe.len as usize).to_string().yellow();
+    let mut description = match opcode {
+        "NOP" => vec!["do nothing".white()],
+        "READ" | "READ_FIXED" | "READ_MULTISHOT" => {
+            let mut description = vec!["async read ".white(), length, " from ".white()];
+            description.push(describe_fd(sqe, child));
+            description.extend(describe_offset(sqe.off));
+            description
+        }
+        "WRITE" | "WRITE_FIXED" => {
+            let mut description = vec!["async write ".white(), length, " to ".white()];
+            description.push(describe_fd(sqe, child));
+            description.extend(describe_offset(sqe.off));
+            description
+        }
+        "READV" | "WRITEV" => {
+            let mut description = vec![
+                if opcode == "READV" {
+                    "async read into ".white()
+                } else {
+                    "async write from ".white()
+                },
+                sqe.len.to_string().yellow(),
+                " buffers ".white(),
+                if opcode == "READV" {
+                    "from ".white()
+                } else {
+                    "to ".white()
+                },
+            ];
+            description.push(describe_fd(sqe, child));
+            description.extend(describe_offset(sqe.off));
+            description
+        }
+        "SEND" | "SEND_ZC" => vec![
+            "async send ".white(),
+            length,
+            " to ".white(),
+            describe_fd(sqe, child),
+        ],
+        "RECV" => vec![
+            "async receive up to ".white(),
+            length,
+            " from ".white(),
+            describe_fd(sqe, child),
+        ],
+        "SENDMSG" | "SENDMSG_ZC" => {
+            vec!["async send a message to ".white(), describe_fd(sqe, child)]
+        }
+        "RECVMSG" => vec![
+            "async receive a message from ".white(),
+            describe_fd(sqe, child),
+        ],
+        "ACCEPT" => vec![
+            "async accept a connection on ".white(),
+            describe_fd(sqe, child),
+        ],
+        "CONNECT" => vec!["async connect ".white(), describe_fd(sqe, child)],
+        "SHUTDOWN" => vec!["async shut down ".white(), describe_fd(sqe, child)],
+        "FSYNC" => vec!["async flush to disk ".white(), describe_fd(sqe, child)],
+        "CLOSE" => vec!["async close ".white(), describe_fd(sqe, child)],
+        "POLL_ADD" => vec!["async wait for events on ".white(), describe_fd(sqe, child)],
+        "OPENAT" | "OPENAT2" => vec!["async open the file ".white(), read_path(sqe.addr, child)],
+        "STATX" => vec![
+            "async get the stats of ".white(),
+            read_path(sqe.addr, child),
+        ],
+        "UNLINKAT" => vec!["async unlink ".white(), read_path(sqe.addr, child)],
+        "MKDIRAT" => vec![
+            "async create the directory ".white(),
+            read_path(sqe.addr, child),
+        ],
+        "RENAMEAT" => vec![
+            "async rename ".white(),
+            read_path(sqe.addr, child),
+            " to ".white(),
+            read_path(sqe.off, child),
+        ],
+        "TIMEOUT" | "LINK_TIMEOUT" => vec!["async timeout".white()],
+        "ASYNC_CANCEL" => vec![
+            "cancel the operation with user data ".white(),
+            sqe.addr.to_string().yellow(),
+        ],
+        "FALLOCATE" => vec!["async allocate space for ".white(), describe_fd(sqe, child)],
+        "FTRUNCATE" => vec![
+            "async truncate ".white(),
+            describe_fd(sqe, child),
+            " to ".white(),
+            Bytes::from(sqe.off as usize).to_string().yellow(),
+        ],
+        "SPLICE" | "TEE" => vec![
+            "async move ".white(),
+            length,
+            " from ".white(),
+            sqe.splice_fd_in.to_string().yellow(),
+            " to ".white(),
+            describe_fd(sqe, child),
+        ],
+        _ => vec![
+            "async ".white(),
+            opcode.yellow(),
+            " on ".white(),
+            describe_fd(sqe, child),
+        ],
+    };
+    if (sqe.flags & IOSQE_IO_LINK) == IOSQE_IO_LINK {
+        description.push(" (linked to the next operation)".white());
+    }
+    description
+}
+
+fn join(parts: Vec<Vec<ColoredString>>) -> Vec<ColoredString> {
+    let mut joined = vec![];
+    for (index, part) in parts.into_iter().enumerate() {
+        if index > 0 {
+            joined.push(", ".white());
+        }
+        joined.extend(part);
+    }
+    joined
+}
+
+impl SyscallObject {
+    pub(crate) fn io_uring_setup_flags(&self) -> Vec<ColoredString> {
+        let Some(params) = SyscallObject::read_bytes_as_struct::<120, IoUringParams>(
+            self.args[1] as usize,
+            self.child,
+        ) else {
+            return vec![];
+        };
+        IORING_SETUP_FLAGS
+            .iter()
+            .filter(|(flag, _)| (params.flags & flag) == *flag)
+            .map(|(_, description)| description.yellow())
+            .collect()
+    }
+
+    pub(crate) fn io_uring_setup_result(&self) -> Vec<ColoredString> {
+        let Some(params) = SyscallObject::read_bytes_as_struct::<120, IoUringParams>(
+            self.args[1] as usize,
+            self.child,
+        ) else {
+            return vec!["io_uring created".green()];
+        };
+        vec![
+            "io_uring created with ".green(),
+            params.sq_entries.to_string().yellow(),
+            " submission entries and ".green(),
+            params.cq_entries.to_string().yellow(),
+            " completion entries".green(),
+        ]
+    }
+
+    // the operations consumed by the kernel lie right before the new submission queue head
+    pub(crate) fn io_uring_submissions(&self) -> Vec<ColoredString> {
+        let registered = (self.args[3] & IORING_ENTER_REGISTERED_RING) != 0;
+        let Some(ring) = find_ring(self.child, self.args[0] as i32, registered) else {
+            return vec![];
+        };
+        let submitted = self.result.0.unwrap_or(0) as u32;
+        let (Some(sq_ring), Some(sqes)) = (ring.sq_ring, ring.sqes) else {
+            return vec![];
+        };
+        if submitted == 0 || (ring.params.flags & IORING_SETUP_SQPOLL) != 0 {
+            return vec![];
+        }
+        let offsets = ring.params.sq_off;
+        let (Some(head), Some(mask)) = (
+            read_u32(sq_ring + offsets.head as u64, self.child),
+            read_u32(sq_ring + offsets.ring_mask as u64, self.child),
+        ) else {
+            return vec![];
+        };
+        let sqe_size = if (ring.params.flags & IORING_SETUP_SQE128) != 0 {
+            128
+        } else {
+            64
+        };
+        // the ring head is a free running u32, the submitted entries can straddle its wrap
+        let first = head.wrapping_sub(submitted);
+        let reported = submitted.min(MAX_REPORTED_SUBMISSIONS);
+        let mut operations = vec![];
+        for position in (0..reported).map(|offset| first.wrapping_add(offset)) {
+            let index = if (ring.params.flags & IORING_SETUP_NO_SQARRAY) != 0 {
+                position & mask
+            } else {
+                let slot = sq_ring + offsets.array as u64 + (position & mask) as u64 * 4;
+                match read_u32(slot, self.child) {
+                    Some(index) => index,
+                    None => continue,
+                }
+            };
+            let address = sqes + index as u64 * sqe_size;
+            if let Some(sqe) =
+                SyscallObject::read_bytes_as_struct::<64, Sqe>(address as usize, self.child)
+            {
+                operations.push(describe_sqe(&sqe, self.child));
+            }
+        }
+        let mut colored = join(operations);
+        if submitted > reported {
+            colored.push(", and ".white());
+            colored.push((submitted - reported).to_string().yellow());
+            colored.push(" more".white());
+        }
+        colored
+    }
+
+    // completions the kernel posted which were not reported in an earlier io_uring_enter
+    pub(crate) fn io_uring_completions(&self) -> Vec<ColoredString> {
+        let registered = (self.args[3] & IORING_ENTER_REGISTERED_RING) != 0;
+        let Some(ring) = find_ring(self.child, self.args[0] as i32, registered) else {
+            return vec![];
+        };
+        let Some(cq_ring) = ring.cq_ring else {
+            return vec![];
+        };
+        let offsets = ring.params.cq_off;
+        let Some(mask) = read_u32(cq_ring + offsets.ring_mask as u64, self.child) else {
+            return vec![];
+        };
+        let (start, tail) = (ring.unreported_cq_head, ring.reported_cq_tail);
+        let cqe_size = if (ring.params.flags & IORING_SETUP_CQE32) != 0 {
+            32
+        } else {
+            16
+        };
+        let mut completions = vec![];
+        let mut position = start;
+        while position != tail && completions.len() < MAX_REPORTED_COMPLETIONS as usize {
+            let address = cq_ring + offsets.cqes as u64 + (position & mask) as u64 * cqe_size;
+            if let Some(cqe) =
+                SyscallObject::read_bytes_as_struct::<16, Cqe>(address as usize, self.child)
+            {
+                let mut completion = vec![
+                    "user data ".white(),
+                    cqe.user_data.to_string().yellow(),
+                    " -> ".white(),
+                ];
+                if cqe.res < 0 {
+                    completion.push(errno_to_string(Errno::from_raw(-cqe.res)).red());
+                } else {
+                    completion.push(cqe.res.to_string().green());
+                }
+                completions.push(completion);
+            }
+            position = position.wrapping_add(1);
+        }
+        let remaining = tail.wrapping_sub(position);
+        let mut colored = join(completions);
+        if remaining > 0 {
+            colored.push(", and ".white());
+            colored.push(remaining.to_string().yellow());
+            colored.push(" more".white());
+        }
+        colored
+    }
+
+    // names the ring an mmap of an io_uring file descriptor maps
+    pub(crate) fn io_uring_mapping(&self) -> Vec<ColoredString> {
+        let is_io_uring =
+            IO_URINGS.with_borrow(|rings| rings.contains_key(&(self.child, self.args[4] as i32)));
+        if !is_io_uring {
+            return vec![];
+        }
+        let ring = match self.args[5] {
+            IORING_OFF_SQ_RING => "io_uring submission queue ring",
+            IORING_OFF_CQ_RING => "io_uring completion queue ring",
+            IORING_OFF_SQES => "io_uring submission entries",
+            _ => return vec![],
+        };
+        vec![" (".white(), ring.yellow(), ")".white()]
+    }
+
+    pub(crate) fn io_uring_waits(&self) -> bool {
+        (self.args[3] & IORING_ENTER_GETEVENTS) != 0
+    }
+}
+
+// name and intent of io_uring_register opcodes
+pub fn io_uring_register_opcode(opcode: u64) -> Option<(&'static str, &'static str)> {
+    REGISTER_OPCODES.get(opcode as usize).copied()
+}
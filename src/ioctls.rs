@@ -0,0 +1,434 @@
+// ioctl request codes are opaque numbers at the syscall level,
+// this module names the well known ones and decodes the structs they exchange
+use crate::{syscall_object::SyscallObject, types::Bytes};
+use colored::{ColoredString, Colorize};
+use nix::libc::{ifreq, winsize, ECHO, ICANON, ISIG};
+use std::net::Ipv4Addr;
+
+// TCGETS and TCSETS exchange the kernel struct termios, not the larger one of glibc
+// c_iflag, c_oflag, c_cflag, c_lflag, c_line and 19 control characters
+const KERNEL_TERMIOS_SIZE: usize = 36;
+const C_LFLAG_OFFSET: usize = 12;
+
+const TCGETS: u64 = 0x5401;
+const TCSETS: u64 = 0x5402;
+const TCSETSW: u64 = 0x5403;
+const TCSETSF: u64 = 0x5404;
+const TCSBRK: u64 = 0x5409;
+const TCXONC: u64 = 0x540A;
+const TCFLSH: u64 = 0x540B;
+const TIOCEXCL: u64 = 0x540C;
+const TIOCNXCL: u64 = 0x540D;
+const TIOCSCTTY: u64 = 0x540E;
+const TIOCGPGRP: u64 = 0x540F;
+const TIOCSPGRP: u64 = 0x5410;
+const TIOCOUTQ: u64 = 0x5411;
+const TIOCSTI: u64 = 0x5412;
+const TIOCGWINSZ: u64 = 0x5413;
+const TIOCSWINSZ: u64 = 0x5414;
+const FIONREAD: u64 = 0x541B;
+const TIOCCONS: u64 = 0x541D;
+const FIONBIO: u64 = 0x5421;
+pub const TIOCNOTTY: u64 = 0x5422;
+const TIOCGSID: u64 = 0x5429;
+const TIOCGPTN: u64 = 0x80045430;
+const TIOCSPTLCK: u64 = 0x40045431;
+const TIOCGPTPEER: u64 = 0x5441;
+const FIONCLEX: u64 = 0x5450;
+const FIOCLEX: u64 = 0x5451;
+const FIOASYNC: u64 = 0x5452;
+const BLKROGET: u64 = 0x125E;
+const BLKRRPART: u64 = 0x125F;
+const BLKGETSIZE: u64 = 0x1260;
+const BLKFLSBUF: u64 = 0x1261;
+const BLKSSZGET: u64 = 0x1268;
+const BLKBSZGET: u64 = 0x80081270;
+const BLKGETSIZE64: u64 = 0x80081272;
+const BLKDISCARD: u64 = 0x1277;
+const FIOSETOWN: u64 = 0x8901;
+const FIOGETOWN: u64 = 0x8903;
+const SIOCATMARK: u64 = 0x8905;
+const SIOCGIFNAME: u64 = 0x8910;
+const SIOCGIFCONF: u64 = 0x8912;
+const SIOCGIFFLAGS: u64 = 0x8913;
+const SIOCSIFFLAGS: u64 = 0x8914;
+const SIOCGIFADDR: u64 = 0x8915;
+const SIOCSIFADDR: u64 = 0x8916;
+const SIOCGIFDSTADDR: u64 = 0x8917;
+const SIOCGIFBRDADDR: u64 = 0x8919;
+const SIOCGIFNETMASK: u64 = 0x891B;
+const SIOCGIFMTU: u64 = 0x8921;
+const SIOCSIFMTU: u64 = 0x8922;
+const SIOCGIFHWADDR: u64 = 0x8927;
+const SIOCGIFINDEX: u64 = 0x8933;
+const FICLONE: u64 = 0x40049409;
+const FS_IOC_GETFLAGS: u64 = 0x80086601;
+const FS_IOC_SETFLAGS: u64 = 0x40086602;
+
+// name and intent of the well known request codes
+pub fn ioctl_request(request: u64) -> Option<(&'static str, &'static str)> {
+    let named = match request {
+        TCGETS => ("TCGETS", "get the terminal attributes of"),
+        TCSETS => ("TCSETS", "set the terminal attributes immediately of"),
+        TCSETSW => ("TCSETSW", "set the terminal attributes after pending output is written of"),
+        TCSETSF => ("TCSETSF", "set the terminal attributes after pending output is written and discard pending input of"),
+        TCSBRK => ("TCSBRK", "wait for pending output to be written to"),
+        TCXONC => ("TCXONC", "suspend or restart transmission on"),
+        TCFLSH => ("TCFLSH", "discard pending input or output of"),
+        TIOCEXCL => ("TIOCEXCL", "enable exclusive mode of"),
+        TIOCNXCL => ("TIOCNXCL", "disable exclusive mode of"),
+        TIOCSCTTY => ("TIOCSCTTY", "make the calling process's controlling terminal"),
+        TIOCGPGRP => ("TIOCGPGRP", "get the foreground process group of"),
+        TIOCSPGRP => ("TIOCSPGRP", "set the foreground process group of"),
+        TIOCOUTQ => ("TIOCOUTQ", "get the number of bytes waiting to be sent in the output buffer of"),
+        TIOCSTI => ("TIOCSTI", "insert a byte into the input queue of"),
+        TIOCGWINSZ => ("TIOCGWINSZ", "get the window size of"),
+        TIOCSWINSZ => ("TIOCSWINSZ", "set the window size of"),
+        FIONREAD => ("FIONREAD", "get the number of bytes available to read from"),
+        TIOCCONS => ("TIOCCONS", "redirect console output to"),
+        FIONBIO => ("FIONBIO", "change the blocking mode of"),
+        TIOCNOTTY => ("TIOCNOTTY", "detach the calling process from its controlling terminal"),
+        TIOCGSID => ("TIOCGSID", "get the session id of"),
+        TIOCGPTN => ("TIOCGPTN", "get the pseudoterminal number of"),
+        TIOCSPTLCK => ("TIOCSPTLCK", "lock or unlock the pseudoterminal of"),
+        TIOCGPTPEER => ("TIOCGPTPEER", "open the peer pseudoterminal of"),
+        FIONCLEX => ("FIONCLEX", "clear close-on-exec on"),
+        FIOCLEX => ("FIOCLEX", "set close-on-exec on"),
+        FIOASYNC => ("FIOASYNC", "change signal driven I/O of"),
+        BLKROGET => ("BLKROGET", "check the read-only status of"),
+        BLKRRPART => ("BLKRRPART", "re-read the partition table of"),
+        BLKGETSIZE => ("BLKGETSIZE", "get the size in 512 Byte sectors of"),
+        BLKFLSBUF => ("BLKFLSBUF", "flush the buffer cache of"),
+        BLKSSZGET => ("BLKSSZGET", "get the logical sector size of"),
+        BLKBSZGET => ("BLKBSZGET", "get the block size of"),
+        BLKGETSIZE64 => ("BLKGETSIZE64", "get the size of"),
+        BLKDISCARD => ("BLKDISCARD", "discard a range of sectors of"),
+        FIOSETOWN => ("FIOSETOWN", "set the process receiving I/O signals of"),
+        FIOGETOWN => ("FIOGETOWN", "get the process receiving I/O signals of"),
+        SIOCATMARK => ("SIOCATMARK", "check if the urgent data mark was reached on"),
+        SIOCGIFNAME => ("SIOCGIFNAME", "get the name of a network interface through"),
+        SIOCGIFCONF => ("SIOCGIFCONF", "get the list of network interface addresses through"),
+        SIOCGIFFLAGS => ("SIOCGIFFLAGS", "get the flags of a network interface through"),
+        SIOCSIFFLAGS => ("SIOCSIFFLAGS", "set the flags of a network interface through"),
+        SIOCGIFADDR => ("SIOCGIFADDR", "get the address of a network interface through"),
+        SIOCSIFADDR => ("SIOCSIFADDR", "set the address of a network interface through"),
+        SIOCGIFDSTADDR => ("SIOCGIFDSTADDR", "get the point-to-point destination address of a network interface through"),
+        SIOCGIFBRDADDR => ("SIOCGIFBRDADDR", "get the broadcast address of a network interface through"),
+        SIOCGIFNETMASK => ("SIOCGIFNETMASK", "get the network mask of a network interface through"),
+        SIOCGIFMTU => ("SIOCGIFMTU", "get the MTU of a network interface through"),
+        SIOCSIFMTU => ("SIOCSIFMTU", "set the MTU of a network interface through"),
+        SIOCGIFHWADDR => ("SIOCGIFHWADDR", "get the hardware address of a network interface through"),
+        SIOCGIFINDEX => ("SIOCGIFINDEX", "get the index of a network interface through"),
+        FICLONE => ("FICLONE", "share all the data of another file with"),
+        FS_IOC_GETFLAGS => ("FS_IOC_GETFLAGS", "get the inode flags of"),
+        FS_IOC_SETFLAGS => ("FS_IOC_SETFLAGS", "set the inode flags of"),
+        _ => return None,
+    };
+    Some(named)
+}
+
+// unknown requests are split into the fields of the _IOC macro
+pub fn ioc_fields(request: u64) -> Vec<ColoredString> {
+    let direction = match (request >> 30) & 3 {
+        0 => "none",
+        1 => "write",
+        2 => "read",
+        _ => "read/write",
+    };
+    let kind = ((request >> 8) & 0xFF) as u8;
+    let kind = if kind.is_ascii_graphic() {
+        format!("'{}' ({kind:#x})", kind as char)
+    } else {
+        format!("{kind:#x}")
+    };
+    vec![
+        "(_IOC direction: ".white(),
+        direction.yellow(),
+        ", type: ".white(),
+        kind.yellow(),
+        ", number: ".white(),
+        (request & 0xFF).to_string().yellow(),
+        ", size: ".white(),
+        Bytes::from(((request >> 16) & 0x3FFF) as usize)
+            .to_string()
+            .yellow(),
+        ")".white(),
+    ]
+}
+
+fn terminal_mode(local_modes: u32) -> Vec<ColoredString> {
+    let mut modes = vec![];
+    if (local_modes & ICANON) == ICANON {
+        modes.push("canonical".yellow());
+    } else {
+        modes.push("raw".yellow());
+    }
+    if (local_modes & ECHO) == ECHO {
+        modes.push("echo".yellow());
+    } else {
+        modes.push("no echo".yellow());
+    }
+    if (local_modes & ISIG) == ISIG {
+        modes.push("signal keys".yellow());
+    }
+    let mut colored = vec![" (".white()];
+    for (index, mode) in modes.into_iter().enumerate() {
+        if index > 0 {
+            colored.push(", ".white());
+        }
+        colored.push(mode);
+    }
+    colored.push(")".white());
+    colored
+}
+
+fn window_size(size: &winsize) -> Vec<ColoredString> {
+    vec![
+        size.ws_col.to_string().yellow(),
+        " columns x ".white(),
+        size.ws_row.to_string().yellow(),
+        " rows".white(),
+    ]
+}
+
+fn interface_name(request: &ifreq) -> String {
+    let name = request
+        .ifr_name
+        .iter()
+        .take_while(|character| **character != 0)
+        .map(|character| *character as u8)
+        .collect::<Vec<u8>>();
+    String::from_utf8_lossy(&name).into_owned()
+}
+
+// the union of struct ifreq starts right after the 16 Byte interface name
+fn ifreq_union(request: &ifreq) -> [u8; 24] {
+    unsafe { std::mem::transmute_copy(&request.ifr_ifru) }
+}
+
+impl SyscallObject {
+    fn read_ioctl_int(&self) -> Option<i32> {
+        SyscallObject::read_bytes_as_struct::<4, i32>(self.args[2] as usize, self.child)
+    }
+
+    fn read_ioctl_local_modes(&self) -> Option<u32> {
+        let attributes =
+            SyscallObject::read_bytes::<KERNEL_TERMIOS_SIZE>(self.args[2] as usize, self.child)?;
+        Some(u32::from_ne_bytes(
+            attributes[C_LFLAG_OFFSET..C_LFLAG_OFFSET + 4]
+                .try_into()
+                .unwrap(),
+        ))
+    }
+
+    fn read_ioctl_ifreq(&self) -> Option<ifreq> {
+        SyscallObject::read_bytes_as_struct::<40, ifreq>(self.args[2] as usize, self.child)
+    }
+
+    // details of what is being requested, read before the syscall runs
+    pub(crate) fn ioctl_request_details(&self) -> Vec<ColoredString> {
+        match self.args[1] {
+            TCSETS | TCSETSW | TCSETSF => match self.read_ioctl_local_modes() {
+                Some(local_modes) => terminal_mode(local_modes),
+                None => vec![],
+            },
+            TIOCSWINSZ => {
+                match SyscallObject::read_bytes_as_struct::<8, winsize>(
+                    self.args[2] as usize,
+                    self.child,
+                ) {
+                    Some(size) => {
+                        let mut details = vec![" to ".white()];
+                        details.extend(window_size(&size));
+                        details
+                    }
+                    None => vec![],
+                }
+            }
+            TIOCSPGRP => match self.read_ioctl_int() {
+                Some(group) => vec![" to ".white(), group.to_string().yellow()],
+                None => vec![],
+            },
+            FIONBIO => match self.read_ioctl_int() {
+                Some(0) => vec![" to ".white(), "blocking".yellow()],
+                Some(_) => vec![" to ".white(), "non-blocking".yellow()],
+                None => vec![],
+            },
+            FIOASYNC => match self.read_ioctl_int() {
+                Some(0) => vec![" (".white(), "disable".yellow(), ")".white()],
+                Some(_) => vec![" (".white(), "enable".yellow(), ")".white()],
+                None => vec![],
+            },
+            TIOCSCTTY if self.args[2] == 1 => vec![
+                " (".white(),
+                "steal it from another session if needed".yellow(),
+                ")".white(),
+            ],
+            TIOCSPTLCK => match self.read_ioctl_int() {
+                Some(0) => vec![" (".white(), "unlock".yellow(), ")".white()],
+                Some(_) => vec![" (".white(), "lock".yellow(), ")".white()],
+                None => vec![],
+            },
+            SIOCGIFFLAGS | SIOCSIFFLAGS | SIOCGIFADDR | SIOCSIFADDR | SIOCGIFDSTADDR
+            | SIOCGIFBRDADDR | SIOCGIFNETMASK | SIOCGIFMTU | SIOCSIFMTU | SIOCGIFHWADDR
+            | SIOCGIFINDEX => match self.read_ioctl_ifreq() {
+                Some(request) => vec![" (".white(), interface_name(&request).blue(), ")".white()],
+                None => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    // what the kernel filled in for the requests that return data
+    pub(crate) fn ioctl_result(&self) -> Vec<ColoredString> {
+        match self.args[1] {
+            TCGETS => match self.read_ioctl_local_modes() {
+                Some(local_modes) => {
+                    let mut result = vec!["terminal attributes retrieved".green()];
+                    result.extend(terminal_mode(local_modes));
+                    result
+                }
+                None => vec![],
+            },
+            TIOCGWINSZ => {
+                match SyscallObject::read_bytes_as_struct::<8, winsize>(
+                    self.args[2] as usize,
+                    self.child,
+                ) {
+                    Some(size) => window_size(&size),
+                    None => vec![],
+                }
+            }
+            TIOCGPGRP => match self.read_ioctl_int() {
+                Some(group) => vec![
+                    "foreground process group: ".green(),
+                    group.to_string().yellow(),
+                ],
+                None => vec![],
+            },
+            TIOCGSID => match self.read_ioctl_int() {
+                Some(session) => vec!["session id: ".green(), session.to_string().yellow()],
+                None => vec![],
+            },
+            TIOCGPTN => match self.read_ioctl_int() {
+                Some(number) => vec![
+                    "pseudoterminal: ".green(),
+                    format!("/dev/pts/{number}").yellow(),
+                ],
+                None => vec![],
+            },
+            FIONREAD | TIOCOUTQ => match self.read_ioctl_int() {
+                Some(bytes) => vec![
+                    Bytes::from(bytes as usize).to_string().yellow(),
+                    if self.args[1] == FIONREAD {
+                        " available to read".green()
+                    } else {
+                        " waiting to be sent".green()
+                    },
+                ],
+                None => vec![],
+            },
+            BLKGETSIZE64 => {
+                match SyscallObject::read_bytes_as_struct::<8, u64>(
+                    self.args[2] as usize,
+                    self.child,
+                ) {
+                    Some(size) => vec![
+                        "device size: ".green(),
+                        Bytes::from(size as usize).to_string().yellow(),
+                    ],
+                    None => vec![],
+                }
+            }
+            BLKGETSIZE => {
+                match SyscallObject::read_bytes_as_struct::<8, u64>(
+                    self.args[2] as usize,
+                    self.child,
+                ) {
+                    Some(sectors) => vec![
+                        "device size: ".green(),
+                        sectors.to_string().yellow(),
+                        " sectors".green(),
+                    ],
+                    None => vec![],
+                }
+            }
+            BLKSSZGET | BLKBSZGET => match self.read_ioctl_int() {
+                Some(size) => vec![
+                    if self.args[1] == BLKSSZGET {
+                        "sector size: ".green()
+                    } else {
+                        "block size: ".green()
+                    },
+                    Bytes::from(size as usize).to_string().yellow(),
+                ],
+                None => vec![],
+            },
+            BLKROGET => match self.read_ioctl_int() {
+                Some(0) => vec!["device is writable".green()],
+                Some(_) => vec!["device is read-only".green()],
+                None => vec![],
+            },
+            SIOCGIFADDR | SIOCGIFDSTADDR | SIOCGIFBRDADDR | SIOCGIFNETMASK => {
+                match self.read_ioctl_ifreq() {
+                    Some(request) => {
+                        // struct sockaddr_in: family, port, then the address
+                        let union = ifreq_union(&request);
+                        let address = Ipv4Addr::new(union[4], union[5], union[6], union[7]);
+                        vec!["address: ".green(), address.to_string().yellow()]
+                    }
+                    None => vec![],
+                }
+            }
+            SIOCGIFHWADDR => match self.read_ioctl_ifreq() {
+                Some(request) => {
+                    // struct sockaddr: family, then the hardware address in sa_data
+                    let union = ifreq_union(&request);
+                    let hardware_address = union[2..8]
+                        .iter()
+                        .map(|byte| format!("{byte:02x}"))
+                        .collect::<Vec<String>>()
+                        .join(":");
+                    vec!["hardware address: ".green(), hardware_address.yellow()]
+                }
+                None => vec![],
+            },
+            SIOCGIFMTU | SIOCGIFINDEX => match self.read_ioctl_ifreq() {
+                Some(request) => {
+                    let union = ifreq_union(&request);
+                    let value = i32::from_ne_bytes([union[0], union[1], union[2], union[3]]);
+                    vec![
+                        if self.args[1] == SIOCGIFMTU {
+                            "MTU: ".green()
+                        } else {
+                            "interface index: ".green()
+                        },
+                        value.to_string().yellow(),
+                    ]
+                }
+                None => vec![],
+            },
+            SIOCGIFFLAGS => match self.read_ioctl_ifreq() {
+                Some(request) => {
+                    let union = ifreq_union(&request);
+                    let flags = i16::from_ne_bytes([union[0], union[1]]) as i32;
+                    let mut result = vec!["interface is ".green()];
+                    if (flags & nix::libc::IFF_UP) == nix::libc::IFF_UP {
+                        result.push("up".yellow());
+                    } else {
+                        result.push("down".yellow());
+                    }
+                    if (flags & nix::libc::IFF_RUNNING) == nix::libc::IFF_RUNNING {
+                        result.push(", running".yellow());
+                    }
+                    if (flags & nix::libc::IFF_LOOPBACK) == nix::libc::IFF_LOOPBACK {
+                        result.push(", loopback".yellow());
+                    }
+                    result
+                }
+                None => vec![],
+            },
+            _ => vec![],
+        }
+    }
+}
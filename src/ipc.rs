@@ -0,0 +1,755 @@
+// decoding of System V shared memory, semaphores and message queues, and of POSIX message queues
+// the sizes of semaphore sets are not passed to semctl, they are looked up in /proc/sysvipc
+use crate::{
+    error_explainer::{group_name, user_name},
+    one_line_formatter::format_timespec,
+    syscall_object::SyscallObject,
+    utilities::x86_signal_to_string,
+};
+use colored::{ColoredString, Colorize};
+use nix::{
+    libc::{
+        ipc_perm, mq_attr, msqid_ds, semid_ds, shmid_ds, timespec, IPC_CREAT, IPC_EXCL, IPC_INFO,
+        IPC_NOWAIT, IPC_RMID, IPC_SET, IPC_STAT, MSG_COPY, MSG_EXCEPT, MSG_INFO, MSG_NOERROR,
+        MSG_STAT, O_ACCMODE, O_CREAT, O_EXCL, O_NONBLOCK, O_RDONLY, O_WRONLY, SHM_EXEC,
+        SHM_HUGETLB, SHM_LOCK, SHM_NORESERVE, SHM_RDONLY, SHM_REMAP, SHM_RND, SHM_UNLOCK,
+        SIGEV_NONE, SIGEV_SIGNAL, SIGEV_THREAD,
+    },
+    unistd::Pid,
+};
+use std::{
+    mem::size_of,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use syscalls::Sysno;
+
+// glibc ors this into the command on some architectures to ask for the 64 bit structures
+const IPC_64: u64 = 0x100;
+
+const SHM_STAT: u64 = 13;
+const SHM_INFO: u64 = 14;
+const SHM_STAT_ANY: u64 = 15;
+
+const GETPID: u64 = 11;
+const GETVAL: u64 = 12;
+const GETALL: u64 = 13;
+const GETNCNT: u64 = 14;
+const GETZCNT: u64 = 15;
+const SETVAL: u64 = 16;
+const SETALL: u64 = 17;
+const SEM_STAT: u64 = 18;
+const SEM_INFO: u64 = 19;
+const SEM_STAT_ANY: u64 = 20;
+const SEM_UNDO: i16 = 0x1000;
+
+const MSG_STAT_ANY: u64 = 13;
+
+const SHMAT_FLAGS: [(i32, &str); 4] = [
+    (SHM_RDONLY, "read-only"),
+    (SHM_EXEC, "executable"),
+    (SHM_RND, "rounding the address down to SHMLBA"),
+    (SHM_REMAP, "replacing the mappings already there"),
+];
+
+// semaphore sets with more members than this only have the first ones listed
+const LISTED_SEMAPHORES: usize = 16;
+
+fn read_text(address: u64, child: Pid) -> String {
+    if address == 0 {
+        return String::new();
+    }
+    SyscallObject::read_bytes_until_null(address as usize, child)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
+
+fn flag_words(flags: i32, table: &[(i32, &'static str)]) -> Vec<&'static str> {
+    table
+        .iter()
+        .filter(|(flag, _)| (flags & flag) != 0)
+        .map(|(_, word)| *word)
+        .collect()
+}
+
+fn ipc_key(key: u64) -> ColoredString {
+    if key as i32 == 0 {
+        "IPC_PRIVATE".yellow()
+    } else {
+        format!("0x{:08x}", key as u32).yellow()
+    }
+}
+
+fn permissions(mode: u64) -> String {
+    let mut rwx = String::new();
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        rwx.push(if (bits & 4) != 0 { 'r' } else { '-' });
+        rwx.push(if (bits & 2) != 0 { 'w' } else { '-' });
+        rwx.push(if (bits & 1) != 0 { 'x' } else { '-' });
+    }
+    format!("{:04o} ({rwx})", mode & 0o777)
+}
+
+fn signal_name(signal: i32) -> String {
+    x86_signal_to_string(signal as u64)
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("signal {signal}"))
+}
+
+// the row of /proc/sysvipc/<kind> describing the id, split on whitespace
+fn sysvipc_row(kind: &str, id: u64) -> Option<Vec<String>> {
+    let table = std::fs::read_to_string(format!("/proc/sysvipc/{kind}")).ok()?;
+    table.lines().skip(1).find_map(|line| {
+        let columns = line
+            .split_whitespace()
+            .map(|column| column.to_owned())
+            .collect::<Vec<String>>();
+        (columns.get(1)? == &id.to_string()).then_some(columns)
+    })
+}
+
+fn owner(permission: &ipc_perm) -> Vec<ColoredString> {
+    vec![
+        ", owned by ".white(),
+        user_name(permission.uid).yellow(),
+        ":".white(),
+        group_name(permission.gid).yellow(),
+        ", permissions ".white(),
+        permissions(permission.mode as u64).yellow(),
+    ]
+}
+
+fn creation(flags: u64, object: &str, key: u64) -> Vec<ColoredString> {
+    let flags = flags as i32;
+    let verb = if (flags & IPC_CREAT) == 0 {
+        "get the "
+    } else if (flags & IPC_EXCL) != 0 {
+        "create the "
+    } else {
+        "get or create the "
+    };
+    vec![
+        verb.white(),
+        object.white(),
+        " with the key ".white(),
+        ipc_key(key),
+    ]
+}
+
+fn creation_mode(flags: u64) -> Vec<ColoredString> {
+    if (flags as i32 & IPC_CREAT) == 0 {
+        return vec![];
+    }
+    vec![", permissions ".white(), permissions(flags).yellow()]
+}
+
+impl SyscallObject {
+    pub(crate) fn read_struct<T>(&self, address: u64) -> Option<T> {
+        if address == 0 {
+            return None;
+        }
+        let bytes = SyscallObject::read_bytes_specific_length(
+            address as usize,
+            self.child,
+            size_of::<T>(),
+        )?;
+        if bytes.len() < size_of::<T>() {
+            return None;
+        }
+        Some(unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) })
+    }
+
+    fn mq_descriptor(&self) -> ColoredString {
+        SyscallObject::style_file_descriptor(self.args[0], self.child)
+            .unwrap_or_else(|| self.args[0].to_string())
+            .yellow()
+    }
+
+    // mq_timedsend and mq_timedreceive take an absolute CLOCK_REALTIME deadline
+    fn mq_deadline(&self, address: u64, intent: &mut Vec<ColoredString>) {
+        let Some(deadline) = self.read_struct::<timespec>(address) else {
+            return;
+        };
+        let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+            return;
+        };
+        // seconds and nanoseconds are kept apart, a far away deadline does not fit in nanoseconds
+        let mut seconds = deadline.tv_sec.saturating_sub(now.as_secs() as i64);
+        let mut nanoseconds = deadline.tv_nsec.saturating_sub(now.subsec_nanos() as i64);
+        if nanoseconds < 0 {
+            seconds = seconds.saturating_sub(1);
+            nanoseconds = nanoseconds.saturating_add(1_000_000_000);
+        }
+        if seconds < 0 {
+            (seconds, nanoseconds) = (0, 0);
+        }
+        intent.push(", giving up ".white());
+        format_timespec(seconds, nanoseconds, intent);
+    }
+
+    // nsops comes from the tracee, the kernel refuses too many with E2BIG
+    fn semaphore_operations(&self) -> Vec<ColoredString> {
+        let count = self.args[2] as usize;
+        let Some(bytes) = SyscallObject::read_bytes_specific_length(
+            self.args[1] as usize,
+            self.child,
+            count.min(LISTED_SEMAPHORES) * 6,
+        ) else {
+            return vec!["unreadable operations".white()];
+        };
+        let operations = bytes
+            .chunks_exact(6)
+            .map(|operation| {
+                let number = u16::from_le_bytes([operation[0], operation[1]]);
+                let value = i16::from_le_bytes([operation[2], operation[3]]);
+                let flags = i16::from_le_bytes([operation[4], operation[5]]);
+                let mut described = match value {
+                    0 => format!("wait for #{number} to be zero"),
+                    value if value > 0 => format!("add {value} to #{number}"),
+                    value => format!("take {} from #{number}", -(value as i32)),
+                };
+                if (flags & IPC_NOWAIT as i16) != 0 {
+                    described.push_str(" without blocking");
+                }
+                if (flags & SEM_UNDO) != 0 {
+                    described.push_str(" (undone when the process exits)");
+                }
+                described
+            })
+            .collect::<Vec<String>>();
+        let mut described = vec![operations.join(", ").yellow()];
+        if count > LISTED_SEMAPHORES {
+            described.push(", and ".white());
+            described.push((count - LISTED_SEMAPHORES).to_string().yellow());
+            described.push(" more".white());
+        }
+        described
+    }
+
+    fn shm_ctl_intent(&self) -> Vec<ColoredString> {
+        let id = self.args[0].to_string().yellow();
+        match self.args[1] & !IPC_64 {
+            command if command == IPC_RMID as u64 => vec![
+                "remove the shared memory segment ".white(),
+                id,
+                " once the last process detaches from it".white(),
+            ],
+            command if command == IPC_SET as u64 => {
+                let mut intent = vec!["change the shared memory segment ".white(), id];
+                if let Some(info) = self.read_struct::<shmid_ds>(self.args[2]) {
+                    intent.extend(owner(&info.shm_perm));
+                }
+                intent
+            }
+            command if command == IPC_STAT as u64 || command == SHM_STAT_ANY => {
+                vec![
+                    "get the information of the shared memory segment ".white(),
+                    id,
+                ]
+            }
+            SHM_STAT => vec![
+                "get the information of the shared memory segment at index ".white(),
+                id,
+            ],
+            command if command == IPC_INFO as u64 => {
+                vec!["get the system wide shared memory limits".white()]
+            }
+            SHM_INFO => vec!["get the system wide shared memory usage".white()],
+            command if command == SHM_LOCK as u64 => vec![
+                "lock the shared memory segment ".white(),
+                id,
+                " into memory".white(),
+            ],
+            command if command == SHM_UNLOCK as u64 => vec![
+                "allow the shared memory segment ".white(),
+                id,
+                " to be swapped out again".white(),
+            ],
+            command => vec![
+                "perform the unknown command ".white(),
+                command.to_string().yellow(),
+                " on the shared memory segment ".white(),
+                id,
+            ],
+        }
+    }
+
+    fn sem_ctl_intent(&self) -> Vec<ColoredString> {
+        let id = self.args[0].to_string().yellow();
+        let number = format!("#{}", self.args[1]).yellow();
+        match self.args[2] & !IPC_64 {
+            command if command == IPC_RMID as u64 => vec![
+                "remove the semaphore set ".white(),
+                id,
+                ", waking up the processes waiting on it".white(),
+            ],
+            command if command == IPC_SET as u64 => {
+                let mut intent = vec!["change the semaphore set ".white(), id];
+                if let Some(info) = self.read_struct::<semid_ds>(self.args[3]) {
+                    intent.extend(owner(&info.sem_perm));
+                }
+                intent
+            }
+            command if command == IPC_STAT as u64 || command == SEM_STAT_ANY => {
+                vec!["get the information of the semaphore set ".white(), id]
+            }
+            SEM_STAT => vec![
+                "get the information of the semaphore set at index ".white(),
+                id,
+            ],
+            command if command == IPC_INFO as u64 || command == SEM_INFO => {
+                vec!["get the system wide semaphore limits".white()]
+            }
+            GETVAL => vec![
+                "get the value of semaphore ".white(),
+                number,
+                " of the set ".white(),
+                id,
+            ],
+            SETVAL => vec![
+                "set semaphore ".white(),
+                number,
+                " of the set ".white(),
+                id,
+                " to ".white(),
+                (self.args[3] as i32).to_string().yellow(),
+            ],
+            GETPID => vec![
+                "get the last process to operate on semaphore ".white(),
+                number,
+                " of the set ".white(),
+                id,
+            ],
+            GETNCNT => vec![
+                "get how many processes wait for semaphore ".white(),
+                number,
+                " of the set ".white(),
+                id,
+                " to increase".white(),
+            ],
+            GETZCNT => vec![
+                "get how many processes wait for semaphore ".white(),
+                number,
+                " of the set ".white(),
+                id,
+                " to become zero".white(),
+            ],
+            GETALL => vec!["get the values of the semaphore set ".white(), id],
+            SETALL => {
+                let mut intent = vec!["set the values of the semaphore set ".white(), id];
+                if let Some(values) = self.semaphore_values(self.args[3]) {
+                    intent.push(" to ".white());
+                    intent.push(values.yellow());
+                }
+                intent
+            }
+            command => vec![
+                "perform the unknown command ".white(),
+                command.to_string().yellow(),
+                " on the semaphore set ".white(),
+                id,
+            ],
+        }
+    }
+
+    // GETALL and SETALL arrays are as long as the set, which only /proc/sysvipc knows
+    fn semaphore_values(&self, address: u64) -> Option<String> {
+        let row = sysvipc_row("sem", self.args[0])?;
+        let count = row.get(3)?.parse::<usize>().ok()?;
+        let bytes = SyscallObject::read_bytes_specific_length(
+            address as usize,
+            self.child,
+            count.min(LISTED_SEMAPHORES) * 2,
+        )?;
+        let mut values = bytes
+            .chunks_exact(2)
+            .map(|value| u16::from_le_bytes([value[0], value[1]]).to_string())
+            .collect::<Vec<String>>();
+        if count > LISTED_SEMAPHORES {
+            values.push(format!("{} more", count - LISTED_SEMAPHORES));
+        }
+        Some(format!("[{}]", values.join(", ")))
+    }
+
+    fn msg_ctl_intent(&self) -> Vec<ColoredString> {
+        let id = self.args[0].to_string().yellow();
+        match self.args[1] & !IPC_64 {
+            command if command == IPC_RMID as u64 => vec![
+                "remove the message queue ".white(),
+                id,
+                ", discarding its messages".white(),
+            ],
+            command if command == IPC_SET as u64 => {
+                let mut intent = vec!["change the message queue ".white(), id];
+                if let Some(info) = self.read_struct::<msqid_ds>(self.args[2]) {
+                    intent.extend(owner(&info.msg_perm));
+                    intent.push(", holding at most ".white());
+                    intent
+                        .push(SyscallObject::style_bytes_length_specific(info.msg_qbytes).yellow());
+                }
+                intent
+            }
+            command if command == IPC_STAT as u64 || command == MSG_STAT_ANY => {
+                vec!["get the information of the message queue ".white(), id]
+            }
+            command if command == MSG_STAT as u64 => vec![
+                "get the information of the message queue at index ".white(),
+                id,
+            ],
+            command if command == IPC_INFO as u64 || command == MSG_INFO as u64 => {
+                vec!["get the system wide message queue limits".white()]
+            }
+            command => vec![
+                "perform the unknown command ".white(),
+                command.to_string().yellow(),
+                " on the message queue ".white(),
+                id,
+            ],
+        }
+    }
+
+    fn msgrcv_intent(&self) -> Vec<ColoredString> {
+        let flags = self.args[4] as i32;
+        let kind = self.args[3] as i64;
+        let mut intent = if (flags & MSG_COPY) != 0 {
+            vec![
+                "copy the message at index ".white(),
+                kind.to_string().yellow(),
+                " without removing it".white(),
+            ]
+        } else if kind == 0 {
+            vec!["receive the first message".white()]
+        } else if kind < 0 {
+            vec![
+                "receive the message with the lowest type up to ".white(),
+                kind.unsigned_abs().to_string().yellow(),
+            ]
+        } else if (flags & MSG_EXCEPT) != 0 {
+            vec![
+                "receive the first message not of type ".white(),
+                kind.to_string().yellow(),
+            ]
+        } else {
+            vec![
+                "receive the first message of type ".white(),
+                kind.to_string().yellow(),
+            ]
+        };
+        intent.push(" from the message queue ".white());
+        intent.push(self.args[0].to_string().yellow());
+        intent.push(", at most ".white());
+        intent.push(SyscallObject::style_bytes_length_specific(self.args[2]).yellow());
+        if (flags & MSG_NOERROR) != 0 {
+            intent.push(" (longer messages are truncated)".white());
+        }
+        if (flags & IPC_NOWAIT) != 0 {
+            intent.push(" (fail instead of waiting for one)".white());
+        }
+        intent
+    }
+
+    fn mq_open_intent(&self) -> Vec<ColoredString> {
+        let flags = self.args[1] as i32;
+        let mut intent = if (flags & O_CREAT) == 0 {
+            vec!["open the message queue ".white()]
+        } else if (flags & O_EXCL) != 0 {
+            vec!["create the message queue ".white()]
+        } else {
+            vec!["open or create the message queue ".white()]
+        };
+        intent.push(read_text(self.args[0], self.child).yellow());
+        intent.push(match flags & O_ACCMODE {
+            O_RDONLY => " for receiving".white(),
+            O_WRONLY => " for sending".white(),
+            _ => " for sending and receiving".white(),
+        });
+        if (flags & O_CREAT) != 0 {
+            intent.push(", permissions ".white());
+            intent.push(permissions(self.args[2]).yellow());
+            if let Some(attributes) = self.read_struct::<mq_attr>(self.args[3]) {
+                intent.push(", holding at most ".white());
+                intent.push(attributes.mq_maxmsg.to_string().yellow());
+                intent.push(" messages of ".white());
+                intent.push(
+                    SyscallObject::style_bytes_length_specific(attributes.mq_msgsize as u64)
+                        .yellow(),
+                );
+            }
+        }
+        if (flags & O_NONBLOCK) != 0 {
+            intent.push(" (without blocking)".white());
+        }
+        intent
+    }
+
+    fn mq_notify_intent(&self) -> Vec<ColoredString> {
+        if self.args[1] == 0 {
+            return vec![
+                "stop being notified of messages arriving on ".white(),
+                self.mq_descriptor(),
+            ];
+        }
+        // struct sigevent: the 8 byte sigev_value, then sigev_signo and sigev_notify
+        let Some(event) = SyscallObject::read_bytes::<16>(self.args[1] as usize, self.child) else {
+            return vec![
+                "be notified of messages arriving on ".white(),
+                self.mq_descriptor(),
+            ];
+        };
+        let signal = i32::from_le_bytes(event[8..12].try_into().unwrap());
+        let notify = i32::from_le_bytes(event[12..16].try_into().unwrap());
+        let mut intent = match notify {
+            SIGEV_SIGNAL => vec![
+                "be sent ".white(),
+                signal_name(signal).yellow(),
+                " when a message arrives on ".white(),
+            ],
+            SIGEV_THREAD => vec![
+                "be notified through the netlink socket ".white(),
+                signal.to_string().yellow(),
+                " when a message arrives on ".white(),
+            ],
+            SIGEV_NONE => vec![
+                "claim the notification without being notified of messages arriving on ".white(),
+            ],
+            _ => vec!["be notified of messages arriving on ".white()],
+        };
+        intent.push(self.mq_descriptor());
+        intent.push(" while it is empty".white());
+        intent
+    }
+
+    pub(crate) fn ipc_intent(&self) -> Vec<ColoredString> {
+        match self.sysno {
+            Sysno::shmget => {
+                let mut intent = creation(self.args[2], "shared memory segment", self.args[0]);
+                intent.push(" of ".white());
+                intent.push(SyscallObject::style_bytes_length_specific(self.args[1]).yellow());
+                intent.extend(creation_mode(self.args[2]));
+                if (self.args[2] as i32 & SHM_HUGETLB) != 0 {
+                    intent.push(" (backed by huge pages)".white());
+                }
+                if (self.args[2] as i32 & SHM_NORESERVE) != 0 {
+                    intent.push(" (without reserving swap)".white());
+                }
+                intent
+            }
+            Sysno::semget => {
+                let mut intent = creation(self.args[2], "semaphore set", self.args[0]);
+                intent.push(" of ".white());
+                intent.push(self.args[1].to_string().yellow());
+                intent.push(" semaphores".white());
+                intent.extend(creation_mode(self.args[2]));
+                intent
+            }
+            Sysno::msgget => {
+                let mut intent = creation(self.args[1], "message queue", self.args[0]);
+                intent.extend(creation_mode(self.args[1]));
+                intent
+            }
+            Sysno::shmat => {
+                let mut intent = vec![
+                    "attach the shared memory segment ".white(),
+                    self.args[0].to_string().yellow(),
+                ];
+                if self.args[1] != 0 {
+                    intent.push(" at ".white());
+                    intent.push(format!("0x{:x}", self.args[1]).yellow());
+                }
+                let flags = flag_words(self.args[2] as i32, &SHMAT_FLAGS);
+                if !flags.is_empty() {
+                    intent.push(" (".white());
+                    intent.push(flags.join(", ").yellow());
+                    intent.push(")".white());
+                }
+                intent
+            }
+            Sysno::shmdt => vec![
+                "detach the shared memory segment attached at ".white(),
+                format!("0x{:x}", self.args[0]).yellow(),
+            ],
+            Sysno::shmctl => self.shm_ctl_intent(),
+            Sysno::semctl => self.sem_ctl_intent(),
+            Sysno::msgctl => self.msg_ctl_intent(),
+            Sysno::semop | Sysno::semtimedop => {
+                let mut intent = vec![
+                    "on the semaphore set ".white(),
+                    self.args[0].to_string().yellow(),
+                    ": ".white(),
+                ];
+                intent.extend(self.semaphore_operations());
+                if self.sysno == Sysno::semtimedop {
+                    if let Some(timeout) = self.read_struct::<timespec>(self.args[3]) {
+                        intent.push(", giving up ".white());
+                        format_timespec(timeout.tv_sec, timeout.tv_nsec, &mut intent);
+                    }
+                }
+                intent
+            }
+            Sysno::msgsnd => {
+                let kind = self.read_struct::<i64>(self.args[1]).unwrap_or(0);
+                let mut intent = vec![
+                    "send a message of type ".white(),
+                    kind.to_string().yellow(),
+                    " with ".white(),
+                    SyscallObject::style_bytes_length_specific(self.args[2]).yellow(),
+                    " to the message queue ".white(),
+                    self.args[0].to_string().yellow(),
+                ];
+                if (self.args[3] as i32 & IPC_NOWAIT) != 0 {
+                    intent.push(" (fail instead of waiting if the queue is full)".white());
+                }
+                intent
+            }
+            Sysno::msgrcv => self.msgrcv_intent(),
+            Sysno::mq_open => self.mq_open_intent(),
+            Sysno::mq_unlink => vec![
+                "remove the message queue ".white(),
+                read_text(self.args[0], self.child).yellow(),
+            ],
+            Sysno::mq_timedsend => {
+                let mut intent = vec![
+                    "send a message of ".white(),
+                    SyscallObject::style_bytes_length_specific(self.args[2]).yellow(),
+                    " with priority ".white(),
+                    self.args[3].to_string().yellow(),
+                    " to ".white(),
+                    self.mq_descriptor(),
+                ];
+                self.mq_deadline(self.args[4], &mut intent);
+                intent
+            }
+            Sysno::mq_timedreceive => {
+                let mut intent = vec![
+                    "receive the oldest message of the highest priority, at most ".white(),
+                    SyscallObject::style_bytes_length_specific(self.args[2]).yellow(),
+                    ", from ".white(),
+                    self.mq_descriptor(),
+                ];
+                self.mq_deadline(self.args[4], &mut intent);
+                intent
+            }
+            Sysno::mq_notify => self.mq_notify_intent(),
+            _ => match self.read_struct::<mq_attr>(self.args[1]) {
+                Some(attributes) if (attributes.mq_flags as i32 & O_NONBLOCK) != 0 => vec![
+                    "make ".white(),
+                    self.mq_descriptor(),
+                    " non-blocking".white(),
+                ],
+                Some(_) => vec!["make ".white(), self.mq_descriptor(), " blocking".white()],
+                None => vec!["get the attributes of ".white(), self.mq_descriptor()],
+            },
+        }
+    }
+
+    fn ipc_stat_result(&self, command: u64, buffer: u64) -> Option<Vec<ColoredString>> {
+        match self.sysno {
+            Sysno::shmctl
+                if command == IPC_STAT as u64 || command == SHM_STAT || command == SHM_STAT_ANY =>
+            {
+                let info = self.read_struct::<shmid_ds>(buffer)?;
+                let mut result = vec![
+                    SyscallObject::style_bytes_length_specific(info.shm_segsz as u64).yellow(),
+                    " attached ".green(),
+                    info.shm_nattch.to_string().yellow(),
+                    " times, created by process ".green(),
+                    info.shm_cpid.to_string().yellow(),
+                ];
+                result.extend(owner(&info.shm_perm));
+                Some(result)
+            }
+            Sysno::semctl
+                if command == IPC_STAT as u64 || command == SEM_STAT || command == SEM_STAT_ANY =>
+            {
+                let info = self.read_struct::<semid_ds>(buffer)?;
+                let mut result = vec![info.sem_nsems.to_string().yellow(), " semaphores".green()];
+                result.extend(owner(&info.sem_perm));
+                Some(result)
+            }
+            Sysno::msgctl
+                if command == IPC_STAT as u64
+                    || command == MSG_STAT as u64
+                    || command == MSG_STAT_ANY =>
+            {
+                let info = self.read_struct::<msqid_ds>(buffer)?;
+                let mut result = vec![
+                    info.msg_qnum.to_string().yellow(),
+                    " messages queued, holding at most ".green(),
+                    SyscallObject::style_bytes_length_specific(info.msg_qbytes).yellow(),
+                ];
+                result.extend(owner(&info.msg_perm));
+                Some(result)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn ipc_result(&self) -> Vec<ColoredString> {
+        let value = self.result.0.unwrap_or(0);
+        match self.sysno {
+            Sysno::shmget | Sysno::semget | Sysno::msgget => {
+                vec!["id ".green(), value.to_string().yellow()]
+            }
+            Sysno::shmat => vec!["attached at ".green(), format!("0x{value:x}").yellow()],
+            Sysno::shmctl | Sysno::msgctl => {
+                let command = self.args[1] & !IPC_64;
+                self.ipc_stat_result(command, self.args[2])
+                    .unwrap_or_else(|| vec!["successful".green()])
+            }
+            Sysno::semctl => match self.args[2] & !IPC_64 {
+                GETVAL => vec!["value ".green(), value.to_string().yellow()],
+                GETPID => vec!["process ".green(), value.to_string().yellow()],
+                GETNCNT | GETZCNT => vec![value.to_string().yellow(), " waiting".green()],
+                GETALL => match self.semaphore_values(self.args[3]) {
+                    Some(values) => vec!["values ".green(), values.yellow()],
+                    None => vec!["successful".green()],
+                },
+                command => self
+                    .ipc_stat_result(command, self.args[3])
+                    .unwrap_or_else(|| vec!["successful".green()]),
+            },
+            Sysno::msgrcv => {
+                let kind = self.read_struct::<i64>(self.args[1]).unwrap_or(0);
+                vec![
+                    "received a message of type ".green(),
+                    kind.to_string().yellow(),
+                    " with ".green(),
+                    SyscallObject::style_bytes_length_specific(value).yellow(),
+                ]
+            }
+            Sysno::mq_open => vec![
+                "message queue descriptor ".green(),
+                value.to_string().yellow(),
+            ],
+            Sysno::mq_timedreceive => {
+                let mut result = vec![
+                    "received ".green(),
+                    SyscallObject::style_bytes_length_specific(value).yellow(),
+                ];
+                if let Some(priority) = self.read_struct::<u32>(self.args[3]) {
+                    result.push(" with priority ".green());
+                    result.push(priority.to_string().yellow());
+                }
+                result
+            }
+            Sysno::mq_getsetattr => match self.read_struct::<mq_attr>(self.args[2]) {
+                Some(attributes) => {
+                    let mut result = vec![
+                        attributes.mq_curmsgs.to_string().yellow(),
+                        " messages queued, at most ".green(),
+                        attributes.mq_maxmsg.to_string().yellow(),
+                        " of ".green(),
+                        SyscallObject::style_bytes_length_specific(attributes.mq_msgsize as u64)
+                            .yellow(),
+                    ];
+                    if (attributes.mq_flags as i32 & O_NONBLOCK) != 0 {
+                        result.push(", non-blocking".green());
+                    }
+                    result
+                }
+                None => vec!["successful".green()],
+            },
+            _ => vec!["successful".green()],
+        }
+    }
+}
@@ -0,0 +1,462 @@
+// decoding of add_key, request_key and keyctl
+// key payloads are secrets more often than not, they stay redacted unless --show-key-payloads is passed
+use crate::{syscall_object::SyscallObject, utilities::SHOW_KEY_PAYLOADS};
+use colored::{ColoredString, Colorize};
+use nix::unistd::Pid;
+
+// the KEY_SPEC_* ids standing for the special keyrings of the caller, from -1 down
+const SPECIAL_KEYRINGS: [&str; 8] = [
+    "the thread keyring",
+    "the process keyring",
+    "the session keyring",
+    "the user keyring",
+    "the user session keyring",
+    "the group keyring",
+    "the authorization key of the request being serviced",
+    "the keyring of the requestor",
+];
+
+// the KEY_REQKEY_DEFL_* values of KEYCTL_SET_REQKEY_KEYRING, from 0 up
+const DEFAULT_KEYRINGS: [&str; 8] = [
+    "the default keyring",
+    "the thread keyring",
+    "the process keyring",
+    "the session keyring",
+    "the user keyring",
+    "the user session keyring",
+    "the group keyring",
+    "the keyring of the requestor",
+];
+
+const KEYCTL_OPERATIONS: [&str; 33] = [
+    "KEYCTL_GET_KEYRING_ID",
+    "KEYCTL_JOIN_SESSION_KEYRING",
+    "KEYCTL_UPDATE",
+    "KEYCTL_REVOKE",
+    "KEYCTL_CHOWN",
+    "KEYCTL_SETPERM",
+    "KEYCTL_DESCRIBE",
+    "KEYCTL_CLEAR",
+    "KEYCTL_LINK",
+    "KEYCTL_UNLINK",
+    "KEYCTL_SEARCH",
+    "KEYCTL_READ",
+    "KEYCTL_INSTANTIATE",
+    "KEYCTL_NEGATE",
+    "KEYCTL_SET_REQKEY_KEYRING",
+    "KEYCTL_SET_TIMEOUT",
+    "KEYCTL_ASSUME_AUTHORITY",
+    "KEYCTL_GET_SECURITY",
+    "KEYCTL_SESSION_TO_PARENT",
+    "KEYCTL_REJECT",
+    "KEYCTL_INSTANTIATE_IOV",
+    "KEYCTL_INVALIDATE",
+    "KEYCTL_GET_PERSISTENT",
+    "KEYCTL_DH_COMPUTE",
+    "KEYCTL_PKEY_QUERY",
+    "KEYCTL_PKEY_ENCRYPT",
+    "KEYCTL_PKEY_DECRYPT",
+    "KEYCTL_PKEY_SIGN",
+    "KEYCTL_PKEY_VERIFY",
+    "KEYCTL_RESTRICT_KEYRING",
+    "KEYCTL_MOVE",
+    "KEYCTL_CAPABILITIES",
+    "KEYCTL_WATCH_KEY",
+];
+
+const KEYCTL_GET_KEYRING_ID: u64 = 0;
+const KEYCTL_JOIN_SESSION_KEYRING: u64 = 1;
+const KEYCTL_UPDATE: u64 = 2;
+const KEYCTL_REVOKE: u64 = 3;
+const KEYCTL_CHOWN: u64 = 4;
+const KEYCTL_SETPERM: u64 = 5;
+const KEYCTL_DESCRIBE: u64 = 6;
+const KEYCTL_CLEAR: u64 = 7;
+const KEYCTL_LINK: u64 = 8;
+const KEYCTL_UNLINK: u64 = 9;
+const KEYCTL_SEARCH: u64 = 10;
+const KEYCTL_READ: u64 = 11;
+const KEYCTL_INSTANTIATE: u64 = 12;
+const KEYCTL_NEGATE: u64 = 13;
+const KEYCTL_SET_REQKEY_KEYRING: u64 = 14;
+const KEYCTL_SET_TIMEOUT: u64 = 15;
+const KEYCTL_ASSUME_AUTHORITY: u64 = 16;
+const KEYCTL_GET_SECURITY: u64 = 17;
+const KEYCTL_SESSION_TO_PARENT: u64 = 18;
+const KEYCTL_REJECT: u64 = 19;
+const KEYCTL_INVALIDATE: u64 = 21;
+const KEYCTL_GET_PERSISTENT: u64 = 22;
+const KEYCTL_RESTRICT_KEYRING: u64 = 29;
+const KEYCTL_MOVE: u64 = 30;
+
+// shown payloads are cut after this many bytes
+const SHOWN_PAYLOAD_BYTES: usize = 64;
+
+fn read_text(address: u64, child: Pid) -> String {
+    if address == 0 {
+        return String::new();
+    }
+    SyscallObject::read_bytes_until_null(address as usize, child)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
+
+// a key serial, one of the KEY_SPEC_* ids, or a key /proc/keys can describe
+fn key_name(serial: u64) -> String {
+    let serial = serial as i32;
+    if serial < 0 {
+        return match SPECIAL_KEYRINGS.get((!serial) as usize) {
+            Some(keyring) => keyring.to_string(),
+            None => format!("the unknown special keyring {serial}"),
+        };
+    }
+    if serial == 0 {
+        return "no keyring".to_owned();
+    }
+    let described = std::fs::read_to_string("/proc/keys").ok().and_then(|keys| {
+        keys.lines().find_map(|line| {
+            let mut columns = line.split_whitespace();
+            let id = u32::from_str_radix(columns.next()?, 16).ok()?;
+            if id != serial as u32 {
+                return None;
+            }
+            let kind = columns.nth(6)?;
+            let description = columns.collect::<Vec<&str>>().join(" ");
+            let description = description.split(':').next().unwrap_or_default().to_owned();
+            Some(format!("{kind} \"{description}\""))
+        })
+    });
+    match described {
+        Some(described) => format!("key {serial} ({described})"),
+        None => format!("key {serial}"),
+    }
+}
+
+fn payload(child: Pid, address: u64, length: usize) -> Vec<ColoredString> {
+    if address == 0 || length == 0 {
+        return vec!["an empty payload".white()];
+    }
+    let size = format!("a {length} byte payload");
+    if !SHOW_KEY_PAYLOADS.get() {
+        return vec![size.white(), " (redacted)".bright_black()];
+    }
+    let Some(bytes) = SyscallObject::read_bytes_specific_length(
+        address as usize,
+        child,
+        length.min(SHOWN_PAYLOAD_BYTES),
+    ) else {
+        return vec![size.white()];
+    };
+    let cut = if length > SHOWN_PAYLOAD_BYTES {
+        "..."
+    } else {
+        ""
+    };
+    let shown = if bytes
+        .iter()
+        .all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+    {
+        format!("\"{}{cut}\"", String::from_utf8_lossy(&bytes))
+    } else {
+        let hex = bytes
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        format!("hex {hex}{cut}")
+    };
+    vec![size.white(), ": ".white(), shown.yellow()]
+}
+
+// the possessor, user, group and other bytes of a key permission mask
+fn key_permissions(permissions: u64) -> String {
+    let words = ["view", "read", "write", "search", "link", "setattr"];
+    ["possessor", "user", "group", "other"]
+        .iter()
+        .enumerate()
+        .map(|(index, who)| {
+            let bits = (permissions >> (24 - index * 8)) & 0x3f;
+            let allowed = words
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| (bits & (1 << bit)) != 0)
+                .map(|(_, word)| *word)
+                .collect::<Vec<&str>>();
+            if allowed.is_empty() {
+                format!("{who}: nothing")
+            } else {
+                format!("{who}: {}", allowed.join("+"))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl SyscallObject {
+    fn key(&self, index: usize) -> ColoredString {
+        key_name(self.args[index]).yellow()
+    }
+
+    fn key_type_and_description(&self, kind: usize, description: usize) -> Vec<ColoredString> {
+        vec![
+            "the ".white(),
+            read_text(self.args[kind], self.child).yellow(),
+            " key ".white(),
+            format!("\"{}\"", read_text(self.args[description], self.child)).yellow(),
+        ]
+    }
+
+    pub(crate) fn keyring_intent(&self) -> Vec<ColoredString> {
+        let child = self.child;
+        match self.sysno {
+            syscalls::Sysno::add_key => {
+                let mut intent = vec!["add ".white()];
+                intent.extend(self.key_type_and_description(0, 1));
+                intent.push(" with ".white());
+                intent.extend(payload(child, self.args[2], self.args[3] as usize));
+                intent.push(" to ".white());
+                intent.push(self.key(4));
+                intent
+            }
+            syscalls::Sysno::request_key => {
+                let mut intent = vec!["find ".white()];
+                intent.extend(self.key_type_and_description(0, 1));
+                if self.args[2] != 0 {
+                    intent.push(", asking /sbin/request-key to create it with ".white());
+                    intent.push(format!("\"{}\"", read_text(self.args[2], child)).yellow());
+                    intent.push(" if it is missing".white());
+                }
+                if self.args[3] != 0 {
+                    intent.push(", and link it into ".white());
+                    intent.push(self.key(3));
+                }
+                intent
+            }
+            _ => self.keyctl_intent(),
+        }
+    }
+
+    fn keyctl_intent(&self) -> Vec<ColoredString> {
+        let child = self.child;
+        let operation = self.args[0];
+        // keyctl arguments start after the operation
+        let key = |index: usize| self.key(index + 1);
+        match operation {
+            KEYCTL_GET_KEYRING_ID => {
+                let mut intent = vec!["get the serial of ".white(), key(0)];
+                if self.args[2] != 0 {
+                    intent.push(", creating it if it does not exist".white());
+                }
+                intent
+            }
+            KEYCTL_JOIN_SESSION_KEYRING => {
+                if self.args[1] == 0 {
+                    vec!["join a new anonymous session keyring".white()]
+                } else {
+                    vec![
+                        "join the session keyring ".white(),
+                        format!("\"{}\"", read_text(self.args[1], child)).yellow(),
+                        ", creating it if it does not exist".white(),
+                    ]
+                }
+            }
+            KEYCTL_UPDATE => {
+                let mut intent = vec!["update ".white(), key(0), " with ".white()];
+                intent.extend(payload(child, self.args[2], self.args[3] as usize));
+                intent
+            }
+            KEYCTL_REVOKE => vec!["revoke ".white(), key(0)],
+            KEYCTL_CHOWN => vec![
+                "change the owner of ".white(),
+                key(0),
+                " to uid ".white(),
+                (self.args[2] as i32).to_string().yellow(),
+                " and gid ".white(),
+                (self.args[3] as i32).to_string().yellow(),
+            ],
+            KEYCTL_SETPERM => vec![
+                "set the permissions of ".white(),
+                key(0),
+                " to ".white(),
+                key_permissions(self.args[2]).yellow(),
+            ],
+            KEYCTL_DESCRIBE => vec!["describe ".white(), key(0)],
+            KEYCTL_CLEAR => vec!["unlink every key from ".white(), key(0)],
+            KEYCTL_LINK => vec!["link ".white(), key(0), " into ".white(), key(1)],
+            KEYCTL_UNLINK => vec!["unlink ".white(), key(0), " from ".white(), key(1)],
+            KEYCTL_SEARCH => {
+                let mut intent = vec!["search ".white(), key(0), " for ".white()];
+                let described = self.key_type_and_description(2, 3);
+                intent.extend(described.into_iter().skip(1));
+                if self.args[4] != 0 {
+                    intent.push(", linking it into ".white());
+                    intent.push(key(3));
+                }
+                intent
+            }
+            KEYCTL_READ => vec!["read the payload of ".white(), key(0)],
+            KEYCTL_INSTANTIATE => {
+                let mut intent = vec!["instantiate ".white(), key(0), " with ".white()];
+                intent.extend(payload(child, self.args[2], self.args[3] as usize));
+                if self.args[4] != 0 {
+                    intent.push(", linking it into ".white());
+                    intent.push(key(3));
+                }
+                intent
+            }
+            KEYCTL_NEGATE | KEYCTL_REJECT => {
+                let mut intent = vec![
+                    "mark ".white(),
+                    key(0),
+                    " as missing for ".white(),
+                    self.args[2].to_string().yellow(),
+                    " seconds".white(),
+                ];
+                if operation == KEYCTL_REJECT {
+                    intent.push(", failing requests with ".white());
+                    intent.push(
+                        nix::errno::Errno::from_raw(self.args[3] as i32)
+                            .to_string()
+                            .yellow(),
+                    );
+                }
+                intent
+            }
+            KEYCTL_SET_REQKEY_KEYRING => match self.args[1] as i32 {
+                -1 => vec!["get the keyring requested keys are linked into".white()],
+                default => vec![
+                    "link requested keys into ".white(),
+                    DEFAULT_KEYRINGS
+                        .get(default as usize)
+                        .unwrap_or(&"an unknown keyring")
+                        .yellow(),
+                    " from now on".white(),
+                ],
+            },
+            KEYCTL_SET_TIMEOUT => {
+                if self.args[2] == 0 {
+                    vec!["clear the expiry of ".white(), key(0)]
+                } else {
+                    vec![
+                        "expire ".white(),
+                        key(0),
+                        " in ".white(),
+                        self.args[2].to_string().yellow(),
+                        " seconds".white(),
+                    ]
+                }
+            }
+            KEYCTL_ASSUME_AUTHORITY => {
+                if self.args[1] == 0 {
+                    vec!["give up the authority to instantiate a key".white()]
+                } else {
+                    vec!["assume the authority to instantiate ".white(), key(0)]
+                }
+            }
+            KEYCTL_GET_SECURITY => vec!["get the security label of ".white(), key(0)],
+            KEYCTL_SESSION_TO_PARENT => {
+                vec!["replace the session keyring of the parent process with this one".white()]
+            }
+            KEYCTL_INVALIDATE => vec!["invalidate ".white(), key(0)],
+            KEYCTL_GET_PERSISTENT => vec![
+                "get the persistent keyring of uid ".white(),
+                (self.args[1] as i32).to_string().yellow(),
+                ", linking it into ".white(),
+                key(1),
+            ],
+            KEYCTL_RESTRICT_KEYRING => vec![
+                "restrict the keys that can be linked into ".white(),
+                key(0),
+                " to ".white(),
+                format!(
+                    "{}:{}",
+                    read_text(self.args[2], child),
+                    read_text(self.args[3], child)
+                )
+                .yellow(),
+            ],
+            KEYCTL_MOVE => vec![
+                "move ".white(),
+                key(0),
+                " from ".white(),
+                key(1),
+                " to ".white(),
+                key(2),
+            ],
+            operation => match KEYCTL_OPERATIONS.get(operation as usize) {
+                Some(name) => vec!["perform ".white(), name.yellow(), " on ".white(), key(0)],
+                None => vec![
+                    "perform the unknown keyctl operation ".white(),
+                    operation.to_string().yellow(),
+                ],
+            },
+        }
+    }
+
+    pub(crate) fn keyring_result(&self) -> Vec<ColoredString> {
+        let value = self.result.0.unwrap_or(0);
+        match self.sysno {
+            syscalls::Sysno::add_key | syscalls::Sysno::request_key => {
+                vec!["key serial ".green(), value.to_string().yellow()]
+            }
+            _ => match self.args[0] {
+                KEYCTL_GET_KEYRING_ID
+                | KEYCTL_JOIN_SESSION_KEYRING
+                | KEYCTL_SEARCH
+                | KEYCTL_GET_PERSISTENT => {
+                    vec!["key serial ".green(), value.to_string().yellow()]
+                }
+                KEYCTL_SET_REQKEY_KEYRING => vec![
+                    "previously ".green(),
+                    DEFAULT_KEYRINGS
+                        .get(value as usize)
+                        .unwrap_or(&"an unknown keyring")
+                        .yellow(),
+                ],
+                // both return the full length even when the buffer was too small for it
+                KEYCTL_DESCRIBE | KEYCTL_GET_SECURITY => {
+                    let length = (value as usize).min(self.args[3] as usize);
+                    if self.args[2] == 0 || length == 0 {
+                        return vec![format!("{value} bytes needed").green()];
+                    }
+                    let text = SyscallObject::read_bytes_specific_length(
+                        self.args[2] as usize,
+                        self.child,
+                        length,
+                    )
+                    .map(|bytes| {
+                        let text = bytes.split(|byte| *byte == 0).next().unwrap_or_default();
+                        String::from_utf8_lossy(text).into_owned()
+                    })
+                    .unwrap_or_default();
+                    if self.args[0] == KEYCTL_GET_SECURITY {
+                        return vec!["label ".green(), text.yellow()];
+                    }
+                    // "type;uid;gid;perm;description"
+                    let fields = text.splitn(5, ';').collect::<Vec<&str>>();
+                    match fields.as_slice() {
+                        [kind, uid, gid, permissions, description] => vec![
+                            kind.yellow(),
+                            " key ".green(),
+                            format!("\"{description}\"").yellow(),
+                            " owned by ".green(),
+                            format!("{uid}:{gid}").yellow(),
+                            ", permissions ".green(),
+                            key_permissions(u64::from_str_radix(permissions, 16).unwrap_or(0))
+                                .yellow(),
+                        ],
+                        _ => vec![text.yellow()],
+                    }
+                }
+                KEYCTL_READ => {
+                    if self.args[2] == 0 || self.args[3] < value {
+                        return vec![format!("{value} bytes needed").green()];
+                    }
+                    let mut result = vec!["read ".green()];
+                    result.extend(payload(self.child, self.args[2], value as usize));
+                    result
+                }
+                _ => vec!["successful".green()],
+            },
+        }
+    }
+}
@@ -0,0 +1,406 @@
+// landlock is a stackable LSM, rules are "path beneath" rules:
+// a directory (or a file) is opened and the rights attached to it
+// are granted to everything in the hierarchy beneath it
+//
+// this module goes the opposite direction of the landlock syscalls decoding,
+// it collects what the tracee actually touched, and how it touched it,
+// and produces the smallest ruleset that would still allow that behaviour
+use crate::{
+    syscall_object::SyscallObject,
+    utilities::{resolve_child_path, LANDLOCK_ACCESSES, LANDLOCK_EXISTING_FILES, LANDLOCK_POLICY},
+};
+use nix::libc::{
+    AT_FDCWD, AT_REMOVEDIR, O_ACCMODE, O_CREAT, O_DIRECTORY, O_PATH, O_RDONLY, O_RDWR, O_TRUNC,
+    O_WRONLY,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use syscalls::Sysno;
+
+// include/uapi/linux/landlock.h
+pub const LANDLOCK_ACCESS_FS_EXECUTE: u64 = 1 << 0;
+pub const LANDLOCK_ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+pub const LANDLOCK_ACCESS_FS_READ_FILE: u64 = 1 << 2;
+pub const LANDLOCK_ACCESS_FS_READ_DIR: u64 = 1 << 3;
+pub const LANDLOCK_ACCESS_FS_REMOVE_DIR: u64 = 1 << 4;
+pub const LANDLOCK_ACCESS_FS_REMOVE_FILE: u64 = 1 << 5;
+pub const LANDLOCK_ACCESS_FS_MAKE_CHAR: u64 = 1 << 6;
+pub const LANDLOCK_ACCESS_FS_MAKE_DIR: u64 = 1 << 7;
+pub const LANDLOCK_ACCESS_FS_MAKE_REG: u64 = 1 << 8;
+pub const LANDLOCK_ACCESS_FS_MAKE_SOCK: u64 = 1 << 9;
+pub const LANDLOCK_ACCESS_FS_MAKE_FIFO: u64 = 1 << 10;
+pub const LANDLOCK_ACCESS_FS_MAKE_BLOCK: u64 = 1 << 11;
+pub const LANDLOCK_ACCESS_FS_MAKE_SYM: u64 = 1 << 12;
+// ABI 2
+pub const LANDLOCK_ACCESS_FS_REFER: u64 = 1 << 13;
+// ABI 3
+pub const LANDLOCK_ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+
+// (bit, uapi name, name of the variant in the `landlock` crate's AccessFs)
+pub const LANDLOCK_ACCESS_FS_NAMES: [(u64, &str, &str); 15] = [
+    (
+        LANDLOCK_ACCESS_FS_EXECUTE,
+        "LANDLOCK_ACCESS_FS_EXECUTE",
+        "Execute",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_WRITE_FILE,
+        "LANDLOCK_ACCESS_FS_WRITE_FILE",
+        "WriteFile",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_READ_FILE,
+        "LANDLOCK_ACCESS_FS_READ_FILE",
+        "ReadFile",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_READ_DIR,
+        "LANDLOCK_ACCESS_FS_READ_DIR",
+        "ReadDir",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_REMOVE_DIR,
+        "LANDLOCK_ACCESS_FS_REMOVE_DIR",
+        "RemoveDir",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_REMOVE_FILE,
+        "LANDLOCK_ACCESS_FS_REMOVE_FILE",
+        "RemoveFile",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_MAKE_CHAR,
+        "LANDLOCK_ACCESS_FS_MAKE_CHAR",
+        "MakeChar",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_MAKE_DIR,
+        "LANDLOCK_ACCESS_FS_MAKE_DIR",
+        "MakeDir",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_MAKE_REG,
+        "LANDLOCK_ACCESS_FS_MAKE_REG",
+        "MakeReg",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_MAKE_SOCK,
+        "LANDLOCK_ACCESS_FS_MAKE_SOCK",
+        "MakeSock",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_MAKE_FIFO,
+        "LANDLOCK_ACCESS_FS_MAKE_FIFO",
+        "MakeFifo",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_MAKE_BLOCK,
+        "LANDLOCK_ACCESS_FS_MAKE_BLOCK",
+        "MakeBlock",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_MAKE_SYM,
+        "LANDLOCK_ACCESS_FS_MAKE_SYM",
+        "MakeSym",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_REFER,
+        "LANDLOCK_ACCESS_FS_REFER",
+        "Refer",
+    ),
+    (
+        LANDLOCK_ACCESS_FS_TRUNCATE,
+        "LANDLOCK_ACCESS_FS_TRUNCATE",
+        "Truncate",
+    ),
+];
+
+fn grant(path: PathBuf, rights: u64) {
+    LANDLOCK_ACCESSES.with_borrow_mut(|accesses| {
+        *accesses.entry(path).or_insert(0) |= rights;
+    });
+}
+
+// execute, read, write, and truncate only make sense on the file itself,
+// they're granted to the directory containing it so that the ruleset stays per-directory
+fn grant_file(path: PathBuf, rights: u64) {
+    if path.is_dir() {
+        grant(path, rights);
+    } else {
+        grant_parent(path, rights);
+    }
+}
+
+// creating, removing, and linking are rights of the directory holding the entry
+fn grant_parent(path: PathBuf, rights: u64) {
+    match path.parent() {
+        Some(parent) => grant(parent.to_path_buf(), rights),
+        None => grant(path, rights),
+    }
+}
+
+fn make_right_for(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => LANDLOCK_ACCESS_FS_MAKE_DIR,
+        Ok(metadata) if metadata.file_type().is_symlink() => LANDLOCK_ACCESS_FS_MAKE_SYM,
+        _ => LANDLOCK_ACCESS_FS_MAKE_REG,
+    }
+}
+
+fn remove_right_for(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => LANDLOCK_ACCESS_FS_REMOVE_DIR,
+        _ => LANDLOCK_ACCESS_FS_REMOVE_FILE,
+    }
+}
+
+fn record_open(path: PathBuf, flags: i32, existed: bool) {
+    // O_PATH file descriptors are not checked by landlock
+    if (flags & O_PATH) == O_PATH {
+        return;
+    }
+    let mut rights = 0;
+    if (flags & O_CREAT) == O_CREAT && !existed {
+        grant_parent(path.clone(), LANDLOCK_ACCESS_FS_MAKE_REG);
+    }
+    if (flags & O_TRUNC) == O_TRUNC {
+        rights |= LANDLOCK_ACCESS_FS_TRUNCATE;
+    }
+    if (flags & O_DIRECTORY) == O_DIRECTORY || path.is_dir() {
+        grant(path, LANDLOCK_ACCESS_FS_READ_DIR);
+        return;
+    }
+    match flags & O_ACCMODE {
+        O_RDONLY => rights |= LANDLOCK_ACCESS_FS_READ_FILE,
+        O_WRONLY => rights |= LANDLOCK_ACCESS_FS_WRITE_FILE,
+        O_RDWR => rights |= LANDLOCK_ACCESS_FS_READ_FILE | LANDLOCK_ACCESS_FS_WRITE_FILE,
+        _ => {}
+    }
+    grant_file(path, rights);
+}
+
+fn record_rename(old_path: PathBuf, new_path: PathBuf) {
+    // the entry already moved, its type is found at the new path
+    let made = make_right_for(&new_path);
+    let removed = if made == LANDLOCK_ACCESS_FS_MAKE_DIR {
+        LANDLOCK_ACCESS_FS_REMOVE_DIR
+    } else {
+        LANDLOCK_ACCESS_FS_REMOVE_FILE
+    };
+    // moving across directories requires LANDLOCK_ACCESS_FS_REFER on both sides
+    let refer = if old_path.parent() != new_path.parent() {
+        LANDLOCK_ACCESS_FS_REFER
+    } else {
+        0
+    };
+    grant_parent(old_path, removed | refer);
+    grant_parent(new_path, made | refer);
+}
+
+fn record_link(old_path: PathBuf, new_path: PathBuf) {
+    if old_path.parent() != new_path.parent() {
+        grant_parent(old_path, LANDLOCK_ACCESS_FS_REFER);
+        grant_parent(
+            new_path,
+            LANDLOCK_ACCESS_FS_MAKE_REG | LANDLOCK_ACCESS_FS_REFER,
+        );
+    } else {
+        grant_parent(new_path, LANDLOCK_ACCESS_FS_MAKE_REG);
+    }
+}
+
+// the path and flags of an open, the same at entry and at return
+fn open_request(syscall: &SyscallObject) -> Option<(PathBuf, i32)> {
+    let child = syscall.child;
+    let at =
+        |dirfd: u64, index: usize| resolve_child_path(child, dirfd as i32, &syscall.pavfol(index));
+    match syscall.sysno {
+        Sysno::open => Some((
+            resolve_child_path(child, AT_FDCWD, &syscall.pavfol(0)),
+            syscall.args[1] as i32,
+        )),
+        Sysno::openat => Some((at(syscall.args[0], 1), syscall.args[2] as i32)),
+        // struct open_how { __u64 flags; __u64 mode; __u64 resolve; }
+        Sysno::openat2 => {
+            SyscallObject::read_bytes_as_struct::<8, u64>(syscall.args[2] as usize, child)
+                .map(|flags| (at(syscall.args[0], 1), flags as i32))
+        }
+        Sysno::creat => Some((
+            resolve_child_path(child, AT_FDCWD, &syscall.pavfol(0)),
+            O_CREAT | O_WRONLY | O_TRUNC,
+        )),
+        _ => None,
+    }
+}
+
+// O_CREAT only creates when nothing is there yet, which can only be told before the open runs
+pub fn snapshot_landlock_access(syscall: &SyscallObject) {
+    if syscall.args.is_empty() {
+        return;
+    }
+    let Some((path, flags)) = open_request(syscall) else {
+        return;
+    };
+    if (flags & O_CREAT) == O_CREAT && fs::symlink_metadata(path).is_ok() {
+        LANDLOCK_EXISTING_FILES.with_borrow_mut(|existing| existing.insert(syscall.child));
+    }
+}
+
+pub fn record_landlock_access(syscall: &SyscallObject) {
+    let existed =
+        LANDLOCK_EXISTING_FILES.with_borrow_mut(|existing| existing.remove(&syscall.child));
+    if syscall.errno.is_some() || syscall.args.is_empty() {
+        return;
+    }
+    let child = syscall.child;
+    let at =
+        |dirfd: u64, index: usize| resolve_child_path(child, dirfd as i32, &syscall.pavfol(index));
+    let cwd = |index: usize| resolve_child_path(child, AT_FDCWD, &syscall.pavfol(index));
+    let fd_path = |fd: u64| fs::read_link(format!("/proc/{child}/fd/{fd}")).ok();
+    match syscall.sysno {
+        Sysno::open | Sysno::openat | Sysno::openat2 | Sysno::creat => {
+            if let Some((path, flags)) = open_request(syscall) {
+                record_open(path, flags, existed);
+            }
+        }
+        Sysno::execve => {
+            // the pathname is gone with the old address space, but the kernel remembers it
+            if let Ok(executable) = fs::read_link(format!("/proc/{child}/exe")) {
+                grant_file(executable, LANDLOCK_ACCESS_FS_EXECUTE);
+            }
+        }
+        Sysno::truncate => grant_file(cwd(0), LANDLOCK_ACCESS_FS_TRUNCATE),
+        Sysno::ftruncate => {
+            if let Some(path) = fd_path(syscall.args[0]) {
+                grant_file(path, LANDLOCK_ACCESS_FS_TRUNCATE);
+            }
+        }
+        Sysno::getdents | Sysno::getdents64 => {
+            if let Some(path) = fd_path(syscall.args[0]) {
+                grant(path, LANDLOCK_ACCESS_FS_READ_DIR);
+            }
+        }
+        Sysno::mkdir => grant_parent(cwd(0), LANDLOCK_ACCESS_FS_MAKE_DIR),
+        Sysno::mkdirat => grant_parent(at(syscall.args[0], 1), LANDLOCK_ACCESS_FS_MAKE_DIR),
+        Sysno::rmdir => grant_parent(cwd(0), LANDLOCK_ACCESS_FS_REMOVE_DIR),
+        Sysno::unlink => grant_parent(cwd(0), LANDLOCK_ACCESS_FS_REMOVE_FILE),
+        Sysno::unlinkat => {
+            let path = at(syscall.args[0], 1);
+            if (syscall.args[2] as i32 & AT_REMOVEDIR) == AT_REMOVEDIR {
+                grant_parent(path, LANDLOCK_ACCESS_FS_REMOVE_DIR);
+            } else {
+                grant_parent(path, LANDLOCK_ACCESS_FS_REMOVE_FILE);
+            }
+        }
+        Sysno::symlink => grant_parent(cwd(1), LANDLOCK_ACCESS_FS_MAKE_SYM),
+        Sysno::symlinkat => grant_parent(at(syscall.args[1], 2), LANDLOCK_ACCESS_FS_MAKE_SYM),
+        Sysno::rename => record_rename(cwd(0), cwd(1)),
+        Sysno::renameat | Sysno::renameat2 => {
+            record_rename(at(syscall.args[0], 1), at(syscall.args[2], 3))
+        }
+        Sysno::link => record_link(cwd(0), cwd(1)),
+        Sysno::linkat => record_link(at(syscall.args[0], 1), at(syscall.args[2], 3)),
+        _ => {}
+    }
+}
+
+fn minimum_abi(all_rights: u64) -> u8 {
+    if all_rights & LANDLOCK_ACCESS_FS_TRUNCATE != 0 {
+        3
+    } else if all_rights & LANDLOCK_ACCESS_FS_REFER != 0 {
+        2
+    } else {
+        1
+    }
+}
+
+fn handled_by_abi(abi: u8) -> u64 {
+    match abi {
+        1 => (LANDLOCK_ACCESS_FS_MAKE_SYM << 1) - 1,
+        2 => (LANDLOCK_ACCESS_FS_REFER << 1) - 1,
+        _ => (LANDLOCK_ACCESS_FS_TRUNCATE << 1) - 1,
+    }
+}
+
+fn rights_names(rights: u64, crate_names: bool) -> Vec<&'static str> {
+    LANDLOCK_ACCESS_FS_NAMES
+        .iter()
+        .filter(|(bit, _, _)| rights & bit != 0)
+        .map(|(_, uapi_name, crate_name)| if crate_names { *crate_name } else { *uapi_name })
+        .collect()
+}
+
+fn landlock_rules() -> Vec<(PathBuf, u64)> {
+    LANDLOCK_ACCESSES.with_borrow(|accesses| {
+        accesses
+            .iter()
+            .filter(|(_, rights)| **rights != 0)
+            .map(|(path, rights)| (path.clone(), *rights))
+            .collect()
+    })
+}
+
+pub fn landlock_policy_file() -> String {
+    let rules = landlock_rules();
+    let abi = minimum_abi(rules.iter().fold(0, |all, (_, rights)| all | rights));
+    let mut policy = String::new();
+    policy.push_str("# landlock ruleset generated by intentrace from the observed file accesses\n");
+    policy.push_str(&format!("abi {abi}\n"));
+    policy.push_str(&format!(
+        "handled_access_fs {}\n",
+        rights_names(handled_by_abi(abi), false).join(" | ")
+    ));
+    // paths are quoted and escaped so that spaces and quotes in them do not split the line
+    for (path, rights) in rules {
+        policy.push_str(&format!(
+            "path_beneath {:?} {}\n",
+            path.display().to_string(),
+            rights_names(rights, false).join(" | ")
+        ));
+    }
+    policy
+}
+
+// uses the `landlock` crate
+pub fn landlock_rust_snippet() -> String {
+    let rules = landlock_rules();
+    let abi = minimum_abi(rules.iter().fold(0, |all, (_, rights)| all | rights));
+    let mut snippet = String::new();
+    snippet.push_str("use landlock::{\n");
+    snippet.push_str("    Access, AccessFs, PathBeneath, PathFd, Ruleset, RulesetAttr, RulesetCreatedAttr, ABI,\n");
+    snippet.push_str("};\n\n");
+    snippet.push_str("fn restrict_self() -> Result<(), Box<dyn std::error::Error>> {\n");
+    snippet.push_str(&format!("    let abi = ABI::V{abi};\n"));
+    snippet.push_str("    Ruleset::default()\n");
+    snippet.push_str("        .handle_access(AccessFs::from_all(abi))?\n");
+    snippet.push_str("        .create()?\n");
+    for (path, rights) in rules {
+        let access = rights_names(rights, true)
+            .iter()
+            .map(|name| format!("AccessFs::{name}"))
+            .collect::<Vec<String>>()
+            .join(" | ");
+        snippet.push_str(&format!(
+            "        .add_rule(PathBeneath::new(PathFd::new({:?})?, {access}))?\n",
+            path.display().to_string()
+        ));
+    }
+    snippet.push_str("        .restrict_self()?;\n");
+    snippet.push_str("    Ok(())\n");
+    snippet.push_str("}\n");
+    snippet
+}
+
+pub fn emit_landlock_policy() {
+    LANDLOCK_POLICY.with_borrow(|policy_path| {
+        if let Some(policy_path) = policy_path {
+            match fs::write(policy_path, landlock_policy_file()) {
+                Ok(_) => println!("\n\nlandlock ruleset written to: {policy_path}"),
+                Err(error) => eprintln!("\n\ncould not write the landlock ruleset: {error}"),
+            }
+            println!("\n{}", landlock_rust_snippet());
+        }
+    });
+}
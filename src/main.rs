@@ -38,10 +38,11 @@ use nix::{
     sys::{
         ptrace::{self},
         signal::{kill, Signal},
-        wait::waitpid,
+        wait::{waitpid, WaitStatus},
     },
     unistd::{fork, ForkResult::*, Pid},
 };
+use pete::{Ptracer, Restart, Stop, Tracee};
 use procfs::process::{MMapPath, MemoryMap};
 use std::{
     cell::{Cell, RefCell},
@@ -56,21 +57,53 @@ use std::{
     ptr::null,
     time::Duration,
 };
-use pete::{Ptracer, Restart, Stop, Tracee};
 use syscalls::Sysno;
 use utilities::{
     display_unsupported, errno_check, parse_args, set_memory_break, ATTACH, EXITERS, FAILED_ONLY,
-    FOLLOW_FORKS, OUTPUT, OUTPUT_FOLLOW_FORKS, QUIET, SUMMARY,
+    FOLLOW_FORKS, INTENT, LANDLOCK_POLICY, OUTPUT, OUTPUT_FOLLOW_FORKS, QUIET, SUMMARY,
 };
 
+mod bpf;
+mod buffers;
+mod clone;
+mod credentials;
+mod epoll;
+mod error_explainer;
+mod io_uring;
+mod ioctls;
+mod ipc;
+mod keyring;
+mod landlock_policy;
+mod mounts;
+mod namespaces;
+mod one_line_formatter;
+mod perf;
+mod prctl;
+mod readiness;
+mod seccomp;
+mod signals;
+mod symbols;
 mod syscall_object;
 mod syscalls_map;
+mod threads;
+mod timers;
 mod types;
-use syscall_object::{SyscallObject, SyscallState};
-mod one_line_formatter;
 mod utilities;
+mod watches;
+mod xattr;
 
-
+use credentials::snapshot_credentials;
+use epoll::{emit_epoll_state, track_epoll};
+use error_explainer::flush_probe_run;
+use io_uring::track_io_uring;
+use landlock_policy::{emit_landlock_policy, record_landlock_access, snapshot_landlock_access};
+use mounts::track_mounts;
+use namespaces::track_namespaces;
+use seccomp::track_seccomp;
+use signals::{killed_by_signal, signal_arrived, track_signals};
+use syscall_object::{SyscallObject, SyscallState};
+use threads::{forget_thread, thread_label, track_threads};
+use watches::track_watches;
 
 fn main() {
     let cl = parse_args();
@@ -159,10 +192,29 @@ fn parent(child_or_attach: Option<Pid>) {
     let mut syscall_entering = true;
     let (mut start, mut end) = (None, None);
     let mut syscall = SyscallObject::default();
+    let mut pending_signal = None;
     'main_loop: loop {
-        match ptrace::syscall(child, None) {
+        match ptrace::syscall(child, pending_signal.take()) {
             Ok(_void) => {
-                let _res = waitpid(child, None).expect("Failed waiting for child.");
+                let status = waitpid(child, None).expect("Failed waiting for child.");
+                if let WaitStatus::Signaled(_, signal, _) = status {
+                    killed_by_signal(child, signal as i32);
+                    break 'main_loop;
+                }
+                // a signal-delivery-stop is not a syscall stop, the signal is passed on once reported
+                if let WaitStatus::Stopped(_, signal) = status {
+                    if signal != Signal::SIGTRAP {
+                        signal_arrived(child, signal as i32);
+                        // stop signals stay suppressed, without PTRACE_SEIZE their group-stop looks the same as their delivery
+                        if !matches!(
+                            signal,
+                            Signal::SIGSTOP | Signal::SIGTSTP | Signal::SIGTTIN | Signal::SIGTTOU
+                        ) {
+                            pending_signal = Some(signal);
+                        }
+                        continue 'main_loop;
+                    }
+                }
                 match syscall_entering {
                     true => {
                         // SYSCALL ABOUT TO RUN
@@ -221,9 +273,12 @@ fn parent(child_or_attach: Option<Pid>) {
             }
         }
     }
+    flush_probe_run();
     if SUMMARY.get() {
         print_table();
     }
+    emit_landlock_policy();
+    emit_epoll_state();
 }
 
 fn ptrace_ptracer(mut ptracer: Ptracer, child: Pid) {
@@ -287,6 +342,9 @@ fn ptrace_ptracer(mut ptracer: Ptracer, child: Pid) {
                 }
                 last_pid = syscall_pid;
             }
+            Stop::SignalDelivery { signal } if tracee.pending.is_some() => {
+                signal_arrived(syscall_pid, signal as i32);
+            }
             _ => {
                 let Tracee { pid, stop, .. } = tracee;
             }
@@ -296,23 +354,35 @@ fn ptrace_ptracer(mut ptracer: Ptracer, child: Pid) {
     if SUMMARY.get() {
         print_table();
     }
+    emit_landlock_policy();
+    emit_epoll_state();
 }
 
 fn syscall_will_run(syscall: &mut SyscallObject, registers: &user_regs_struct, child: Pid) {
     // GET PRECALL DATA (some data will be lost if not saved in this time frame)
     syscall.get_precall_data();
+    snapshot_credentials(syscall);
+    if LANDLOCK_POLICY.with_borrow(|policy_path| policy_path.is_some()) {
+        snapshot_landlock_access(syscall);
+    }
 
     // handle program break point
     if syscall.is_mem_alloc_dealloc() {
         set_memory_break(syscall.child);
     }
 
+    // held back lookups belong before the exit line and its banner
+    if syscall.is_exiting() {
+        flush_probe_run();
+    }
     if FOLLOW_FORKS.get() || syscall.is_exiting() {
         syscall.format();
         if syscall.is_exiting() {
             let exited = " EXITED ".on_bright_red();
-            let pid = format!(" {} ", syscall.child).on_black();
+            let (id, name) = thread_label(syscall.child);
+            let pid = format!(" {id} {name} ").on_black();
             print!("\n\n {pid}{exited}\n",);
+            forget_thread(syscall);
         }
     }
 }
@@ -328,7 +398,23 @@ fn syscall_returned(syscall: &mut SyscallObject, registers: &user_regs_struct) {
     // GET POSTCALL DATA (some data will be lost if not saved in this time frame)
     syscall.get_postcall_data();
 
+    track_io_uring(syscall);
+    track_seccomp(syscall);
+    track_mounts(syscall);
+    track_namespaces(syscall);
+    track_watches(syscall);
+    track_threads(syscall);
+    track_epoll(syscall);
+    track_signals(syscall);
+
+    if LANDLOCK_POLICY.with_borrow(|policy_path| policy_path.is_some()) {
+        record_landlock_access(syscall);
+    }
+
     if !FOLLOW_FORKS.get() {
+        if INTENT.get() && syscall.collapse_probe() {
+            return;
+        }
         if FAILED_ONLY.get() && !syscall.parse_return_value_one_line().is_err() {
             return;
         }
@@ -349,6 +435,7 @@ fn handle_getting_registers_error(errno: Errno, syscall_enter_or_exit: &str, sys
                 println!(
                 "\n\n getting registers: syscall-{syscall_enter_or_exit} error: process disappeared\nsyscall: {sysno}, error: {errno}"
             );
+                emit_landlock_policy();
                 exit(0);
             }
             _ => println!("can some error while getting registers"),
@@ -0,0 +1,668 @@
+// decoding of the mount API, both the old mount(2) and the file descriptor based one of fsopen/fsmount/move_mount
+// changes to the mount namespace are recorded so the state a container runtime builds up can be followed
+use crate::{
+    syscall_object::SyscallObject,
+    types::{MountChange, MountHandle},
+    utilities::{resolve_child_path, MOUNT_CHANGES, MOUNT_HANDLES},
+};
+use colored::{ColoredString, Colorize};
+use nix::{
+    libc::{
+        MNT_DETACH, MNT_EXPIRE, MNT_FORCE, MS_BIND, MS_DIRSYNC, MS_LAZYTIME, MS_MANDLOCK, MS_MOVE,
+        MS_NOATIME, MS_NODEV, MS_NODIRATIME, MS_NOEXEC, MS_NOSUID, MS_POSIXACL, MS_PRIVATE,
+        MS_RDONLY, MS_REC, MS_RELATIME, MS_REMOUNT, MS_SHARED, MS_SILENT, MS_SLAVE, MS_STRICTATIME,
+        MS_SYNCHRONOUS, MS_UNBINDABLE, UMOUNT_NOFOLLOW,
+    },
+    unistd::Pid,
+};
+use std::path::PathBuf;
+use syscalls::Sysno;
+
+const MS_NOSYMFOLLOW: u64 = 256;
+
+const MOUNT_FLAGS: [(u64, &str); 15] = [
+    (MS_RDONLY, "read-only"),
+    (MS_NOSUID, "nosuid"),
+    (MS_NODEV, "nodev"),
+    (MS_NOEXEC, "noexec"),
+    (MS_SYNCHRONOUS, "synchronous"),
+    (MS_MANDLOCK, "mandatory locking"),
+    (MS_DIRSYNC, "dirsync"),
+    (MS_NOSYMFOLLOW, "nosymfollow"),
+    (MS_NOATIME, "noatime"),
+    (MS_NODIRATIME, "nodiratime"),
+    (MS_SILENT, "silent"),
+    (MS_POSIXACL, "posixacl"),
+    (MS_RELATIME, "relatime"),
+    (MS_STRICTATIME, "strictatime"),
+    (MS_LAZYTIME, "lazytime"),
+];
+
+const PROPAGATION_TYPES: [(u64, &str); 4] = [
+    (MS_SHARED, "shared"),
+    (MS_PRIVATE, "private"),
+    (MS_SLAVE, "a slave of its peer group"),
+    (MS_UNBINDABLE, "unbindable"),
+];
+
+const UNMOUNT_FLAGS: [(i32, &str); 4] = [
+    (MNT_FORCE, "forcibly"),
+    (
+        MNT_DETACH,
+        "lazily, detaching it now and cleaning up once it is unused",
+    ),
+    (MNT_EXPIRE, "only if it was already marked as expired"),
+    (UMOUNT_NOFOLLOW, "without following a trailing symlink"),
+];
+
+// the MOUNT_ATTR_* flags of fsmount and mount_setattr, the atime ones are a value in a 3 bit field
+const MOUNT_ATTRIBUTES: [(u64, &str); 6] = [
+    (0x1, "read-only"),
+    (0x2, "nosuid"),
+    (0x4, "nodev"),
+    (0x8, "noexec"),
+    (0x80, "nodiratime"),
+    (0x20_0000, "nosymfollow"),
+];
+const MOUNT_ATTR__ATIME: u64 = 0x70;
+const MOUNT_ATTR_NOATIME: u64 = 0x10;
+const MOUNT_ATTR_STRICTATIME: u64 = 0x20;
+const MOUNT_ATTR_IDMAP: u64 = 0x10_0000;
+
+const OPEN_TREE_CLONE: u64 = 1;
+const AT_RECURSIVE: u64 = 0x8000;
+const MOVE_MOUNT_F_EMPTY_PATH: u64 = 0x4;
+
+const FSCONFIG_SET_FLAG: u64 = 0;
+const FSCONFIG_SET_STRING: u64 = 1;
+const FSCONFIG_SET_BINARY: u64 = 2;
+const FSCONFIG_SET_PATH: u64 = 3;
+const FSCONFIG_SET_PATH_EMPTY: u64 = 4;
+const FSCONFIG_SET_FD: u64 = 5;
+const FSCONFIG_CMD_CREATE: u64 = 6;
+const FSCONFIG_CMD_RECONFIGURE: u64 = 7;
+const FSCONFIG_CMD_CREATE_EXCL: u64 = 8;
+
+// sources that only name the pseudo filesystem, and are not worth showing
+const PLACEHOLDER_SOURCES: [&str; 3] = ["", "none", "nodev"];
+
+// the running view only shows the latest changes
+const SHOWN_MOUNT_CHANGES: usize = 10;
+
+const CAP_SYS_CHROOT: u64 = 18;
+const CAP_SYS_ADMIN: u64 = 21;
+
+// the inode number the kernel gives the initial user namespace
+const PROC_USER_INIT_INO: u64 = 0xEFFF_FFFD;
+
+fn read_text(address: u64, child: Pid) -> String {
+    if address == 0 {
+        return String::new();
+    }
+    SyscallObject::read_bytes_until_null(address as usize, child)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
+
+fn flag_words(flags: u64, table: &[(u64, &'static str)]) -> Vec<&'static str> {
+    table
+        .iter()
+        .filter(|(flag, _)| (flags & flag) == *flag)
+        .map(|(_, word)| *word)
+        .collect()
+}
+
+fn attribute_words(attributes: u64) -> Vec<&'static str> {
+    let mut words = flag_words(attributes, &MOUNT_ATTRIBUTES);
+    match attributes & MOUNT_ATTR__ATIME {
+        MOUNT_ATTR_NOATIME => words.push("noatime"),
+        MOUNT_ATTR_STRICTATIME => words.push("strictatime"),
+        _ => {}
+    }
+    if (attributes & MOUNT_ATTR_IDMAP) != 0 {
+        words.push("id-mapped");
+    }
+    words
+}
+
+// filesystem options are a comma separated list, except for the few filesystems taking binary data
+fn filesystem_options(data: &str) -> Vec<String> {
+    data.split(',')
+        .filter(|option| !option.is_empty())
+        .map(|option| option.to_owned())
+        .collect()
+}
+
+fn describe_handle(handle: &MountHandle) -> String {
+    if handle.options.is_empty() {
+        handle.description.clone()
+    } else {
+        format!("{} ({})", handle.description, handle.options.join(", "))
+    }
+}
+
+fn find_handle(child: Pid, fd: i32) -> Option<MountHandle> {
+    MOUNT_HANDLES.with_borrow(|handles| handles.get(&(child, fd)).cloned())
+}
+
+fn record_change(target: PathBuf, description: String) {
+    MOUNT_CHANGES.with_borrow_mut(|changes| {
+        changes.push(MountChange {
+            target,
+            description,
+        })
+    });
+}
+
+// adds to the change already recorded for the mount, so a later remount does not show up as a second mount
+fn amend_change(target: PathBuf, description: String) {
+    MOUNT_CHANGES.with_borrow_mut(|changes| {
+        match changes
+            .iter_mut()
+            .rev()
+            .find(|change| change.target == target)
+        {
+            Some(change) => {
+                change.description.push_str(", ");
+                change.description.push_str(&description);
+            }
+            None => changes.push(MountChange {
+                target,
+                description,
+            }),
+        }
+    });
+}
+
+pub fn track_mounts(syscall: &SyscallObject) {
+    if syscall.errno.is_some() {
+        return;
+    }
+    let child = syscall.child;
+    let fd = syscall.result.0.unwrap_or(0) as i32;
+    match syscall.sysno {
+        Sysno::mount => {
+            let target = syscall.mount_path(nix::libc::AT_FDCWD, syscall.args[1]);
+            let flags = syscall.args[3];
+            let description = syscall.mount_description();
+            if (flags & (MS_REMOUNT | MS_SHARED | MS_PRIVATE | MS_SLAVE | MS_UNBINDABLE)) != 0 {
+                amend_change(target, description);
+            } else {
+                record_change(target, description);
+            }
+        }
+        Sysno::umount2 => {
+            let target = syscall.mount_path(nix::libc::AT_FDCWD, syscall.args[0]);
+            let removed = MOUNT_CHANGES.with_borrow_mut(|changes| {
+                let before = changes.len();
+                changes.retain(|change| change.target != target);
+                before != changes.len()
+            });
+            // a mount the tracee did not make itself, like one inherited from the parent namespace
+            if !removed {
+                record_change(target, "unmounted".to_owned());
+            }
+        }
+        Sysno::pivot_root => {
+            let new_root = syscall.mount_path(nix::libc::AT_FDCWD, syscall.args[0]);
+            let put_old = syscall.mount_path(nix::libc::AT_FDCWD, syscall.args[1]);
+            record_change(
+                PathBuf::from("/"),
+                format!(
+                    "now the mount that was at {}, the old root is at {}",
+                    new_root.display(),
+                    put_old.display()
+                ),
+            );
+        }
+        Sysno::move_mount => {
+            let target = syscall.mount_path(syscall.args[2] as i32, syscall.args[3]);
+            record_change(target, syscall.moved_mount());
+        }
+        Sysno::fsopen => {
+            let handle = MountHandle {
+                description: format!("new {} filesystem", read_text(syscall.args[0], child)),
+                options: vec![],
+            };
+            MOUNT_HANDLES.with_borrow_mut(|handles| handles.insert((child, fd), handle));
+        }
+        Sysno::fspick => {
+            let path = syscall.mount_path(syscall.args[0] as i32, syscall.args[1]);
+            let handle = MountHandle {
+                description: format!("filesystem mounted at {}", path.display()),
+                options: vec![],
+            };
+            MOUNT_HANDLES.with_borrow_mut(|handles| handles.insert((child, fd), handle));
+        }
+        Sysno::open_tree => {
+            let path = syscall.mount_path(syscall.args[0] as i32, syscall.args[1]);
+            let description = if (syscall.args[2] & OPEN_TREE_CLONE) == 0 {
+                format!("mount at {}", path.display())
+            } else if (syscall.args[2] & AT_RECURSIVE) != 0 {
+                format!("recursive bind of {}", path.display())
+            } else {
+                format!("bind of {}", path.display())
+            };
+            let handle = MountHandle {
+                description,
+                options: vec![],
+            };
+            MOUNT_HANDLES.with_borrow_mut(|handles| handles.insert((child, fd), handle));
+        }
+        Sysno::fsconfig => {
+            if let Some(option) = syscall.fsconfig_option() {
+                MOUNT_HANDLES.with_borrow_mut(|handles| {
+                    if let Some(handle) = handles.get_mut(&(child, syscall.args[0] as i32)) {
+                        handle.options.push(option);
+                    }
+                });
+            }
+        }
+        Sysno::fsmount => {
+            let mut handle = find_handle(child, syscall.args[0] as i32).unwrap_or_default();
+            handle.options.extend(
+                attribute_words(syscall.args[2])
+                    .into_iter()
+                    .map(|word| word.to_owned()),
+            );
+            MOUNT_HANDLES.with_borrow_mut(|handles| handles.insert((child, fd), handle));
+        }
+        Sysno::close => {
+            MOUNT_HANDLES
+                .with_borrow_mut(|handles| handles.remove(&(child, syscall.args[0] as i32)));
+        }
+        _ => {}
+    }
+}
+
+impl SyscallObject {
+    fn mount_path(&self, dirfd: i32, address: u64) -> PathBuf {
+        resolve_child_path(self.child, dirfd, &read_text(address, self.child))
+    }
+
+    // what mount(2) did to its target, in the form shown by the running view
+    fn mount_description(&self) -> String {
+        let source = read_text(self.args[0], self.child);
+        let filesystem = read_text(self.args[2], self.child);
+        let flags = self.args[3];
+        let mut words = flag_words(flags, &MOUNT_FLAGS)
+            .into_iter()
+            .map(|word| word.to_owned())
+            .collect::<Vec<String>>();
+        let recursively = if (flags & MS_REC) != 0 {
+            ", recursively"
+        } else {
+            ""
+        };
+        let propagation = flag_words(flags, &PROPAGATION_TYPES);
+        if !propagation.is_empty() {
+            let changed = format!("made {}{recursively}", propagation.join(" and "));
+            return if words.is_empty() {
+                changed
+            } else {
+                format!("{changed} ({})", words.join(", "))
+            };
+        }
+        if (flags & MS_MOVE) != 0 {
+            return format!("moved from {source}");
+        }
+        if (flags & MS_REMOUNT) == 0 {
+            words.extend(filesystem_options(&read_text(self.args[4], self.child)));
+        }
+        let description = if (flags & MS_REMOUNT) != 0 {
+            "remounted".to_owned()
+        } else if (flags & MS_BIND) != 0 {
+            let recursive = if (flags & MS_REC) != 0 {
+                "recursive "
+            } else {
+                ""
+            };
+            format!("{recursive}bind of {source}")
+        } else if PLACEHOLDER_SOURCES.contains(&source.as_str()) || source == filesystem {
+            filesystem
+        } else {
+            format!("{filesystem} from {source}")
+        };
+        if words.is_empty() {
+            description
+        } else {
+            format!("{description} ({})", words.join(", "))
+        }
+    }
+
+    fn moved_mount(&self) -> String {
+        if (self.args[4] & MOVE_MOUNT_F_EMPTY_PATH) != 0 {
+            if let Some(handle) = find_handle(self.child, self.args[0] as i32) {
+                return describe_handle(&handle);
+            }
+        }
+        let from = self.mount_path(self.args[0] as i32, self.args[1]);
+        format!("moved from {}", from.display())
+    }
+
+    // the option an fsconfig call adds to its filesystem context
+    fn fsconfig_option(&self) -> Option<String> {
+        let key = read_text(self.args[2], self.child);
+        match self.args[1] {
+            FSCONFIG_SET_FLAG => Some(key),
+            FSCONFIG_SET_STRING => Some(format!("{key}={}", read_text(self.args[3], self.child))),
+            FSCONFIG_SET_BINARY => Some(format!("{key}=<{} Bytes>", self.args[4])),
+            FSCONFIG_SET_PATH | FSCONFIG_SET_PATH_EMPTY => Some(format!(
+                "{key}={}",
+                self.mount_path(self.args[4] as i32, self.args[3]).display()
+            )),
+            FSCONFIG_SET_FD => Some(format!("{key}=fd {}", self.args[4])),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn mount_intent(&self) -> Vec<ColoredString> {
+        let child = self.child;
+        match self.sysno {
+            Sysno::mount => {
+                let source = read_text(self.args[0], child);
+                let target = self.mount_path(nix::libc::AT_FDCWD, self.args[1]);
+                let target = target.display().to_string();
+                let filesystem = read_text(self.args[2], child);
+                let flags = self.args[3];
+                let recursive = (flags & MS_REC) != 0;
+                let mut words = flag_words(flags, &MOUNT_FLAGS)
+                    .into_iter()
+                    .map(|word| word.to_owned())
+                    .collect::<Vec<String>>();
+                // the kernel takes a single propagation type, every one set is shown all the same
+                let propagation = flag_words(flags, &PROPAGATION_TYPES);
+                if !propagation.is_empty() {
+                    let mut intent = vec![
+                        "make the mount at ".white(),
+                        target.yellow(),
+                        " ".white(),
+                        propagation.join(" and ").yellow(),
+                    ];
+                    if recursive {
+                        intent.push(", together with the mounts below it".white());
+                    }
+                    if !words.is_empty() {
+                        intent.push(" (".white());
+                        intent.push(words.join(", ").yellow());
+                        intent.push(")".white());
+                    }
+                    return intent;
+                }
+                if (flags & MS_MOVE) != 0 {
+                    return vec![
+                        "move the mount at ".white(),
+                        source.yellow(),
+                        " to ".white(),
+                        target.yellow(),
+                    ];
+                }
+                let mut intent = if (flags & MS_REMOUNT) != 0 && (flags & MS_BIND) != 0 {
+                    vec![
+                        "change the flags of the bind mount at ".white(),
+                        target.yellow(),
+                    ]
+                } else if (flags & MS_REMOUNT) != 0 {
+                    vec!["remount ".white(), target.yellow()]
+                } else if (flags & MS_BIND) != 0 {
+                    if recursive {
+                        words.push("recursively".to_owned());
+                    }
+                    vec![
+                        "bind-mount ".white(),
+                        source.yellow(),
+                        " onto ".white(),
+                        target.yellow(),
+                    ]
+                } else {
+                    let mut intent = vec![
+                        "mount the ".white(),
+                        filesystem.yellow(),
+                        " filesystem".white(),
+                    ];
+                    if !PLACEHOLDER_SOURCES.contains(&source.as_str()) && source != filesystem {
+                        intent.push(" from ".white());
+                        intent.push(source.yellow());
+                    }
+                    intent.push(" onto ".white());
+                    intent.push(target.yellow());
+                    intent
+                };
+                if !words.is_empty() {
+                    intent.push(" ".white());
+                    intent.push(words.join(", ").yellow());
+                }
+                let options = filesystem_options(&read_text(self.args[4], child));
+                if !options.is_empty() && (flags & MS_BIND) == 0 {
+                    intent.push(" with the options: ".white());
+                    intent.push(options.join(", ").yellow());
+                }
+                intent
+            }
+            Sysno::umount2 => {
+                let target = self.mount_path(nix::libc::AT_FDCWD, self.args[0]);
+                let mut intent = vec![
+                    "unmount the filesystem at ".white(),
+                    target.display().to_string().yellow(),
+                ];
+                let words = UNMOUNT_FLAGS
+                    .iter()
+                    .filter(|(flag, _)| (self.args[1] as i32 & flag) == *flag)
+                    .map(|(_, word)| *word)
+                    .collect::<Vec<&str>>();
+                if !words.is_empty() {
+                    intent.push(" ".white());
+                    intent.push(words.join(", ").yellow());
+                }
+                intent
+            }
+            Sysno::pivot_root => vec![
+                "make ".white(),
+                self.mount_path(nix::libc::AT_FDCWD, self.args[0])
+                    .display()
+                    .to_string()
+                    .yellow(),
+                " the root mount and move the old root mount to ".white(),
+                self.mount_path(nix::libc::AT_FDCWD, self.args[1])
+                    .display()
+                    .to_string()
+                    .yellow(),
+            ],
+            Sysno::chroot => vec![
+                "change the root directory of the process to ".white(),
+                self.mount_path(nix::libc::AT_FDCWD, self.args[0])
+                    .display()
+                    .to_string()
+                    .yellow(),
+            ],
+            Sysno::open_tree => {
+                let path = self.mount_path(self.args[0] as i32, self.args[1]);
+                let path = path.display().to_string();
+                if (self.args[2] & OPEN_TREE_CLONE) == 0 {
+                    return vec!["open the mount at ".white(), path.yellow()];
+                }
+                let mut intent = vec!["clone the mount at ".white(), path.yellow()];
+                if (self.args[2] & AT_RECURSIVE) != 0 {
+                    intent.push(" and the mounts below it".white());
+                }
+                intent.push(" into a detached mount".white());
+                intent
+            }
+            Sysno::move_mount => {
+                let target = self.mount_path(self.args[2] as i32, self.args[3]);
+                let target = target.display().to_string();
+                if (self.args[4] & MOVE_MOUNT_F_EMPTY_PATH) != 0 {
+                    if let Some(handle) = find_handle(child, self.args[0] as i32) {
+                        return vec![
+                            "attach the detached mount of the ".white(),
+                            describe_handle(&handle).yellow(),
+                            " onto ".white(),
+                            target.yellow(),
+                        ];
+                    }
+                }
+                vec![
+                    "move the mount at ".white(),
+                    self.mount_path(self.args[0] as i32, self.args[1])
+                        .display()
+                        .to_string()
+                        .yellow(),
+                    " onto ".white(),
+                    target.yellow(),
+                ]
+            }
+            Sysno::fsopen => vec![
+                "create a filesystem context for a new ".white(),
+                read_text(self.args[0], child).yellow(),
+                " filesystem".white(),
+            ],
+            Sysno::fspick => vec![
+                "open the filesystem mounted at ".white(),
+                self.mount_path(self.args[0] as i32, self.args[1])
+                    .display()
+                    .to_string()
+                    .yellow(),
+                " for reconfiguration".white(),
+            ],
+            Sysno::fsconfig => {
+                // the option being set is already recorded by the time the line is printed
+                let context = find_handle(child, self.args[0] as i32)
+                    .map(|handle| handle.description)
+                    .unwrap_or_else(|| format!("filesystem context {}", self.args[0]));
+                match self.args[1] {
+                    FSCONFIG_CMD_CREATE | FSCONFIG_CMD_CREATE_EXCL => {
+                        let context = find_handle(child, self.args[0] as i32)
+                            .map(|handle| describe_handle(&handle))
+                            .unwrap_or(context);
+                        let mut intent = vec!["create the ".white(), context.yellow()];
+                        if self.args[1] == FSCONFIG_CMD_CREATE_EXCL {
+                            intent.push(" (without reusing an existing superblock)".white());
+                        }
+                        intent
+                    }
+                    FSCONFIG_CMD_RECONFIGURE => vec![
+                        "apply the new configuration to the ".white(),
+                        context.yellow(),
+                    ],
+                    _ => match self.fsconfig_option() {
+                        Some(option) => vec![
+                            "set the option ".white(),
+                            option.yellow(),
+                            " on the ".white(),
+                            context.yellow(),
+                        ],
+                        None => vec![
+                            "perform the fsconfig command ".white(),
+                            format!("#{}", self.args[1]).yellow(),
+                            " on the ".white(),
+                            context.yellow(),
+                        ],
+                    },
+                }
+            }
+            Sysno::fsmount => {
+                let context = find_handle(child, self.args[0] as i32)
+                    .map(|handle| describe_handle(&handle))
+                    .unwrap_or_else(|| format!("filesystem context {}", self.args[0]));
+                let mut intent = vec!["create a detached mount of the ".white(), context.yellow()];
+                let words = attribute_words(self.args[2]);
+                if !words.is_empty() {
+                    intent.push(" ".white());
+                    intent.push(words.join(", ").yellow());
+                }
+                intent
+            }
+            _ => vec![],
+        }
+    }
+
+    pub(crate) fn mount_result(&self) -> Vec<ColoredString> {
+        let fd = self.result.0.unwrap_or(0).to_string();
+        let mut result = match self.sysno {
+            Sysno::mount => vec!["done".green()],
+            Sysno::umount2 => vec!["unmounted".green()],
+            Sysno::pivot_root => vec!["root mount switched".green()],
+            Sysno::chroot => vec!["root directory changed".green()],
+            Sysno::open_tree | Sysno::fsmount => {
+                vec!["mount file descriptor: ".green(), fd.yellow()]
+            }
+            Sysno::fsopen | Sysno::fspick => {
+                vec!["filesystem context: ".green(), fd.yellow()]
+            }
+            Sysno::move_mount => vec!["mounted".green()],
+            _ => vec!["successful".green()],
+        };
+        if matches!(
+            self.sysno,
+            Sysno::mount | Sysno::umount2 | Sysno::pivot_root | Sysno::move_mount
+        ) {
+            result.extend(mount_namespace_view());
+        }
+        result
+    }
+
+    // mount operations need CAP_SYS_ADMIN in the user namespace that owns the mount namespace
+    pub(crate) fn mount_denial(&self) -> Option<Vec<ColoredString>> {
+        if self.errno != Some(nix::errno::Errno::EPERM) {
+            return None;
+        }
+        let capability = match self.sysno {
+            Sysno::chroot => CAP_SYS_CHROOT,
+            Sysno::mount
+            | Sysno::umount2
+            | Sysno::pivot_root
+            | Sysno::open_tree
+            | Sysno::move_mount
+            | Sysno::fsopen
+            | Sysno::fsmount
+            | Sysno::fspick => CAP_SYS_ADMIN,
+            _ => return None,
+        };
+        let name = if capability == CAP_SYS_CHROOT {
+            "CAP_SYS_CHROOT"
+        } else {
+            "CAP_SYS_ADMIN"
+        };
+        let status = procfs::process::Process::new(self.child.as_raw())
+            .and_then(|process| process.status())
+            .ok()?;
+        if (status.capeff & (1 << capability)) == 0 {
+            return Some(vec![
+                " (the process lacks ".white(),
+                name.yellow(),
+                ", it runs as uid ".white(),
+                status.euid.to_string().yellow(),
+                ")".white(),
+            ]);
+        }
+        let user_namespace = std::fs::read_link(format!("/proc/{}/ns/user", self.child)).ok()?;
+        let initial_namespace = format!("user:[{PROC_USER_INIT_INO}]");
+        if capability == CAP_SYS_ADMIN && user_namespace.as_os_str() != initial_namespace.as_str() {
+            let mount_namespace =
+                std::fs::read_link(format!("/proc/{}/ns/mnt", self.child)).ok()?;
+            return Some(vec![
+                " (the process has ".white(),
+                name.yellow(),
+                " only in its own user namespace, either the mount namespace ".white(),
+                mount_namespace.display().to_string().yellow(),
+                " belongs to a more privileged user namespace or the mount is locked because it was inherited from one)".white(),
+            ]);
+        }
+        None
+    }
+}
+
+fn mount_namespace_view() -> Vec<ColoredString> {
+    MOUNT_CHANGES.with_borrow(|changes| {
+        let mut view = vec!["\n\tmount namespace changes so far:".dimmed()];
+        let skipped = changes.len().saturating_sub(SHOWN_MOUNT_CHANGES);
+        if skipped > 0 {
+            view.push(format!("\n\t  ... {skipped} earlier changes").dimmed());
+        }
+        for change in changes.iter().skip(skipped) {
+            view.push(format!("\n\t  {}: ", change.target.display()).white());
+            view.push(change.description.as_str().yellow());
+        }
+        view
+    })
+}
@@ -0,0 +1,307 @@
+// decoding of unshare, setns and the CLONE_NEW* flags
+// the namespaces and cgroup of every tracee are recorded so moves between them can be marked in the output
+use crate::{
+    syscall_object::SyscallObject,
+    threads::thread_group,
+    types::ProcessNamespaces,
+    utilities::{CGROUP_FILES, NAMESPACES, NAMESPACE_TRANSITIONS},
+};
+use colored::{ColoredString, Colorize};
+use nix::unistd::Pid;
+use syscalls::Sysno;
+
+// the CLONE_NEW* flags, in the order a container runtime usually creates them
+const NAMESPACE_FLAGS: [(u64, &str); 8] = [
+    (0x1000_0000, "user"),
+    (0x0002_0000, "mount"),
+    (0x2000_0000, "pid"),
+    (0x4000_0000, "network"),
+    (0x0800_0000, "ipc"),
+    (0x0400_0000, "uts"),
+    (0x0200_0000, "cgroup"),
+    (0x0000_0080, "time"),
+];
+const CLONE_NEWPID: u64 = 0x2000_0000;
+const CLONE_NEWTIME: u64 = 0x80;
+
+// the other parts of the process context unshare can stop sharing
+const UNSHARED_CONTEXT: [(u64, &str); 3] = [
+    (0x0000_0400, "the file descriptor table"),
+    (0x0000_0200, "filesystem information"),
+    (0x0004_0000, "sem-adj values"),
+];
+
+// the entries of /proc/<pid>/ns, and what they are called in the output
+const PROC_NAMESPACES: [(&str, &str); 10] = [
+    ("user", "user"),
+    ("mnt", "mount"),
+    ("pid", "pid"),
+    ("pid_for_children", "pid (for children)"),
+    ("net", "network"),
+    ("ipc", "ipc"),
+    ("uts", "uts"),
+    ("cgroup", "cgroup"),
+    ("time", "time"),
+    ("time_for_children", "time (for children)"),
+];
+
+// "user+mount+pid" for the CLONE_NEW* flags set in the flags
+pub fn namespace_names(flags: u64) -> Option<String> {
+    let names = NAMESPACE_FLAGS
+        .iter()
+        .filter(|(flag, _)| (flags & flag) != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<&str>>();
+    if names.is_empty() {
+        None
+    } else {
+        Some(names.join("+"))
+    }
+}
+
+fn read_namespaces(pid: Pid) -> Option<ProcessNamespaces> {
+    let mut namespaces = vec![];
+    for (entry, name) in PROC_NAMESPACES {
+        // time namespaces are missing on older kernels
+        if let Ok(link) = std::fs::read_link(format!("/proc/{pid}/ns/{entry}")) {
+            namespaces.push((name, link.to_string_lossy().into_owned()));
+        }
+    }
+    if namespaces.is_empty() {
+        return None;
+    }
+    let cgroup = std::fs::read_to_string(format!("/proc/{pid}/cgroup")).unwrap_or_default();
+    // cgroup v2 has a single "0::<path>" line, v1 has one line per hierarchy
+    let cgroup = cgroup
+        .lines()
+        .map(|line| line.splitn(3, ':').nth(2).unwrap_or(line))
+        .collect::<Vec<&str>>()
+        .join(";");
+    Some(ProcessNamespaces { namespaces, cgroup })
+}
+
+// the first time a tracee is seen is the baseline later moves are compared against,
+// a move after one of the syscalls that can cause them is kept for the formatter to mark
+pub fn track_namespaces(syscall: &SyscallObject) {
+    NAMESPACE_TRANSITIONS.with_borrow_mut(|transitions| transitions.remove(&syscall.child));
+    let known = NAMESPACES.with_borrow(|namespaces| namespaces.contains_key(&syscall.child));
+    if !known {
+        if let Some(current) = read_namespaces(syscall.child) {
+            NAMESPACES.with_borrow_mut(|namespaces| namespaces.insert(syscall.child, current));
+        }
+    }
+    if syscall.errno.is_some() {
+        return;
+    }
+    track_cgroup_files(syscall);
+    let Some((moved, before)) = syscall.namespace_move() else {
+        return;
+    };
+    let Some(after) = read_namespaces(moved) else {
+        return;
+    };
+    let before =
+        before.or_else(|| NAMESPACES.with_borrow(|namespaces| namespaces.get(&moved).cloned()));
+    NAMESPACES.with_borrow_mut(|namespaces| namespaces.insert(moved, after.clone()));
+    if let Some(before) = before {
+        if before != after {
+            NAMESPACE_TRANSITIONS.with_borrow_mut(|transitions| {
+                transitions.insert(syscall.child, (moved, before, after))
+            });
+        }
+    }
+}
+
+// cgroup.procs and cgroup.threads are remembered when opened, so other writes are passed over without touching /proc
+fn track_cgroup_files(syscall: &SyscallObject) {
+    let process = thread_group(syscall.child);
+    match syscall.sysno {
+        Sysno::open | Sysno::openat | Sysno::openat2 | Sysno::creat => {
+            let path_index = if let Sysno::open | Sysno::creat = syscall.sysno {
+                0
+            } else {
+                1
+            };
+            let Some(fd) = syscall.result.0 else {
+                return;
+            };
+            let path = syscall.pavfol(path_index);
+            if path.ends_with("cgroup.procs") || path.ends_with("cgroup.threads") {
+                CGROUP_FILES.with_borrow_mut(|files| files.insert((process, fd as i32)));
+            }
+        }
+        Sysno::close => {
+            CGROUP_FILES.with_borrow_mut(|files| files.remove(&(process, syscall.args[0] as i32)));
+        }
+        _ => {}
+    }
+}
+
+fn describe_transition(
+    before: &ProcessNamespaces,
+    after: &ProcessNamespaces,
+) -> Vec<ColoredString> {
+    let mut transition = vec![];
+    for (name, link) in &after.namespaces {
+        let previous = before
+            .namespaces
+            .iter()
+            .find(|(previous_name, _)| previous_name == name)
+            .map(|(_, previous_link)| previous_link.as_str());
+        if previous == Some(link.as_str()) {
+            continue;
+        }
+        transition.push(format!("\n\t=> new {name} namespace: ").bright_cyan());
+        transition.push(previous.unwrap_or("?").yellow());
+        transition.push(" -> ".white());
+        transition.push(link.as_str().yellow());
+    }
+    if !after.cgroup.is_empty() && before.cgroup != after.cgroup {
+        transition.push("\n\t=> new cgroup: ".bright_cyan());
+        transition.push(before.cgroup.as_str().yellow());
+        transition.push(" -> ".white());
+        transition.push(after.cgroup.as_str().yellow());
+    }
+    transition
+}
+
+impl SyscallObject {
+    // the namespace a setns file descriptor refers to, or the process a pidfd refers to
+    fn namespace_fd(&self, fd: u64) -> (String, Option<String>) {
+        let link = std::fs::read_link(format!("/proc/{}/fd/{fd}", self.child))
+            .map(|link| link.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if link.contains("pidfd") {
+            let pid = std::fs::read_to_string(format!("/proc/{}/fdinfo/{fd}", self.child))
+                .ok()
+                .and_then(|fdinfo| {
+                    fdinfo
+                        .lines()
+                        .find_map(|line| line.strip_prefix("Pid:"))
+                        .map(|pid| pid.trim().to_owned())
+                });
+            (link, pid)
+        } else {
+            (link, None)
+        }
+    }
+
+    pub(crate) fn unshare_intent(&self) -> Vec<ColoredString> {
+        let flags = self.args[0];
+        let mut intent = vec![];
+        // new pid and time namespaces are only entered by the children created afterwards
+        let entered = namespace_names(flags & !(CLONE_NEWPID | CLONE_NEWTIME));
+        let for_children = namespace_names(flags & (CLONE_NEWPID | CLONE_NEWTIME));
+        if let Some(names) = &entered {
+            intent.push("enter a new ".white());
+            intent.push(names.as_str().yellow());
+            intent.push(" namespace".white());
+        }
+        if let Some(names) = &for_children {
+            if entered.is_some() {
+                intent.push(", and ".white());
+            }
+            intent.push("start the children created from now on in a new ".white());
+            intent.push(names.as_str().yellow());
+            intent.push(" namespace".white());
+        }
+        let context = UNSHARED_CONTEXT
+            .iter()
+            .filter(|(flag, _)| (flags & flag) != 0)
+            .map(|(_, context)| *context)
+            .collect::<Vec<&str>>();
+        if !context.is_empty() {
+            if intent.is_empty() {
+                intent.push("stop sharing ".white());
+            } else {
+                intent.push(", and stop sharing ".white());
+            }
+            intent.push(context.join(", ").yellow());
+            intent.push(" with other processes".white());
+        }
+        if intent.is_empty() {
+            intent.push("unshare nothing".white());
+        }
+        intent
+    }
+
+    pub(crate) fn setns_intent(&self) -> Vec<ColoredString> {
+        let (link, pid) = self.namespace_fd(self.args[0]);
+        let kinds = namespace_names(self.args[1]);
+        match pid {
+            Some(pid) => match kinds {
+                Some(kinds) => vec![
+                    "enter the ".white(),
+                    kinds.yellow(),
+                    " namespace of the process ".white(),
+                    pid.yellow(),
+                ],
+                None => vec![
+                    "enter all the namespaces of the process ".white(),
+                    pid.yellow(),
+                ],
+            },
+            None => {
+                let mut intent = vec!["enter the namespace: ".white(), link.yellow()];
+                if let Some(kinds) = kinds {
+                    intent.push(" (must be a ".white());
+                    intent.push(kinds.yellow());
+                    intent.push(" namespace)".white());
+                }
+                intent
+            }
+        }
+    }
+
+    // the process that may have moved, and the namespaces it had before when they are not the recorded ones
+    fn namespace_move(&self) -> Option<(Pid, Option<ProcessNamespaces>)> {
+        match self.sysno {
+            Sysno::unshare | Sysno::setns => Some((self.child, None)),
+            Sysno::clone | Sysno::clone3 | Sysno::fork | Sysno::vfork => {
+                let child = self.result.0?;
+                // the new process starts with the namespaces of its parent
+                let parent =
+                    NAMESPACES.with_borrow(|namespaces| namespaces.get(&self.child).cloned());
+                Some((Pid::from_raw(child as i32), parent))
+            }
+            Sysno::write | Sysno::pwrite64 => Some((self.cgroup_move()?, None)),
+            _ => None,
+        }
+    }
+
+    // marks moves to a new namespace or cgroup, after the syscalls that can cause them
+    pub(crate) fn namespace_transitions(&self) -> Vec<ColoredString> {
+        NAMESPACE_TRANSITIONS.with_borrow(|transitions| {
+            let Some((moved, before, after)) = transitions.get(&self.child) else {
+                return vec![];
+            };
+            let mut transition = describe_transition(before, after);
+            if *moved != self.child && !transition.is_empty() {
+                transition.insert(0, format!("\n\tprocess {moved} runs in:").bright_cyan());
+            }
+            transition
+        })
+    }
+
+    // the process moved by writing its pid into a cgroup.procs or cgroup.threads file
+    fn cgroup_move(&self) -> Option<Pid> {
+        let fd = (thread_group(self.child), self.args[0] as i32);
+        if !CGROUP_FILES.with_borrow(|files| files.contains(&fd)) {
+            return None;
+        }
+        let written = SyscallObject::read_bytes_specific_length(
+            self.args[1] as usize,
+            self.child,
+            (self.args[2] as usize).min(32),
+        )?;
+        let pid = String::from_utf8_lossy(&written)
+            .trim()
+            .parse::<i32>()
+            .ok()?;
+        if pid == 0 {
+            Some(self.child)
+        } else {
+            Some(Pid::from_raw(pid))
+        }
+    }
+}
@@ -6,7 +6,10 @@ use std::{
 };
 
 use crate::{
+    io_uring::io_uring_register_opcode,
+    ioctls::{ioc_fields, ioctl_request, TIOCNOTTY},
     syscall_object::SyscallObject,
+    threads::thread_label,
     types::{Bytes, BytesPagesRelevant, LandlockRuleTypeFlags},
     utilities::{
         errno_to_string, get_child_memory_break, get_mem_difference_from_previous,
@@ -18,11 +21,15 @@ use nix::{
     errno::Errno,
     fcntl::{self, AtFlags, FallocateFlags},
     libc::{
-        cpu_set_t, pid_t, rlimit, timespec, timeval, AT_FDCWD, EPOLL_CLOEXEC, EPOLL_CTL_ADD,
-        EPOLL_CTL_DEL, EPOLL_CTL_MOD, FUTEX_CLOCK_REALTIME, FUTEX_CMP_REQUEUE,
+        cpu_set_t, flock, pid_t, rlimit, timespec, timeval, AT_FDCWD, EPOLL_CLOEXEC, EPOLL_CTL_ADD,
+        EPOLL_CTL_DEL, EPOLL_CTL_MOD, FD_CLOEXEC, FUTEX_CLOCK_REALTIME, FUTEX_CMP_REQUEUE,
         FUTEX_CMP_REQUEUE_PI, FUTEX_FD, FUTEX_LOCK_PI, FUTEX_LOCK_PI2, FUTEX_PRIVATE_FLAG,
         FUTEX_REQUEUE, FUTEX_TRYLOCK_PI, FUTEX_UNLOCK_PI, FUTEX_WAIT, FUTEX_WAIT_BITSET,
-        FUTEX_WAIT_REQUEUE_PI, FUTEX_WAKE, FUTEX_WAKE_BITSET, FUTEX_WAKE_OP,
+        FUTEX_WAIT_REQUEUE_PI, FUTEX_WAKE, FUTEX_WAKE_BITSET, FUTEX_WAKE_OP, F_ADD_SEALS, F_DUPFD,
+        F_DUPFD_CLOEXEC, F_GETFD, F_GETFL, F_GETLEASE, F_GETLK, F_GETOWN, F_GETPIPE_SZ,
+        F_GET_SEALS, F_NOTIFY, F_OFD_GETLK, F_OFD_SETLK, F_OFD_SETLKW, F_RDLCK,
+        F_SEAL_FUTURE_WRITE, F_SEAL_GROW, F_SEAL_SEAL, F_SEAL_SHRINK, F_SEAL_WRITE, F_SETFD,
+        F_SETFL, F_SETLEASE, F_SETLK, F_SETLKW, F_SETOWN, F_SETPIPE_SZ, F_UNLCK, F_WRLCK,
         LINUX_REBOOT_CMD_CAD_OFF, MADV_COLD, MADV_COLLAPSE, MADV_DODUMP, MADV_DOFORK,
         MADV_DONTDUMP, MADV_DONTFORK, MADV_DONTNEED, MADV_FREE, MADV_HUGEPAGE, MADV_HWPOISON,
         MADV_KEEPONFORK, MADV_MERGEABLE, MADV_NOHUGEPAGE, MADV_NORMAL, MADV_PAGEOUT,
@@ -32,10 +39,10 @@ use nix::{
         MAP_HUGE_1GB, MAP_HUGE_1MB, MAP_HUGE_256MB, MAP_HUGE_2GB, MAP_HUGE_2MB, MAP_HUGE_32MB,
         MAP_HUGE_512KB, MAP_HUGE_512MB, MAP_HUGE_64KB, MAP_HUGE_8MB, MAP_LOCKED, MAP_NONBLOCK,
         MAP_NORESERVE, MAP_POPULATE, MAP_PRIVATE, MAP_SHARED, MAP_SHARED_VALIDATE, MAP_STACK,
-        MAP_SYNC, MCL_CURRENT, MCL_FUTURE, MCL_ONFAULT, O_APPEND, O_ASYNC, O_CLOEXEC, O_CREAT,
-        O_DIRECT, O_DIRECTORY, O_DSYNC, O_EXCL, O_LARGEFILE, O_NDELAY, O_NOATIME, O_NOCTTY,
-        O_NOFOLLOW, O_NONBLOCK, O_PATH, O_SYNC, O_TMPFILE, O_TRUNC, PRIO_PGRP, PRIO_PROCESS,
-        PRIO_USER, P_ALL, P_PGID, P_PID, P_PIDFD,
+        MAP_SYNC, MCL_CURRENT, MCL_FUTURE, MCL_ONFAULT, O_ACCMODE, O_APPEND, O_ASYNC, O_CLOEXEC,
+        O_CREAT, O_DIRECT, O_DIRECTORY, O_DSYNC, O_EXCL, O_LARGEFILE, O_NDELAY, O_NOATIME,
+        O_NOCTTY, O_NOFOLLOW, O_NONBLOCK, O_PATH, O_RDONLY, O_SYNC, O_TMPFILE, O_TRUNC, O_WRONLY,
+        PRIO_PGRP, PRIO_PROCESS, PRIO_USER, P_ALL, P_PGID, P_PID, P_PIDFD, SEEK_CUR, SEEK_END,
     },
     sys::{
         eventfd,
@@ -63,7 +70,7 @@ impl SyscallObject {
     pub(crate) fn get_syscall_return(&mut self) -> Result<String, ()> {
         let eph_return = self.parse_return_value_one_line();
         if self.paused {
-            self.one_line.truncate(5);
+            self.one_line.truncate(6);
             self.one_line.iter_mut().for_each(|colored| {
                 *colored = colored.clone().dimmed();
             });
@@ -77,10 +84,17 @@ impl SyscallObject {
         use crate::syscall_object::SyscallState::*;
 
         if self.state == Entering {
+            let (id, name) = thread_label(self.child);
+            let name = if name.is_empty() {
+                name
+            } else {
+                format!(" {name}")
+            };
             if FOLLOW_FORKS.get() {
                 self.one_line.extend(vec![
                     "\n".white(),
-                    self.child.to_string().bright_blue(),
+                    id.bright_blue(),
+                    name.clone().cyan(),
                     " ".dimmed(),
                     SyscallObject::colorize_syscall_name(&self.sysno, &self.category),
                     " - ".dimmed(),
@@ -89,7 +103,8 @@ impl SyscallObject {
                 if self.get_syscall_return().is_ok() {
                     self.one_line.extend(vec![
                         "\n".white(),
-                        self.child.to_string().blue(),
+                        id.blue(),
+                        name.clone().cyan(),
                         // self.child.to_string().on_black(),
                         " ".dimmed(),
                         SyscallObject::colorize_syscall_name(&self.sysno, &self.category),
@@ -98,7 +113,8 @@ impl SyscallObject {
                 } else {
                     self.one_line.extend(vec![
                         "\n".white(),
-                        self.child.to_string().red(),
+                        id.red(),
+                        name.clone().cyan(),
                         // self.child.to_string().on_red(),
                         " ".dimmed(),
                         SyscallObject::colorize_syscall_name(&self.sysno, &self.category),
@@ -113,8 +129,6 @@ impl SyscallObject {
         match self.sysno {
             // TODO! unimplemented syscalls
             // preferable to always create a syscall entry in `consts.rs` before writing an entry here
-            // preadv2
-            // pwritev2
             // openat2
             // creat
             // chdir
@@ -136,12 +150,8 @@ impl SyscallObject {
             // accept
             // accept4
             // connect
-            // sendto
             // sendmsg
-            // recvfrom
             // recvmsg
-            // setuid
-            // setgid
             Sysno::brk => {
                 let syscall_brk_num = self.args[0];
                 let syscall_brk = self.pavfol(0);
@@ -278,62 +288,7 @@ impl SyscallObject {
                                 // self.one_line.push(joined_path.as_str().unwrap().yellow());
                             }
                         }
-                        let mut directives = vec![];
-                        if (flags_num & O_APPEND) == O_APPEND {
-                            directives.push("open the file in append mode".yellow());
-                        }
-                        if (flags_num & O_ASYNC) == O_ASYNC {
-                            directives.push("enable signal-driven I/O".yellow());
-                        }
-                        if (flags_num & O_CLOEXEC) == O_CLOEXEC {
-                            directives.push(
-                                "close the file descriptor on the next exec syscall".yellow(),
-                            );
-                        }
-                        if (flags_num & O_CREAT) > 0 {
-                            directives.push("create the file if it does not exist".yellow());
-                        }
-                        if (flags_num & O_DIRECT) > 0 {
-                            directives.push("use direct file I/O".yellow());
-                        }
-                        if (flags_num & O_DIRECTORY) > 0 {
-                            directives.push("fail if the path is not a directory".yellow());
-                        }
-                        if (flags_num & O_DSYNC) > 0 {
-                            directives.push("ensure writes are completely teransferred to hardware before return".yellow());
-                        }
-                        if (flags_num & O_EXCL) > 0 {
-                            directives.push("ensure O_CREAT fails if the file already exists or is a symbolic link".yellow());
-                        }
-                        if (flags_num & O_LARGEFILE) > 0 {
-                            directives.push(
-                                "allow files larger than `off_t` and up to `off64_t`".yellow(),
-                            );
-                        }
-                        if (flags_num & O_NOATIME) > 0 {
-                            directives
-                                .push("do not update the file last access time on read".yellow());
-                        }
-                        if (flags_num & O_NOCTTY) > 0 {
-                            directives
-                                .push("do not use the file as the process's controlling terminal if its a terminal device".yellow());
-                        }
-                        if (flags_num & O_NOFOLLOW) > 0 {
-                            // TODO! change this to have better wording, change `base`
-                            directives
-                                .push("fail if the base of the file is a symbolic link".yellow());
-                        }
-                        if (flags_num & O_NONBLOCK) > 0 || (flags_num & O_NDELAY) > 0 {
-                            // TODO! change this to have better wording, change `base`
-                            directives.push("open the file in non-blocking mode".yellow());
-                        }
-                        if (flags_num & O_PATH) > 0 {
-                            // TODO! change this to have better wording, change `base`
-                            directives.push("return a `shallow` file descriptor".yellow());
-                        }
-                        if (flags_num & O_SYNC) > 0 {
-                            directives.push("ensure writes are completely teransferred to hardware before return".yellow());
-                        }
+                        let directives = open_flags_directives(flags_num);
                         directives_handler(directives, &mut self.one_line);
 
                         if (flags_num & O_TRUNC) > 0 {
@@ -1748,6 +1703,11 @@ impl SyscallObject {
                                 self.one_line.push(bytes_to_read.to_string().yellow());
                                 self.one_line.push(" Bytes".yellow());
                             }
+                            if bytes_num != 0 {
+                                let text = self.buffer_text(self.args[1], bytes_num);
+                                self.one_line.push(": ".green());
+                                self.one_line.push(text.yellow());
+                            }
                         } else {
                             // TODO! granular
                             one_line_error(eph_return, &mut self.one_line, &self.errno);
@@ -1761,11 +1721,11 @@ impl SyscallObject {
                 match self.state {
                     Entering => {
                         self.one_line.push("write ".white());
-                        if bytes_to_write < 20 {
-                            self.one_line.push(format!("{:?}", self.pavfol(1)).yellow());
-                        } else {
-                            self.one_line.push(self.pavfol(2).yellow());
-                        }
+                        let text = self.buffer_text(self.args[1], bytes_to_write);
+                        self.one_line.push(text.yellow());
+                        self.one_line.push(" (".white());
+                        self.one_line.push(self.pavfol(2).yellow());
+                        self.one_line.push(")".white());
                         self.one_line.push(" into the file: ".white());
                         self.one_line.push(filename.yellow());
                     }
@@ -1823,6 +1783,11 @@ impl SyscallObject {
                                 self.one_line.push(bytes_to_read.to_string().yellow());
                                 self.one_line.push(" Bytes".yellow());
                             }
+                            if bytes_num != 0 {
+                                let text = self.buffer_text(self.args[1], bytes_num);
+                                self.one_line.push(": ".green());
+                                self.one_line.push(text.yellow());
+                            }
                         } else {
                             // TODO! granular
                             one_line_error(eph_return, &mut self.one_line, &self.errno);
@@ -1838,11 +1803,11 @@ impl SyscallObject {
                 match self.state {
                     Entering => {
                         self.one_line.push("write ".white());
-                        if bytes_to_write < 20 {
-                            self.one_line.push(format!("{:?}", self.pavfol(1)).yellow());
-                        } else {
-                            self.one_line.push(self.pavfol(2).yellow());
-                        }
+                        let text = self.buffer_text(self.args[1], bytes_to_write);
+                        self.one_line.push(text.yellow());
+                        self.one_line.push(" (".white());
+                        self.one_line.push(self.pavfol(2).yellow());
+                        self.one_line.push(")".white());
                         self.one_line.push(" into the file: ".white());
                         self.one_line.push(filename.yellow());
                         self.one_line.push(" at an offset of ".white());
@@ -1870,6 +1835,71 @@ impl SyscallObject {
                     }
                 }
             }
+            Sysno::sendto => {
+                let bytes_to_send = self.args[2];
+                let socket = self.pavfol(0);
+                match self.state {
+                    Entering => {
+                        self.one_line.push("send ".white());
+                        let text = self.buffer_text(self.args[1], bytes_to_send);
+                        self.one_line.push(text.yellow());
+                        self.one_line.push(" (".white());
+                        self.one_line.push(self.pavfol(2).yellow());
+                        self.one_line.push(") through the socket: ".white());
+                        self.one_line.push(socket.yellow());
+                        if self.args[4] != 0 {
+                            self.one_line.push(" to a specific address".white());
+                        }
+                    }
+                    Exiting => {
+                        let eph_return = self.get_syscall_return();
+                        if eph_return.is_ok() {
+                            let bytes_string = eph_return.unwrap();
+                            let bytes_num = self.result.0.unwrap();
+                            self.one_line.push(" |=> ".white());
+                            if bytes_num < bytes_to_send {
+                                self.one_line.push("sent ".green());
+                                self.one_line.push(bytes_string.yellow());
+                                self.one_line.push(" (fewer than requested)".green());
+                            } else {
+                                self.one_line.push("sent all ".green());
+                                self.one_line.push(bytes_to_send.to_string().yellow());
+                                self.one_line.push(" Bytes".yellow());
+                            }
+                        } else {
+                            one_line_error(eph_return, &mut self.one_line, &self.errno);
+                        }
+                    }
+                }
+            }
+            Sysno::recvfrom => {
+                let socket = self.pavfol(0);
+                match self.state {
+                    Entering => {
+                        self.one_line.push("receive up to ".white());
+                        self.one_line.push(self.pavfol(2).yellow());
+                        self.one_line.push(" from the socket: ".white());
+                        self.one_line.push(socket.yellow());
+                    }
+                    Exiting => {
+                        let eph_return = self.get_syscall_return();
+                        if eph_return.is_ok() {
+                            let bytes_string = eph_return.unwrap();
+                            let bytes_num = self.result.0.unwrap();
+                            self.one_line.push(" |=> ".white());
+                            self.one_line.push("received ".green());
+                            self.one_line.push(bytes_string.yellow());
+                            if bytes_num != 0 {
+                                let text = self.buffer_text(self.args[1], bytes_num);
+                                self.one_line.push(": ".green());
+                                self.one_line.push(text.yellow());
+                            }
+                        } else {
+                            one_line_error(eph_return, &mut self.one_line, &self.errno);
+                        }
+                    }
+                }
+            }
             Sysno::readv => {
                 let number_of_iovecs = self.args[2];
                 let filename = self.pavfol(0);
@@ -1931,7 +1961,7 @@ impl SyscallObject {
                     }
                 }
             }
-            Sysno::preadv => {
+            Sysno::preadv | Sysno::preadv2 => {
                 let number_of_iovecs = self.args[2];
                 let filename = self.pavfol(0);
                 let offset = self.pavfol(3);
@@ -1947,8 +1977,16 @@ impl SyscallObject {
                                 .push(" scattered regions of memory from the file: ".white());
                         }
                         self.one_line.push(filename.yellow());
-                        self.one_line.push(" at an offset of ".white());
-                        self.one_line.push(offset.yellow());
+                        if self.sysno == Sysno::preadv2 && self.args[3] as i64 == -1 {
+                            self.one_line.push(" at the current file offset".white());
+                        } else {
+                            self.one_line.push(" at an offset of ".white());
+                            self.one_line.push(offset.yellow());
+                        }
+                        if self.sysno == Sysno::preadv2 {
+                            let flags = self.rwf_flags();
+                            self.one_line.extend(flags);
+                        }
                     }
                     Exiting => {
                         let eph_return = self.get_syscall_return();
@@ -1964,7 +2002,7 @@ impl SyscallObject {
                     }
                 }
             }
-            Sysno::pwritev => {
+            Sysno::pwritev | Sysno::pwritev2 => {
                 let number_of_iovecs = self.args[2];
                 let filename = self.pavfol(0);
                 let offset = self.pavfol(3);
@@ -1981,8 +2019,16 @@ impl SyscallObject {
                                 .push(" scattered regions of memory of the file: ".white());
                         }
                         self.one_line.push(filename.yellow());
-                        self.one_line.push(" at an offset of ".white());
-                        self.one_line.push(offset.yellow());
+                        if self.sysno == Sysno::pwritev2 && self.args[3] as i64 == -1 {
+                            self.one_line.push(" at the current file offset".white());
+                        } else {
+                            self.one_line.push(" at an offset of ".white());
+                            self.one_line.push(offset.yellow());
+                        }
+                        if self.sysno == Sysno::pwritev2 {
+                            let flags = self.rwf_flags();
+                            self.one_line.extend(flags);
+                        }
                     }
                     Exiting => {
                         let eph_return = self.get_syscall_return();
@@ -2934,6 +2980,8 @@ impl SyscallObject {
                                 self.one_line.push(res.to_string().blue());
                                 self.one_line
                                     .push(" file descriptors with new events".green());
+                                let ready = self.ready_fds();
+                                self.one_line.extend(ready);
                             }
                         } else {
                             // TODO! granular
@@ -3006,6 +3054,8 @@ impl SyscallObject {
                                 self.one_line.push(res.to_string().blue());
                                 self.one_line
                                     .push(" file descriptors with new events".green());
+                                let ready = self.ready_fds();
+                                self.one_line.extend(ready);
                             }
                         } else {
                             // TODO! granular
@@ -3037,6 +3087,8 @@ impl SyscallObject {
                                 self.one_line.push(num_fds.to_string().blue());
                                 self.one_line
                                     .push(" file descriptors with new events".green());
+                                let ready = self.ready_fds();
+                                self.one_line.extend(ready);
                             }
                         } else {
                             // TODO! granular
@@ -3086,6 +3138,8 @@ impl SyscallObject {
                                 self.one_line.push(num_fds.to_string().blue());
                                 self.one_line
                                     .push(" file descriptors with new events".green());
+                                let ready = self.ready_fds();
+                                self.one_line.extend(ready);
                             }
                         } else {
                             // TODO! granular
@@ -3164,8 +3218,17 @@ impl SyscallObject {
                     Exiting => {
                         let eph_return = self.get_syscall_return();
                         if eph_return.is_ok() {
+                            let num_events = self.result.0.unwrap();
                             self.one_line.push(" |=> ".white());
-                            self.one_line.push("Successfull".green());
+                            if num_events == 0 {
+                                self.one_line.push("timed out before any events".green());
+                            } else {
+                                self.one_line.push(num_events.to_string().blue());
+                                self.one_line
+                                    .push(" file descriptors with new events".green());
+                                let ready = self.ready_fds();
+                                self.one_line.extend(ready);
+                            }
                         } else {
                             // TODO! granular
                             one_line_error(eph_return, &mut self.one_line, &self.errno);
@@ -3204,8 +3267,17 @@ impl SyscallObject {
                     Exiting => {
                         let eph_return = self.get_syscall_return();
                         if eph_return.is_ok() {
+                            let num_events = self.result.0.unwrap();
                             self.one_line.push(" |=> ".white());
-                            self.one_line.push("Successfull".green());
+                            if num_events == 0 {
+                                self.one_line.push("timed out before any events".green());
+                            } else {
+                                self.one_line.push(num_events.to_string().blue());
+                                self.one_line
+                                    .push(" file descriptors with new events".green());
+                                let ready = self.ready_fds();
+                                self.one_line.extend(ready);
+                            }
                         } else {
                             // TODO! granular
                             one_line_error(eph_return, &mut self.one_line, &self.errno);
@@ -3248,8 +3320,17 @@ impl SyscallObject {
                     Exiting => {
                         let eph_return = self.get_syscall_return();
                         if eph_return.is_ok() {
+                            let num_events = self.result.0.unwrap();
                             self.one_line.push(" |=> ".white());
-                            self.one_line.push("Successfull".green());
+                            if num_events == 0 {
+                                self.one_line.push("timed out before any events".green());
+                            } else {
+                                self.one_line.push(num_events.to_string().blue());
+                                self.one_line
+                                    .push(" file descriptors with new events".green());
+                                let ready = self.ready_fds();
+                                self.one_line.extend(ready);
+                            }
                         } else {
                             // TODO! granular
                             one_line_error(eph_return, &mut self.one_line, &self.errno);
@@ -3263,24 +3344,32 @@ impl SyscallObject {
                 let file_descriptor = self.args[2];
                 match self.state {
                     Entering => {
-                        if (operation as i32 & EPOLL_CTL_ADD) == EPOLL_CTL_ADD {
-                            self.one_line.push("add".yellow());
-                            self.one_line.push(" file descriptor ".white());
-                            self.one_line.push(file_descriptor.to_string().blue());
-                            self.one_line.push(" to ".white());
-                        } else if (operation as i32 & EPOLL_CTL_DEL) == EPOLL_CTL_DEL {
-                            self.one_line.push("remove".yellow());
-                            self.one_line.push(" file descriptor ".white());
-                            self.one_line.push(file_descriptor.to_string().blue());
-                            self.one_line.push(" from ".white());
-                        } else if (operation as i32 & EPOLL_CTL_MOD) == EPOLL_CTL_MOD {
-                            self.one_line.push("modify the settings of ".yellow());
-                            self.one_line.push(" file descriptor ".white());
-                            self.one_line.push(file_descriptor.to_string().blue());
-                            self.one_line.push(" in ".white());
+                        // the operations are plain values, MOD shares its bits with ADD and DEL
+                        match operation as i32 {
+                            EPOLL_CTL_ADD => {
+                                self.one_line.push("add".yellow());
+                                self.one_line.push(" file descriptor ".white());
+                                self.one_line.push(file_descriptor.to_string().blue());
+                                self.one_line.push(" to ".white());
+                            }
+                            EPOLL_CTL_DEL => {
+                                self.one_line.push("remove".yellow());
+                                self.one_line.push(" file descriptor ".white());
+                                self.one_line.push(file_descriptor.to_string().blue());
+                                self.one_line.push(" from ".white());
+                            }
+                            EPOLL_CTL_MOD => {
+                                self.one_line.push("modify the settings of".yellow());
+                                self.one_line.push(" file descriptor ".white());
+                                self.one_line.push(file_descriptor.to_string().blue());
+                                self.one_line.push(" in ".white());
+                            }
+                            _ => {}
                         }
                         self.one_line.push("epoll instance ".white());
                         self.one_line.push(epfd.to_string().blue());
+                        let interest = self.epoll_ctl_interest();
+                        self.one_line.extend(interest);
                     }
                     Exiting => {
                         let eph_return = self.get_syscall_return();
@@ -3297,18 +3386,47 @@ impl SyscallObject {
             Sysno::ioctl => {
                 let filename = self.pavfol(0);
                 match self.state {
-                    Entering => {
-                        self.one_line.push("perform operation ".white());
-                        self.one_line
-                            .push(format!("#{}", self.args[1].to_string()).yellow());
-                        self.one_line.push(" on the device: ".white());
-                        self.one_line.push(filename.yellow());
-                    }
+                    Entering => match ioctl_request(self.args[1]) {
+                        Some((name, intent)) => {
+                            self.one_line.push(intent.white());
+                            if self.args[1] != TIOCNOTTY {
+                                if intent.ends_with("through") {
+                                    self.one_line.push(" the socket: ".white());
+                                } else {
+                                    self.one_line.push(" the device: ".white());
+                                }
+                                if filename.is_empty() {
+                                    self.one_line.push(self.args[0].to_string().yellow());
+                                } else {
+                                    self.one_line.push(filename.yellow());
+                                }
+                            }
+                            let details = self.ioctl_request_details();
+                            self.one_line.extend(details);
+                            self.one_line.push(" (".white());
+                            self.one_line.push(name.yellow());
+                            self.one_line.push(")".white());
+                        }
+                        None => {
+                            self.one_line.push("perform operation ".white());
+                            self.one_line
+                                .push(format!("#{}", self.args[1].to_string()).yellow());
+                            self.one_line.push(" on the device: ".white());
+                            self.one_line.push(filename.yellow());
+                            self.one_line.push(" ".white());
+                            self.one_line.extend(ioc_fields(self.args[1]));
+                        }
+                    },
                     Exiting => {
                         let eph_return = self.get_syscall_return();
                         if eph_return.is_ok() {
                             self.one_line.push(" |=> ".white());
-                            self.one_line.push("operation successful".green());
+                            let result = self.ioctl_result();
+                            if result.is_empty() {
+                                self.one_line.push("operation successful".green());
+                            } else {
+                                self.one_line.extend(result);
+                            }
                         } else {
                             // TODO! granular
                             one_line_error(eph_return, &mut self.one_line, &self.errno);
@@ -3317,23 +3435,314 @@ impl SyscallObject {
                 }
             }
             Sysno::fcntl => {
+                // not exported by libc for gnu targets
+                const F_SETSIG: i32 = 10;
+                const F_GETSIG: i32 = 11;
                 let filename = self.pavfol(0);
-                match self.state {
-                    Entering => {
-                        self.one_line.push("perform operation ".white());
-                        self.one_line
-                            .push(format!("#{}", self.args[1].to_string()).yellow());
-                        self.one_line.push(" on the file: ".white());
-                        self.one_line.push(filename.yellow());
-                    }
+                let operation = self.args[1] as i32;
+                let argument = self.args[2];
+                let is_lock = matches!(
+                    operation,
+                    F_GETLK | F_SETLK | F_SETLKW | F_OFD_GETLK | F_OFD_SETLK | F_OFD_SETLKW
+                );
+                match self.state {
+                    Entering => match operation {
+                        F_DUPFD | F_DUPFD_CLOEXEC => {
+                            self.one_line
+                                .push("duplicate the file descriptor: ".white());
+                            self.one_line.push(filename.yellow());
+                            self.one_line
+                                .push(" using the lowest available number from ".white());
+                            self.one_line.push(argument.to_string().yellow());
+                            if operation == F_DUPFD_CLOEXEC {
+                                self.one_line.push(" (".white());
+                                self.one_line.push(
+                                    "close the file descriptor on the next exec syscall".yellow(),
+                                );
+                                self.one_line.push(")".white());
+                            }
+                        }
+                        F_GETFD => {
+                            self.one_line
+                                .push("get the file descriptor flags of: ".white());
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_SETFD => {
+                            if (argument as i32 & FD_CLOEXEC) == FD_CLOEXEC {
+                                self.one_line.push(
+                                    "close on the next exec syscall the file descriptor: ".white(),
+                                );
+                            } else {
+                                self.one_line.push(
+                                    "keep open on the next exec syscall the file descriptor: "
+                                        .white(),
+                                );
+                            }
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_GETFL => {
+                            self.one_line
+                                .push("get the status flags of the file: ".white());
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_SETFL => {
+                            self.one_line
+                                .push("set the status flags of the file: ".white());
+                            self.one_line.push(filename.yellow());
+                            let directives = open_flags_directives(argument as i32);
+                            if directives.is_empty() {
+                                self.one_line.push(" (".white());
+                                self.one_line.push("clear all status flags".yellow());
+                                self.one_line.push(")".white());
+                            } else {
+                                directives_handler(directives, &mut self.one_line);
+                            }
+                        }
+                        F_GETLK | F_OFD_GETLK => {
+                            // the kernel overwrites the struct with the conflicting lock, so it is described on exit
+                            self.one_line.push(
+                                "check for locks conflicting with a lock on the file: ".white(),
+                            );
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_SETLK | F_SETLKW | F_OFD_SETLK | F_OFD_SETLKW => {
+                            match SyscallObject::read_bytes_as_struct::<32, flock>(
+                                argument as usize,
+                                self.child,
+                            ) {
+                                Some(lock) => {
+                                    if lock.l_type == F_UNLCK as i16 {
+                                        self.one_line.push("release the lock on ".white());
+                                    } else {
+                                        self.one_line.push("place a ".white());
+                                        self.one_line.push(lock_type_name(lock.l_type).yellow());
+                                        self.one_line.push(" on ".white());
+                                    }
+                                    self.one_line.extend(lock_range(&lock));
+                                }
+                                None => {
+                                    self.one_line.push("change a lock on ".white());
+                                }
+                            }
+                            self.one_line.push(" of the file: ".white());
+                            self.one_line.push(filename.yellow());
+                            let mut directives = vec![];
+                            if operation == F_SETLKW || operation == F_OFD_SETLKW {
+                                directives
+                                    .push("wait until conflicting locks are released".yellow());
+                            } else {
+                                directives.push(
+                                    "fail immediately if a conflicting lock is held".yellow(),
+                                );
+                            }
+                            if operation == F_OFD_SETLK || operation == F_OFD_SETLKW {
+                                directives.push("open file description lock".yellow());
+                            }
+                            directives_handler(directives, &mut self.one_line);
+                        }
+                        F_SETOWN => {
+                            self.one_line
+                                .push("send the I/O availability signals of the file: ".white());
+                            self.one_line.push(filename.yellow());
+                            let owner = argument as i32;
+                            if owner < 0 {
+                                self.one_line.push(" to the process group: ".white());
+                                self.one_line.push((-owner).to_string().yellow());
+                            } else {
+                                self.one_line.push(" to the process: ".white());
+                                self.one_line.push(owner.to_string().yellow());
+                            }
+                        }
+                        F_GETOWN => {
+                            self.one_line.push(
+                                    "get the process receiving the I/O availability signals of the file: "
+                                        .white(),
+                                );
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_SETSIG => {
+                            self.one_line
+                                .push("change the I/O availability signal of the file: ".white());
+                            self.one_line.push(filename.yellow());
+                            self.one_line.push(" to ".white());
+                            match x86_signal_to_string(argument) {
+                                Some(signal) => self.one_line.push(signal.yellow()),
+                                None => self.one_line.push("SIGIO".yellow()),
+                            }
+                        }
+                        F_GETSIG => {
+                            self.one_line
+                                .push("get the I/O availability signal of the file: ".white());
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_SETLEASE => {
+                            match argument as i32 {
+                                F_RDLCK => self
+                                    .one_line
+                                    .push("take a read lease on the file: ".white()),
+                                F_WRLCK => self
+                                    .one_line
+                                    .push("take a write lease on the file: ".white()),
+                                _ => self
+                                    .one_line
+                                    .push("release the lease on the file: ".white()),
+                            }
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_GETLEASE => {
+                            self.one_line
+                                .push("get the lease held on the file: ".white());
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_NOTIFY => {
+                            self.one_line.push("watch the directory: ".white());
+                            self.one_line.push(filename.yellow());
+                            self.one_line.push(" for changes".white());
+                        }
+                        F_SETPIPE_SZ => {
+                            self.one_line
+                                .push("change the capacity of the pipe: ".white());
+                            self.one_line.push(filename.yellow());
+                            self.one_line.push(" to ".white());
+                            self.one_line
+                                .push(Bytes::from(argument as usize).to_string().yellow());
+                        }
+                        F_GETPIPE_SZ => {
+                            self.one_line.push("get the capacity of the pipe: ".white());
+                            self.one_line.push(filename.yellow());
+                        }
+                        F_ADD_SEALS => {
+                            self.one_line.push("seal the file: ".white());
+                            self.one_line.push(filename.yellow());
+                            self.one_line.push(" against ".white());
+                            vanilla_commas_handler(
+                                seals_names(argument as i32),
+                                &mut self.one_line,
+                            );
+                        }
+                        F_GET_SEALS => {
+                            self.one_line.push("get the seals of the file: ".white());
+                            self.one_line.push(filename.yellow());
+                        }
+                        _ => {
+                            self.one_line.push("perform operation ".white());
+                            self.one_line
+                                .push(format!("#{}", self.args[1].to_string()).yellow());
+                            self.one_line.push(" on the file: ".white());
+                            self.one_line.push(filename.yellow());
+                        }
+                    },
                     Exiting => {
                         let eph_return = self.get_syscall_return();
                         if eph_return.is_ok() {
+                            let result = self.result.0.unwrap();
                             self.one_line.push(" |=> ".white());
-                            self.one_line.push("operation successful".green());
+                            match operation {
+                                F_DUPFD | F_DUPFD_CLOEXEC => {
+                                    self.one_line
+                                        .push("file descriptor duplicated to: ".green());
+                                    self.one_line.push(result.to_string().yellow());
+                                }
+                                F_GETFD => {
+                                    if (result as i32 & FD_CLOEXEC) == FD_CLOEXEC {
+                                        self.one_line
+                                            .push("closes on the next exec syscall".green());
+                                    } else {
+                                        self.one_line
+                                            .push("stays open on the next exec syscall".green());
+                                    }
+                                }
+                                F_GETFL => {
+                                    let flags = result as i32;
+                                    match flags & O_ACCMODE {
+                                        O_RDONLY => {
+                                            self.one_line.push("opened for reading".green())
+                                        }
+                                        O_WRONLY => {
+                                            self.one_line.push("opened for writing".green())
+                                        }
+                                        _ => self
+                                            .one_line
+                                            .push("opened for reading and writing".green()),
+                                    }
+                                    let directives = open_flags_directives(flags);
+                                    directives_handler(directives, &mut self.one_line);
+                                }
+                                F_GETLK | F_OFD_GETLK => {
+                                    match SyscallObject::read_bytes_as_struct::<32, flock>(
+                                        argument as usize,
+                                        self.child,
+                                    ) {
+                                        Some(lock) if lock.l_type != F_UNLCK as i16 => {
+                                            self.one_line.push("conflicting ".green());
+                                            self.one_line
+                                                .push(lock_type_name(lock.l_type).yellow());
+                                            self.one_line.push(" on ".green());
+                                            self.one_line.extend(lock_range(&lock));
+                                            if lock.l_pid > 0 {
+                                                self.one_line.push(" held by process: ".green());
+                                                self.one_line.push(lock.l_pid.to_string().yellow());
+                                            }
+                                        }
+                                        _ => {
+                                            self.one_line.push("no conflicting lock".green());
+                                        }
+                                    }
+                                }
+                                F_SETLK | F_SETLKW | F_OFD_SETLK | F_OFD_SETLKW => {
+                                    self.one_line.push("lock changed".green());
+                                }
+                                F_GETOWN => {
+                                    let owner = result as i32;
+                                    if owner < 0 {
+                                        self.one_line.push("process group: ".green());
+                                        self.one_line.push((-owner).to_string().yellow());
+                                    } else if owner == 0 {
+                                        self.one_line.push("no process".green());
+                                    } else {
+                                        self.one_line.push("process: ".green());
+                                        self.one_line.push(owner.to_string().yellow());
+                                    }
+                                }
+                                F_GETSIG => match x86_signal_to_string(result) {
+                                    Some(signal) => self.one_line.push(signal.yellow()),
+                                    None => self.one_line.push("SIGIO".yellow()),
+                                },
+                                F_GETLEASE => match result as i32 {
+                                    F_RDLCK => self.one_line.push("read lease held".green()),
+                                    F_WRLCK => self.one_line.push("write lease held".green()),
+                                    _ => self.one_line.push("no lease held".green()),
+                                },
+                                F_SETPIPE_SZ | F_GETPIPE_SZ => {
+                                    self.one_line.push("pipe capacity: ".green());
+                                    self.one_line
+                                        .push(Bytes::from(result as usize).to_string().yellow());
+                                }
+                                F_GET_SEALS => {
+                                    let seals = seals_names(result as i32);
+                                    if seals.is_empty() {
+                                        self.one_line.push("no seals".green());
+                                    } else {
+                                        self.one_line.push("sealed against ".green());
+                                        vanilla_commas_handler(seals, &mut self.one_line);
+                                    }
+                                }
+                                _ => {
+                                    self.one_line.push("operation successful".green());
+                                }
+                            }
                         } else {
-                            // TODO! granular
-                            one_line_error(eph_return, &mut self.one_line, &self.errno);
+                            // lock contention is reported as EAGAIN or EACCES
+                            if is_lock
+                                && matches!(self.errno, Some(Errno::EAGAIN) | Some(Errno::EACCES))
+                            {
+                                self.one_line.push(" |=> ".white());
+                                self.one_line
+                                    .push("a conflicting lock is held by another process".red());
+                            } else {
+                                // TODO! granular
+                                one_line_error(eph_return, &mut self.one_line, &self.errno);
+                            }
                         }
                     }
                 }
@@ -3507,11 +3916,17 @@ impl SyscallObject {
                                 // second is NULL: query the current signal handler
                                 // second and third is NULL: check whether a given signal is valid for the current machine
                                 if !signal_action.is_null() {
-                                    self.one_line
-                                        .push("change the process's default handler for ".white());
-                                    self.one_line.push(signal_as_string.yellow());
-                                    self.one_line.push(" to the provided action".white());
-                                    if !old_signal_action.is_null() {
+                                    match self.sigaction_intent() {
+                                        Some(intent) => self.one_line.extend(intent),
+                                        None => {
+                                            self.one_line.push(
+                                                "change the process's default handler for ".white(),
+                                            );
+                                            self.one_line.push(signal_as_string.yellow());
+                                            self.one_line.push(" to the provided action".white());
+                                        }
+                                    }
+                                    if !old_signal_action.is_null() {
                                         self.one_line.push(
                                             ", and retrieve the current signal handler".white(),
                                         );
@@ -3562,6 +3977,8 @@ impl SyscallObject {
                                     self.one_line.push("successful".green());
                                 }
                             }
+                            let previous = self.previous_sigaction();
+                            self.one_line.extend(previous);
                         } else {
                             // TODO! granular
                             one_line_error(eph_return, &mut self.one_line, &self.errno);
@@ -3599,6 +4016,8 @@ impl SyscallObject {
                                 }
                                 _ => {}
                             }
+                            let set = self.sigprocmask_set();
+                            self.one_line.extend(set);
                         }
                     }
                     Exiting => {
@@ -3625,6 +4044,8 @@ impl SyscallObject {
                                     }
                                     _ => {}
                                 }
+                                let blocked = self.blocked_signals();
+                                self.one_line.extend(blocked);
                             }
                         } else {
                             // TODO! granular
@@ -5475,370 +5896,30 @@ impl SyscallObject {
                     }
                 }
             }
-            Sysno::clone3 => {
-                let size_of_cl_args = self.args[1];
-                let cl_args = SyscallObject::read_bytes_as_struct::<88, clone3::CloneArgs>(
-                    self.args[0] as usize,
-                    self.child as _,
-                )
-                .unwrap();
-                let clone_flags: clone3::Flags = unsafe { std::mem::transmute(cl_args.flags) };
-                let clone_vm = clone_flags.contains(clone3::Flags::VM);
-
-                match self.state {
-                    Entering => {
-                        if clone_vm {
-                            self.one_line.push("spawn a new thread with a ".white());
-
-                            self.one_line.push(
-                                SyscallObject::style_bytes_page_aligned_ceil(cl_args.stack_size)
-                                    .yellow(),
-                            );
-                            self.one_line.push(" stack starting at ".white());
-                            self.one_line
-                                .push(format!("0x{:x}", cl_args.stack).yellow());
-                            // directives.push("run in the same memory space".yellow());
-                        } else {
-                            self.one_line.push("spawn a new child process".white());
-                            // directives.push("copy the memory space".yellow());
-                        }
-
-                        // share with parent
-                        //
-                        //
-                        //
-                        //
-
-                        let mut shares = vec![];
-                        if clone_flags.contains(clone3::Flags::FILES) {
-                            shares.push("the file descriptor table".yellow());
-                        }
-
-                        //  else {
-                        //     shares.push("copy the file descriptor table".yellow());
-                        // }
-
-                        if clone_flags.contains(clone3::Flags::FS) {
-                            shares.push("filesystem information".yellow());
-                        }
-
-                        // else {
-                        //     shares.push("copy filesystem information".yellow());
-                        // }
-
-                        // if clone_flags.contains(clone3::Flags::INTO_CGROUP) {
-                        // }
-
-                        if clone_flags.contains(clone3::Flags::IO) {
-                            shares.push("I/O context".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::SIGHAND) {
-                            shares.push("the table of signal handlers".yellow());
-                        }
-                        //  else {
-                        //     shares.push("copy the signal handlers table".yellow());
-                        // }
-                        if clone_flags.contains(clone3::Flags::SYSVSEM) {
-                            shares.push("sem-adj values".yellow());
-                        }
-                        //  else {
-                        //     shares.push("don't share sem-adj values".yellow());
-                        // }
-
-                        if !shares.is_empty() {
-                            self.one_line.push(" (".white());
-                            self.one_line.push("share ".white());
-                            anding_handler(shares, &mut self.one_line);
-                            self.one_line.push(")".white());
-                        }
-
-                        // execute in new
-                        //
-                        //
-                        //
-                        //
-                        let mut executes = vec![];
-
-                        if clone_flags.contains(clone3::Flags::NEWCGROUP) {
-                            executes.push("CGroup namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWIPC) {
-                            executes.push("IPC namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWNET) {
-                            executes.push("network namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWNS) {
-                            executes.push("mount namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWPID) {
-                            executes.push("PID namespace".yellow());
-                        }
-                        // if clone_flags.contains(clone3::Flags::NEWTIME) {
-                        // }
-                        if clone_flags.contains(clone3::Flags::NEWUSER) {
-                            executes.push("user namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWUTS) {
-                            executes.push("UTS namespace".yellow());
-                        }
-
-                        if !executes.is_empty() {
-                            self.one_line.push(" (".white());
-                            self.one_line.push("execute in a new ".white());
-                            anding_handler(executes, &mut self.one_line);
-                            self.one_line.push(")".white());
-                        }
-
-                        let mut directives = vec![];
-
-                        if clone_flags.contains(clone3::Flags::PARENT) {
-                            directives.push("inherit the same parent".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::PARENT_SETTID) {
-                            directives.push("store the child TID in the parent's memory".yellow());
-                        }
-                        // It is currently not possible to use this flag together with CLONE_THREAD. This
-                        // means that the process identified by the PID file descriptor will always be a
-                        // thread group leader.
-                        if clone_flags.contains(clone3::Flags::PIDFD) {
-                            directives.push("return a PIDFD for the child".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::PTRACE) {
-                            directives.push("allow ptracing if parent is ptraced".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::SETTLS) {
-                            directives.push("modify the thread local storage descriptor".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::THREAD) {
-                            directives.push("place in the same thread group".yellow());
-                        } else {
-                            directives.push("place in a new thread group".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::UNTRACED) {
-                            directives.push("prevent forcing of CLONE_PTRACE".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::VFORK) {
-                            directives.push("suspend parent execution as with vFork".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::CHILD_CLEARTID) {
-                            // directives.push("set the child's ".yellow());
-                            // directives.push("clear_child_tid".blue());
-                            // directives.push("to ".yellow());
-                            // directives.push(cl_args.child_tid.to_string().blue());
-                            directives.push(
-                        "clear TID on the child's memory on exit and wake the associated futex"
-                            .yellow(),
-                    );
-                        }
-                        if clone_flags.contains(clone3::Flags::CHILD_SETTID) {
-                            directives.push("store the child TID in child's memory".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::CLEAR_SIGHAND) {
-                            directives.push("default all inherited signal handlers".yellow());
-                        }
-
-                        directives_handler(directives, &mut self.one_line);
-                    }
-                    Exiting => {
-                        let eph_return = self.get_syscall_return();
-                        if eph_return.is_ok() {
-                            self.one_line.push(" |=> ".white());
-                            self.one_line.push("thread id of the child: ".green());
-                            self.one_line.push(eph_return.unwrap().yellow());
-                            if clone_vm {
-                                self.one_line.push(new_thread());
-                            } else {
-                                self.one_line.push(new_process());
-                            }
-                        } else {
-                            // TODO! granular
-                            one_line_error(eph_return, &mut self.one_line, &self.errno);
-                        }
-                    }
+            Sysno::clone3 | Sysno::clone => match self.state {
+                Entering => {
+                    let intent = self.clone_intent();
+                    self.one_line.extend(intent);
                 }
-            }
-            Sysno::clone => {
-                let clone_flags: clone3::Flags = unsafe { std::mem::transmute(self.args[0]) };
-                let clone_vm = clone_flags.contains(clone3::Flags::VM);
-                let stack = self.args[0];
-
-                match self.state {
-                    Entering => {
-                        if clone_vm {
-                            self.one_line
-                                .push("spawn a new thread at stack address ".white());
-                            self.one_line.push(format!("0x{:x}", stack).yellow());
-                            // directives.push("run in the same memory space".yellow());
-                        } else {
-                            self.one_line.push("spawn a new child process".white());
-                            // directives.push("copy the memory space".yellow());
-                        }
-
-                        // share with parent
-                        //
-                        //
-                        //
-                        //
-
-                        let mut shares = vec![];
-                        if clone_flags.contains(clone3::Flags::FILES) {
-                            shares.push("the file descriptor table".yellow());
-                        }
-
-                        //  else {
-                        //     shares.push("copy the file descriptor table".yellow());
-                        // }
-
-                        if clone_flags.contains(clone3::Flags::FS) {
-                            shares.push("filesystem information".yellow());
-                        }
-
-                        // else {
-                        //     shares.push("copy filesystem information".yellow());
-                        // }
-
-                        // if clone_flags.contains(clone3::Flags::INTO_CGROUP) {
-                        // }
-
-                        if clone_flags.contains(clone3::Flags::IO) {
-                            shares.push("I/O context".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::SIGHAND) {
-                            shares.push("the table of signal handlers".yellow());
-                        }
-                        //  else {
-                        //     shares.push("copy the signal handlers table".yellow());
-                        // }
-                        if clone_flags.contains(clone3::Flags::SYSVSEM) {
-                            shares.push("sem-adj values".yellow());
-                        }
-                        //  else {
-                        //     shares.push("don't share sem-adj values".yellow());
-                        // }
-
-                        if !shares.is_empty() {
-                            self.one_line.push(" (".white());
-                            self.one_line.push("share ".white());
-                            anding_handler(shares, &mut self.one_line);
-                            self.one_line.push(")".white());
-                        }
-
-                        // execute in new
-                        //
-                        //
-                        //
-                        //
-                        let mut executes = vec![];
-
-                        if clone_flags.contains(clone3::Flags::NEWCGROUP) {
-                            executes.push("CGroup namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWIPC) {
-                            executes.push("IPC namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWNET) {
-                            executes.push("network namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWNS) {
-                            executes.push("mount namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWPID) {
-                            executes.push("PID namespace".yellow());
-                        }
-                        // if clone_flags.contains(clone3::Flags::NEWTIME) {
-                        // }
-                        if clone_flags.contains(clone3::Flags::NEWUSER) {
-                            executes.push("user namespace".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::NEWUTS) {
-                            executes.push("UTS namespace".yellow());
-                        }
-
-                        if !executes.is_empty() {
-                            self.one_line.push(" (".white());
-                            self.one_line.push("execute in a new ".white());
-                            anding_handler(executes, &mut self.one_line);
-                            self.one_line.push(")".white());
-                        }
-
-                        let mut directives = vec![];
-
-                        if clone_flags.contains(clone3::Flags::PARENT) {
-                            directives.push("inherit the same parent".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::PARENT_SETTID) {
-                            directives.push("store the child TID in the parent's memory".yellow());
-                        }
-                        // It is currently not possible to use this flag together with CLONE_THREAD. This
-                        // means that the process identified by the PID file descriptor will always be a
-                        // thread group leader.
-                        if clone_flags.contains(clone3::Flags::PIDFD) {
-                            directives.push("return a PIDFD for the child".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::PTRACE) {
-                            directives.push("allow ptracing if parent is ptraced".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::SETTLS) {
-                            directives.push("modify the thread local storage descriptor".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::THREAD) {
-                            directives.push("place in the same thread group".yellow());
-                        } else {
-                            directives.push("place in a new thread group".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::UNTRACED) {
-                            directives.push("prevent forcing of CLONE_PTRACE".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::VFORK) {
-                            directives.push("suspend parent execution as with vFork".yellow());
-                        }
-                        // CLONE_CHILD_CLEARTID, for instance, is designed to support pthread_join.
-                        // What it essentially does is zero the value at ctid,
-                        // then wake up threads that have called a futex_wait on that address.
-                        // Thus, pthread_join can be implemented by simply checking to see if ctid is zero
-                        // (and returning immediately with the status if it is),
-                        // then doing a futex_wait if necessary (assuming proper synchronization).
-                        if clone_flags.contains(clone3::Flags::CHILD_CLEARTID) {
-                            directives.push(
-                        "clear TID on the child's memory on exit and wake the associated futex"
-                            .yellow(),
-                    );
-                        }
-                        if clone_flags.contains(clone3::Flags::CHILD_SETTID) {
-                            directives.push("store the child TID in child's memory".yellow());
-                        }
-                        if clone_flags.contains(clone3::Flags::CLEAR_SIGHAND) {
-                            directives.push("default all inherited signal handlers".yellow());
-                        }
-
-                        directives_handler(directives, &mut self.one_line);
-                    }
-                    Exiting => {
-                        let eph_return = self.get_syscall_return();
-                        if eph_return.is_ok() {
-                            self.one_line.push(" |=> ".white());
-                            self.one_line.push("thread id of the child: ".green());
-                            self.one_line.push(eph_return.unwrap().yellow());
-                            // let a = -38;
-                            // let b: u32 = unsafe { mem::transmute(a) };
-                            // pp!("b: ", b);
-                            // let a: i64 = -38;
-                            // let b: u64 = unsafe { mem::transmute(a) };
-                            // pp!("b: ", b);
-                            // TODO! fix occasional error (syscall returns -38)
-                            if clone_vm {
-                                self.one_line.push(new_thread());
-                            } else {
-                                self.one_line.push(new_process());
-                            }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        self.one_line.push("thread id of the child: ".green());
+                        self.one_line.push(eph_return.unwrap().yellow());
+                        let result = self.clone_result();
+                        self.one_line.extend(result);
+                        if self.creates_thread() {
+                            self.one_line.push(new_thread());
                         } else {
-                            // TODO! granular
-                            one_line_error(eph_return, &mut self.one_line, &self.errno);
+                            self.one_line.push(new_process());
                         }
+                    } else {
+                        // TODO! granular
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
                     }
                 }
-            }
+            },
             Sysno::execve => {
                 let program_name = self.pavfol(0);
                 let arguments = self.pavfol(1);
@@ -6264,10 +6345,420 @@ impl SyscallObject {
                 }
             }
 
+            Sysno::io_uring_setup => match self.state {
+                Entering => {
+                    self.one_line
+                        .push("create an io_uring instance with ".white());
+                    self.one_line.push(self.args[0].to_string().yellow());
+                    self.one_line.push(" submission queue entries".white());
+                    let directives = self.io_uring_setup_flags();
+                    directives_handler(directives, &mut self.one_line);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.io_uring_setup_result();
+                        self.one_line.extend(result);
+                    } else {
+                        // TODO! granular
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::io_uring_enter => {
+                let io_uring = self.pavfol(0);
+                match self.state {
+                    Entering => {
+                        if self.args[1] > 0 {
+                            self.one_line.push("submit ".white());
+                            self.one_line.push(self.args[1].to_string().yellow());
+                            self.one_line.push(" operations to the io_uring: ".white());
+                            self.one_line.push(io_uring.yellow());
+                            if self.io_uring_waits() {
+                                self.one_line.push(" and wait for ".white());
+                                self.one_line.push(self.args[2].to_string().yellow());
+                                self.one_line.push(" completions".white());
+                            }
+                        } else {
+                            self.one_line.push("wait for ".white());
+                            self.one_line.push(self.args[2].to_string().yellow());
+                            self.one_line.push(" completions on the io_uring: ".white());
+                            self.one_line.push(io_uring.yellow());
+                        }
+                    }
+                    Exiting => {
+                        let eph_return = self.get_syscall_return();
+                        if eph_return.is_ok() {
+                            self.one_line.push(" |=> ".white());
+                            let submitted = self.io_uring_submissions();
+                            if submitted.is_empty() {
+                                self.one_line.push(eph_return.unwrap().yellow());
+                                self.one_line.push(" operations submitted".green());
+                            } else {
+                                self.one_line.push("submitted: ".green());
+                                self.one_line.extend(submitted);
+                            }
+                            let completions = self.io_uring_completions();
+                            if !completions.is_empty() {
+                                self.one_line.push("; completed: ".green());
+                                self.one_line.extend(completions);
+                            }
+                        } else {
+                            // TODO! granular
+                            one_line_error(eph_return, &mut self.one_line, &self.errno);
+                        }
+                    }
+                }
+            }
+            Sysno::io_uring_register => {
+                let io_uring = self.pavfol(0);
+                let opcode = io_uring_register_opcode(self.args[1]);
+                match self.state {
+                    Entering => match opcode {
+                        Some((name, intent)) => {
+                            self.one_line.push(intent.white());
+                            self.one_line.push(" the io_uring: ".white());
+                            self.one_line.push(io_uring.yellow());
+                            if self.args[3] > 0 {
+                                self.one_line.push(" (".white());
+                                self.one_line.push(self.args[3].to_string().yellow());
+                                self.one_line.push(" entries, ".white());
+                                self.one_line.push(name.yellow());
+                                self.one_line.push(")".white());
+                            } else {
+                                self.one_line.push(" (".white());
+                                self.one_line.push(name.yellow());
+                                self.one_line.push(")".white());
+                            }
+                        }
+                        None => {
+                            self.one_line
+                                .push("perform registration operation ".white());
+                            self.one_line.push(format!("#{}", self.args[1]).yellow());
+                            self.one_line.push(" on the io_uring: ".white());
+                            self.one_line.push(io_uring.yellow());
+                        }
+                    },
+                    Exiting => {
+                        let eph_return = self.get_syscall_return();
+                        if eph_return.is_ok() {
+                            self.one_line.push(" |=> ".white());
+                            self.one_line.push("operation successful".green());
+                        } else {
+                            // TODO! granular
+                            one_line_error(eph_return, &mut self.one_line, &self.errno);
+                        }
+                    }
+                }
+            }
+            Sysno::mount
+            | Sysno::umount2
+            | Sysno::pivot_root
+            | Sysno::chroot
+            | Sysno::open_tree
+            | Sysno::move_mount
+            | Sysno::fsopen
+            | Sysno::fsconfig
+            | Sysno::fsmount
+            | Sysno::fspick => match self.state {
+                Entering => {
+                    let intent = self.mount_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.mount_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::perf_event_open => match self.state {
+                Entering => {
+                    let intent = self.perf_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.perf_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::add_key | Sysno::request_key | Sysno::keyctl => match self.state {
+                Entering => {
+                    let intent = self.keyring_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.keyring_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::inotify_init
+            | Sysno::inotify_init1
+            | Sysno::inotify_add_watch
+            | Sysno::inotify_rm_watch
+            | Sysno::fanotify_init
+            | Sysno::fanotify_mark => match self.state {
+                Entering => {
+                    let intent = self.watch_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.watch_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::timer_create
+            | Sysno::timer_settime
+            | Sysno::timer_gettime
+            | Sysno::timer_getoverrun
+            | Sysno::timer_delete
+            | Sysno::timerfd_create
+            | Sysno::timerfd_settime
+            | Sysno::timerfd_gettime
+            | Sysno::clock_gettime
+            | Sysno::clock_getres
+            | Sysno::clock_settime
+            | Sysno::clock_nanosleep
+            | Sysno::setitimer
+            | Sysno::getitimer
+            | Sysno::alarm => match self.state {
+                Entering => {
+                    let intent = self.timer_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.timer_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::shmget
+            | Sysno::shmat
+            | Sysno::shmdt
+            | Sysno::shmctl
+            | Sysno::semget
+            | Sysno::semop
+            | Sysno::semtimedop
+            | Sysno::semctl
+            | Sysno::msgget
+            | Sysno::msgsnd
+            | Sysno::msgrcv
+            | Sysno::msgctl
+            | Sysno::mq_open
+            | Sysno::mq_unlink
+            | Sysno::mq_timedsend
+            | Sysno::mq_timedreceive
+            | Sysno::mq_notify
+            | Sysno::mq_getsetattr => match self.state {
+                Entering => {
+                    let intent = self.ipc_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.ipc_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::setxattr
+            | Sysno::lsetxattr
+            | Sysno::fsetxattr
+            | Sysno::getxattr
+            | Sysno::lgetxattr
+            | Sysno::fgetxattr
+            | Sysno::listxattr
+            | Sysno::llistxattr
+            | Sysno::flistxattr
+            | Sysno::removexattr
+            | Sysno::lremovexattr
+            | Sysno::fremovexattr => match self.state {
+                Entering => {
+                    let intent = self.xattr_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.xattr_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::setuid
+            | Sysno::setgid
+            | Sysno::setreuid
+            | Sysno::setregid
+            | Sysno::setresuid
+            | Sysno::setresgid
+            | Sysno::setfsuid
+            | Sysno::setfsgid
+            | Sysno::setgroups
+            | Sysno::capget
+            | Sysno::capset => match self.state {
+                Entering => {
+                    let intent = self.credentials_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.credentials_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::unshare => match self.state {
+                Entering => {
+                    let intent = self.unshare_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        self.one_line.push("successful".green());
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::setns => match self.state {
+                Entering => {
+                    let intent = self.setns_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        self.one_line.push("successful".green());
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::seccomp => match self.state {
+                Entering => {
+                    let intent = self.seccomp_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.seccomp_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::prctl => match self.state {
+                Entering => {
+                    let intent = self.prctl_intent();
+                    self.one_line.extend(intent);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.prctl_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                    }
+                }
+            },
+            Sysno::bpf => match self.state {
+                Entering => {
+                    let intent = self.bpf_intent();
+                    self.one_line.extend(intent);
+                    let disassembly = self.bpf_disassembly();
+                    self.one_line.extend(disassembly);
+                }
+                Exiting => {
+                    let eph_return = self.get_syscall_return();
+                    if eph_return.is_ok() {
+                        self.one_line.push(" |=> ".white());
+                        let result = self.bpf_result();
+                        self.one_line.extend(result);
+                    } else {
+                        one_line_error(eph_return, &mut self.one_line, &self.errno);
+                        let verifier_log = self.bpf_verifier_log();
+                        self.one_line.extend(verifier_log);
+                    }
+                }
+            },
             _ => {
                 return Err(());
             }
         }
+        if self.state == Exiting && self.sysno == Sysno::mmap && self.errno.is_none() {
+            let mapping = self.io_uring_mapping();
+            self.one_line.extend(mapping);
+        }
+        if self.state == Exiting && self.sysno == Sysno::read && self.errno.is_none() {
+            let events = self.watch_events();
+            self.one_line.extend(events);
+        }
+        if self.state == Exiting && self.errno.is_none() {
+            let segments = self.iovec_segments();
+            self.one_line.extend(segments);
+        }
+        if self.state == Exiting && self.errno.is_none() {
+            let dump = self.data_dump();
+            self.one_line.extend(dump);
+        }
+        if self.state == Exiting && self.errno.is_none() {
+            let warnings = self.epoll_warnings();
+            self.one_line.extend(warnings);
+        }
+        if self.state == Exiting && self.errno.is_none() {
+            let transitions = self.namespace_transitions();
+            self.one_line.extend(transitions);
+        }
+        if self.state == Exiting && self.errno.is_some() {
+            self.attach_error_explanation();
+        }
         Ok(())
     }
 }
@@ -6537,6 +7028,139 @@ pub fn anding_handler(vector: Vec<ColoredString>, one_line: &mut Vec<ColoredStri
     one_line.extend(ender);
 }
 
+pub fn open_flags_directives(flags_num: i32) -> Vec<ColoredString> {
+    let mut directives = vec![];
+    if (flags_num & O_APPEND) == O_APPEND {
+        directives.push("open the file in append mode".yellow());
+    }
+    if (flags_num & O_ASYNC) == O_ASYNC {
+        directives.push("enable signal-driven I/O".yellow());
+    }
+    if (flags_num & O_CLOEXEC) == O_CLOEXEC {
+        directives.push("close the file descriptor on the next exec syscall".yellow());
+    }
+    if (flags_num & O_CREAT) > 0 {
+        directives.push("create the file if it does not exist".yellow());
+    }
+    if (flags_num & O_DIRECT) > 0 {
+        directives.push("use direct file I/O".yellow());
+    }
+    if (flags_num & O_DIRECTORY) > 0 {
+        directives.push("fail if the path is not a directory".yellow());
+    }
+    if (flags_num & O_DSYNC) > 0 {
+        directives
+            .push("ensure writes are completely teransferred to hardware before return".yellow());
+    }
+    if (flags_num & O_EXCL) > 0 {
+        directives
+            .push("ensure O_CREAT fails if the file already exists or is a symbolic link".yellow());
+    }
+    if (flags_num & O_LARGEFILE) > 0 {
+        directives.push("allow files larger than `off_t` and up to `off64_t`".yellow());
+    }
+    if (flags_num & O_NOATIME) > 0 {
+        directives.push("do not update the file last access time on read".yellow());
+    }
+    if (flags_num & O_NOCTTY) > 0 {
+        directives.push(
+            "do not use the file as the process's controlling terminal if its a terminal device"
+                .yellow(),
+        );
+    }
+    if (flags_num & O_NOFOLLOW) > 0 {
+        // TODO! change this to have better wording, change `base`
+        directives.push("fail if the base of the file is a symbolic link".yellow());
+    }
+    if (flags_num & O_NONBLOCK) > 0 || (flags_num & O_NDELAY) > 0 {
+        // TODO! change this to have better wording, change `base`
+        directives.push("open the file in non-blocking mode".yellow());
+    }
+    if (flags_num & O_PATH) > 0 {
+        // TODO! change this to have better wording, change `base`
+        directives.push("return a `shallow` file descriptor".yellow());
+    }
+    if (flags_num & O_SYNC) > 0 {
+        directives
+            .push("ensure writes are completely teransferred to hardware before return".yellow());
+    }
+    directives
+}
+
+fn lock_type_name(lock_type: i16) -> &'static str {
+    match lock_type as i32 {
+        F_RDLCK => "read lock",
+        F_WRLCK => "write lock",
+        _ => "lock",
+    }
+}
+
+// the byte range covered by a struct flock
+fn lock_range(lock: &flock) -> Vec<ColoredString> {
+    let anchor = match lock.l_whence as i32 {
+        SEEK_CUR => " (relative to the current file offset)",
+        SEEK_END => " (relative to the end of the file)",
+        _ => "",
+    };
+    let mut range = vec![];
+    if lock.l_len == 0 {
+        if lock.l_start == 0 && anchor.is_empty() {
+            range.push("all bytes".yellow());
+            return range;
+        }
+        range.push("bytes from ".white());
+        range.push(lock.l_start.to_string().yellow());
+        range.push(" onwards".white());
+    } else {
+        // the values come from the tracee, a range past the limits of off_t is shown as given
+        let bounds = if lock.l_len > 0 {
+            lock.l_start
+                .checked_add(lock.l_len - 1)
+                .map(|end| (lock.l_start, end))
+        } else {
+            lock.l_start
+                .checked_add(lock.l_len)
+                .zip(lock.l_start.checked_sub(1))
+        };
+        match bounds {
+            Some((start, end)) => {
+                range.push("bytes ".white());
+                range.push(start.to_string().yellow());
+                range.push(" to ".white());
+                range.push(end.to_string().yellow());
+            }
+            None => {
+                range.push("bytes from ".white());
+                range.push(lock.l_start.to_string().yellow());
+                range.push(" with a length of ".white());
+                range.push(lock.l_len.to_string().yellow());
+            }
+        }
+    }
+    range.push(anchor.white());
+    range
+}
+
+fn seals_names(seals: i32) -> Vec<ColoredString> {
+    let mut names = vec![];
+    if (seals & F_SEAL_SEAL) == F_SEAL_SEAL {
+        names.push("further sealing".yellow());
+    }
+    if (seals & F_SEAL_SHRINK) == F_SEAL_SHRINK {
+        names.push("shrinking".yellow());
+    }
+    if (seals & F_SEAL_GROW) == F_SEAL_GROW {
+        names.push("growing".yellow());
+    }
+    if (seals & F_SEAL_WRITE) == F_SEAL_WRITE {
+        names.push("writing".yellow());
+    }
+    if (seals & F_SEAL_FUTURE_WRITE) == F_SEAL_FUTURE_WRITE {
+        names.push("future writing".yellow());
+    }
+    names
+}
+
 pub fn directives_handler(vector: Vec<ColoredString>, one_line: &mut Vec<ColoredString>) {
     if !vector.is_empty() {
         one_line.push(" (".white());
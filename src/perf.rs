@@ -0,0 +1,601 @@
+// decoding of perf_event_open, the perf_event_attr and the pid, cpu, group_fd and flags around it
+// failures are explained against perf_event_paranoid, the capabilities and the attr itself
+use crate::syscall_object::SyscallObject;
+use colored::{ColoredString, Colorize};
+use nix::errno::Errno;
+
+const PERF_TYPE_HARDWARE: u32 = 0;
+const PERF_TYPE_SOFTWARE: u32 = 1;
+const PERF_TYPE_TRACEPOINT: u32 = 2;
+const PERF_TYPE_HW_CACHE: u32 = 3;
+const PERF_TYPE_RAW: u32 = 4;
+const PERF_TYPE_BREAKPOINT: u32 = 5;
+
+const HARDWARE_EVENTS: [&str; 10] = [
+    "CPU cycles",
+    "instructions",
+    "cache references",
+    "cache misses",
+    "branch instructions",
+    "branch misses",
+    "bus cycles",
+    "frontend stalled cycles",
+    "backend stalled cycles",
+    "reference CPU cycles",
+];
+
+const SOFTWARE_EVENTS: [&str; 12] = [
+    "CPU clock time",
+    "task clock time",
+    "page faults",
+    "context switches",
+    "CPU migrations",
+    "minor page faults",
+    "major page faults",
+    "alignment faults",
+    "emulation faults",
+    "nothing (dummy event)",
+    "BPF output",
+    "cgroup switches",
+];
+
+const CACHES: [&str; 7] = [
+    "L1 data cache",
+    "L1 instruction cache",
+    "last level cache",
+    "data TLB",
+    "instruction TLB",
+    "branch prediction unit",
+    "local memory node",
+];
+const CACHE_OPERATIONS: [&str; 3] = ["read", "write", "prefetch"];
+const CACHE_RESULTS: [&str; 2] = ["accesses", "misses"];
+
+const SAMPLE_TYPES: [&str; 25] = [
+    "ip",
+    "tid",
+    "time",
+    "addr",
+    "read",
+    "callchain",
+    "id",
+    "cpu",
+    "period",
+    "stream_id",
+    "raw",
+    "branch_stack",
+    "regs_user",
+    "stack_user",
+    "weight",
+    "data_src",
+    "identifier",
+    "transaction",
+    "regs_intr",
+    "phys_addr",
+    "aux",
+    "cgroup",
+    "data_page_size",
+    "code_page_size",
+    "weight_struct",
+];
+
+const READ_FORMATS: [&str; 5] = [
+    "total_time_enabled",
+    "total_time_running",
+    "id",
+    "group",
+    "lost",
+];
+
+// the single bit fields of the bitfield word, precise_ip (bits 15 and 16) is read on its own
+const ATTR_FLAGS: [(u32, &str); 36] = [
+    (0, "disabled"),
+    (1, "inherit"),
+    (2, "pinned"),
+    (3, "exclusive"),
+    (4, "exclude_user"),
+    (5, "exclude_kernel"),
+    (6, "exclude_hv"),
+    (7, "exclude_idle"),
+    (8, "mmap"),
+    (9, "comm"),
+    (10, "freq"),
+    (11, "inherit_stat"),
+    (12, "enable_on_exec"),
+    (13, "task"),
+    (14, "watermark"),
+    (17, "mmap_data"),
+    (18, "sample_id_all"),
+    (19, "exclude_host"),
+    (20, "exclude_guest"),
+    (21, "exclude_callchain_kernel"),
+    (22, "exclude_callchain_user"),
+    (23, "mmap2"),
+    (24, "comm_exec"),
+    (25, "use_clockid"),
+    (26, "context_switch"),
+    (27, "write_backward"),
+    (28, "namespaces"),
+    (29, "ksymbol"),
+    (30, "bpf_event"),
+    (31, "aux_output"),
+    (32, "cgroup"),
+    (33, "text_poke"),
+    (34, "build_id"),
+    (35, "inherit_thread"),
+    (36, "remove_on_exec"),
+    (37, "sigtrap"),
+];
+
+const DISABLED: u64 = 1 << 0;
+const INHERIT: u64 = 1 << 1;
+const PINNED: u64 = 1 << 2;
+const EXCLUDE_USER: u64 = 1 << 4;
+const EXCLUDE_KERNEL: u64 = 1 << 5;
+const FREQ: u64 = 1 << 10;
+const ENABLE_ON_EXEC: u64 = 1 << 12;
+const PRECISE_IP_SHIFT: u64 = 15;
+
+const PERF_FLAG_FD_NO_GROUP: u64 = 1 << 0;
+const PERF_FLAG_FD_OUTPUT: u64 = 1 << 1;
+const PERF_FLAG_PID_CGROUP: u64 = 1 << 2;
+const PERF_FLAG_FD_CLOEXEC: u64 = 1 << 3;
+
+const HW_BREAKPOINT_R: u32 = 1;
+const HW_BREAKPOINT_W: u32 = 2;
+const HW_BREAKPOINT_X: u32 = 4;
+
+// the size of the first published perf_event_attr, and of the newest one decoded here
+const PERF_ATTR_SIZE_VER0: usize = 64;
+const PERF_ATTR_SIZE_DECODED: usize = 136;
+
+// hybrid CPUs put the PMU type in the upper half of hardware and cache configs
+const PERF_PMU_TYPE_SHIFT: u64 = 32;
+
+const CAP_SYS_ADMIN: u64 = 21;
+const CAP_PERFMON: u64 = 38;
+
+struct PerfAttr {
+    kind: u32,
+    size: u32,
+    config: u64,
+    sample_period: u64,
+    sample_type: u64,
+    read_format: u64,
+    flags: u64,
+    bp_type: u32,
+    bp_addr: u64,
+    bp_len: u64,
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    bytes
+        .get(offset..offset + 4)
+        .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+        .unwrap_or(0)
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    bytes
+        .get(offset..offset + 8)
+        .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+        .unwrap_or(0)
+}
+
+fn bit_names(value: u64, names: &[&str]) -> Vec<String> {
+    let mut set = names
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| (value & (1 << bit)) != 0)
+        .map(|(_, name)| name.to_string())
+        .collect::<Vec<String>>();
+    let unknown = value & !((1u64 << names.len()) - 1);
+    if unknown != 0 {
+        set.push(format!("{unknown:#x}"));
+    }
+    set
+}
+
+fn read_number<T: std::str::FromStr>(path: &str) -> Option<T> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+// the name of a dynamic PMU, like kprobe, uprobe or intel_pt
+fn pmu_name(kind: u32) -> Option<String> {
+    std::fs::read_dir("/sys/bus/event_source/devices")
+        .ok()?
+        .flatten()
+        .find(|device| {
+            read_number::<u32>(&device.path().join("type").to_string_lossy()) == Some(kind)
+        })
+        .map(|device| device.file_name().to_string_lossy().into_owned())
+}
+
+// tracepoint ids are listed under the tracefs events directory as <system>/<event>/id
+fn tracepoint_name(id: u64) -> Option<String> {
+    [
+        "/sys/kernel/tracing/events",
+        "/sys/kernel/debug/tracing/events",
+    ]
+    .iter()
+    .find_map(|events| {
+        std::fs::read_dir(events)
+            .ok()?
+            .flatten()
+            .filter(|system| system.path().is_dir())
+            .find_map(|system| {
+                std::fs::read_dir(system.path())
+                    .ok()?
+                    .flatten()
+                    .find(|event| {
+                        read_number::<u64>(&event.path().join("id").to_string_lossy()) == Some(id)
+                    })
+                    .map(|event| {
+                        format!(
+                            "{}:{}",
+                            system.file_name().to_string_lossy(),
+                            event.file_name().to_string_lossy()
+                        )
+                    })
+            })
+    })
+}
+
+fn possible_cpus() -> Option<u64> {
+    let possible = std::fs::read_to_string("/sys/devices/system/cpu/possible").ok()?;
+    let last = possible.trim().rsplit(['-', ',']).next()?;
+    Some(last.parse::<u64>().ok()? + 1)
+}
+
+fn has_capability(pid: i32, capability: u64) -> bool {
+    procfs::process::Process::new(pid)
+        .and_then(|process| process.status())
+        .map(|status| (status.capeff & (1 << capability)) != 0)
+        .unwrap_or(false)
+}
+
+fn breakpoint_access(bp_type: u32) -> String {
+    let mut access = vec![];
+    if (bp_type & HW_BREAKPOINT_R) != 0 {
+        access.push("reads of");
+    }
+    if (bp_type & HW_BREAKPOINT_W) != 0 {
+        access.push("writes to");
+    }
+    if (bp_type & HW_BREAKPOINT_X) != 0 {
+        access.push("execution of");
+    }
+    if access.is_empty() {
+        "no access to".to_owned()
+    } else {
+        access.join(" and ")
+    }
+}
+
+impl PerfAttr {
+    // what is counted, in the words of the sentence "count <event>"
+    fn event(&self) -> String {
+        let pmu_type = self.config >> PERF_PMU_TYPE_SHIFT;
+        let config = self.config & 0xFFFF_FFFF;
+        let on_pmu = |event: String| match pmu_type {
+            0 => event,
+            pmu_type => match pmu_name(pmu_type as u32) {
+                Some(pmu) => format!("{event} of the {pmu} PMU"),
+                None => format!("{event} of PMU {pmu_type}"),
+            },
+        };
+        match self.kind {
+            PERF_TYPE_HARDWARE => on_pmu(match HARDWARE_EVENTS.get(config as usize) {
+                Some(event) => event.to_string(),
+                None => format!("the unknown hardware event {config}"),
+            }),
+            PERF_TYPE_SOFTWARE => match SOFTWARE_EVENTS.get(self.config as usize) {
+                Some(event) => event.to_string(),
+                None => format!("the unknown software event {}", self.config),
+            },
+            PERF_TYPE_TRACEPOINT => match tracepoint_name(self.config) {
+                Some(tracepoint) => format!("hits of the {tracepoint} tracepoint"),
+                None => format!("hits of tracepoint {}", self.config),
+            },
+            PERF_TYPE_HW_CACHE => {
+                let cache = CACHES.get((config & 0xFF) as usize);
+                let operation = CACHE_OPERATIONS.get(((config >> 8) & 0xFF) as usize);
+                let result = CACHE_RESULTS.get(((config >> 16) & 0xFF) as usize);
+                on_pmu(match (cache, operation, result) {
+                    (Some(cache), Some(operation), Some(result)) => {
+                        format!("{cache} {operation} {result}")
+                    }
+                    _ => format!("the unknown cache event {config:#x}"),
+                })
+            }
+            PERF_TYPE_RAW => format!("the raw hardware event {:#x}", self.config),
+            PERF_TYPE_BREAKPOINT => format!(
+                "{} the {} bytes at {:#x} (hardware breakpoint)",
+                breakpoint_access(self.bp_type),
+                self.bp_len,
+                self.bp_addr
+            ),
+            kind => match pmu_name(kind) {
+                Some(pmu) => format!("event {:#x} of the {pmu} PMU", self.config),
+                None => format!("event {:#x} of the unknown PMU {kind}", self.config),
+            },
+        }
+    }
+
+    fn flag_names(&self) -> Vec<String> {
+        let mut flags = ATTR_FLAGS
+            .iter()
+            .filter(|(bit, _)| (self.flags & (1 << bit)) != 0)
+            .map(|(_, name)| name.to_string())
+            .collect::<Vec<String>>();
+        let precise_ip = (self.flags >> PRECISE_IP_SHIFT) & 0b11;
+        if precise_ip != 0 {
+            flags.push(format!("precise_ip={precise_ip}"));
+        }
+        flags
+    }
+}
+
+impl SyscallObject {
+    fn perf_attr(&self) -> Option<PerfAttr> {
+        let size = SyscallObject::read_bytes_specific_length(self.args[0] as usize, self.child, 8)
+            .map(|bytes| u32_at(&bytes, 4))?;
+        // a size of 0 means the first published layout
+        let readable = match size as usize {
+            0 => PERF_ATTR_SIZE_VER0,
+            size => size.min(PERF_ATTR_SIZE_DECODED),
+        };
+        let bytes =
+            SyscallObject::read_bytes_specific_length(self.args[0] as usize, self.child, readable)?;
+        Some(PerfAttr {
+            kind: u32_at(&bytes, 0),
+            size,
+            config: u64_at(&bytes, 8),
+            sample_period: u64_at(&bytes, 16),
+            sample_type: u64_at(&bytes, 24),
+            read_format: u64_at(&bytes, 32),
+            flags: u64_at(&bytes, 40),
+            bp_type: u32_at(&bytes, 52),
+            bp_addr: u64_at(&bytes, 56),
+            bp_len: u64_at(&bytes, 64),
+        })
+    }
+
+    // "for this thread on any CPU", "for every process on CPU 2", ...
+    fn perf_target(&self) -> Vec<ColoredString> {
+        let pid = self.args[1] as i32;
+        let cpu = self.args[2] as i32;
+        let mut target = vec![" for ".white()];
+        if (self.args[4] & PERF_FLAG_PID_CGROUP) != 0 {
+            target.push("the cgroup of ".white());
+            target.push(
+                SyscallObject::style_file_descriptor(self.args[1], self.child)
+                    .unwrap_or_else(|| pid.to_string())
+                    .yellow(),
+            );
+        } else {
+            target.push(match pid {
+                -1 => "every process".yellow(),
+                0 => "this thread".yellow(),
+                pid => format!("thread {pid}").yellow(),
+            });
+        }
+        if cpu == -1 {
+            target.push(" on any CPU".white());
+        } else {
+            target.push(" on CPU ".white());
+            target.push(cpu.to_string().yellow());
+        }
+        target
+    }
+
+    pub(crate) fn perf_intent(&self) -> Vec<ColoredString> {
+        let Some(attr) = self.perf_attr() else {
+            let mut intent = vec!["open a performance monitoring event".white()];
+            intent.extend(self.perf_target());
+            return intent;
+        };
+        let mut intent = vec![];
+        if attr.sample_period == 0 {
+            intent.push("count ".white());
+            intent.push(attr.event().yellow());
+        } else {
+            intent.push("sample ".white());
+            intent.push(attr.event().yellow());
+            if (attr.flags & FREQ) != 0 {
+                intent.push(" ".white());
+                intent.push(attr.sample_period.to_string().yellow());
+                intent.push(" times a second".white());
+            } else {
+                intent.push(" every ".white());
+                intent.push(attr.sample_period.to_string().yellow());
+                intent.push(" events".white());
+            }
+        }
+        intent.extend(self.perf_target());
+        if (attr.flags & (EXCLUDE_USER | EXCLUDE_KERNEL)) == EXCLUDE_KERNEL {
+            intent.push(", in user space only".white());
+        } else if (attr.flags & (EXCLUDE_USER | EXCLUDE_KERNEL)) == EXCLUDE_USER {
+            intent.push(", in the kernel only".white());
+        }
+        if (attr.flags & INHERIT) != 0 {
+            intent.push(", including threads and children created later".white());
+        }
+        if (attr.flags & ENABLE_ON_EXEC) != 0 {
+            intent.push(", starting at the next execve".white());
+        } else if (attr.flags & DISABLED) != 0 {
+            intent.push(", starting disabled".white());
+        }
+        if (attr.flags & PINNED) != 0 {
+            intent.push(", pinned to the counters".white());
+        }
+        let group_fd = self.args[3] as i32;
+        if group_fd != -1 {
+            if (self.args[4] & PERF_FLAG_FD_OUTPUT) != 0 {
+                intent.push(", writing its samples into the buffer of ".white());
+            } else {
+                intent.push(", in the group led by ".white());
+            }
+            intent.push(
+                SyscallObject::style_file_descriptor(self.args[3], self.child)
+                    .unwrap_or_else(|| group_fd.to_string())
+                    .yellow(),
+            );
+        }
+        if attr.sample_period != 0 && attr.sample_type != 0 {
+            intent.push("\n\trecording: ".white());
+            intent.push(
+                bit_names(attr.sample_type, &SAMPLE_TYPES)
+                    .join(", ")
+                    .yellow(),
+            );
+        }
+        if attr.read_format != 0 {
+            intent.push("\n\treading: ".white());
+            intent.push(
+                bit_names(attr.read_format, &READ_FORMATS)
+                    .join(", ")
+                    .yellow(),
+            );
+        }
+        let flags = attr.flag_names();
+        if !flags.is_empty() {
+            intent.push("\n\tattr flags: ".white());
+            intent.push(flags.join(", ").yellow());
+        }
+        let mut open_flags = vec![];
+        if (self.args[4] & PERF_FLAG_FD_NO_GROUP) != 0 {
+            open_flags.push("PERF_FLAG_FD_NO_GROUP");
+        }
+        if (self.args[4] & PERF_FLAG_FD_CLOEXEC) != 0 {
+            open_flags.push("PERF_FLAG_FD_CLOEXEC");
+        }
+        if !open_flags.is_empty() {
+            intent.push("\n\tflags: ".white());
+            intent.push(open_flags.join(", ").yellow());
+        }
+        intent
+    }
+
+    pub(crate) fn perf_result(&self) -> Vec<ColoredString> {
+        vec![
+            "event opened as fd ".green(),
+            self.result.0.unwrap_or(0).to_string().yellow(),
+        ]
+    }
+
+    // perf_event_paranoid: 2 forbids kernel events, 1 also CPU wide events, 0 also raw tracepoint data
+    // and anything above 2 forbids perf_event_open altogether, CAP_PERFMON (or CAP_SYS_ADMIN) bypasses them
+    pub(crate) fn perf_denial(&self) -> Option<Vec<ColoredString>> {
+        if self.sysno != syscalls::Sysno::perf_event_open {
+            return None;
+        }
+        let attr = self.perf_attr()?;
+        let pid = self.args[1] as i32;
+        let cpu = self.args[2] as i32;
+        let explain = |text: String| Some(vec![" (".white(), text.yellow(), ")".white()]);
+        match self.errno? {
+            Errno::EACCES | Errno::EPERM => {
+                let privileged = has_capability(self.child.as_raw(), CAP_PERFMON)
+                    || has_capability(self.child.as_raw(), CAP_SYS_ADMIN);
+                let paranoid = read_number::<i32>("/proc/sys/kernel/perf_event_paranoid")?;
+                if !privileged {
+                    if paranoid > 2 {
+                        return explain(format!(
+                            "perf_event_paranoid is {paranoid}, which forbids perf_event_open to processes without CAP_PERFMON"
+                        ));
+                    }
+                    if pid == -1 && paranoid >= 1 {
+                        return explain(format!(
+                            "watching every process on a CPU needs CAP_PERFMON or perf_event_paranoid below 1, it is {paranoid}"
+                        ));
+                    }
+                    if (attr.flags & EXCLUDE_KERNEL) == 0 && paranoid >= 2 {
+                        return explain(format!(
+                            "counting kernel events needs CAP_PERFMON or perf_event_paranoid below 2, it is {paranoid}, set exclude_kernel to count user space only"
+                        ));
+                    }
+                }
+                if pid > 0 && pid != self.child.as_raw() {
+                    return explain(format!(
+                        "watching process {pid} needs the same ptrace access to it as attaching a debugger"
+                    ));
+                }
+                None
+            }
+            Errno::EINVAL => {
+                if pid == -1 && cpu == -1 {
+                    return explain("pid and cpu cannot both be -1".to_owned());
+                }
+                if (self.args[4] & PERF_FLAG_PID_CGROUP) != 0 && cpu == -1 {
+                    return explain("cgroup events need a specific cpu".to_owned());
+                }
+                if let Some(cpus) = possible_cpus() {
+                    if cpu >= 0 && cpu as u64 >= cpus {
+                        return explain(format!(
+                            "cpu {cpu} does not exist, the possible CPUs are 0 to {}",
+                            cpus - 1
+                        ));
+                    }
+                }
+                if (self.args[4]
+                    & !(PERF_FLAG_FD_NO_GROUP
+                        | PERF_FLAG_FD_OUTPUT
+                        | PERF_FLAG_PID_CGROUP
+                        | PERF_FLAG_FD_CLOEXEC))
+                    != 0
+                {
+                    return explain(format!("unknown flags {:#x}", self.args[4]));
+                }
+                if attr.size != 0 && (attr.size as usize) < PERF_ATTR_SIZE_VER0 {
+                    return explain(format!(
+                        "attr.size is {}, smaller than the first published perf_event_attr of {PERF_ATTR_SIZE_VER0} bytes",
+                        attr.size
+                    ));
+                }
+                if (attr.flags & FREQ) != 0 {
+                    let max = read_number::<u64>("/proc/sys/kernel/perf_event_max_sample_rate")?;
+                    if attr.sample_period > max {
+                        return explain(format!(
+                            "a sample frequency of {} is above perf_event_max_sample_rate, {max}",
+                            attr.sample_period
+                        ));
+                    }
+                }
+                if ((attr.flags >> PRECISE_IP_SHIFT) & 0b11) != 0 && attr.kind != PERF_TYPE_HARDWARE
+                {
+                    return explain("precise_ip only applies to hardware events".to_owned());
+                }
+                if (attr.kind == PERF_TYPE_HARDWARE
+                    && (attr.config & 0xFFFF_FFFF) as usize >= HARDWARE_EVENTS.len())
+                    || (attr.kind == PERF_TYPE_SOFTWARE
+                        && attr.config as usize >= SOFTWARE_EVENTS.len())
+                {
+                    return explain(format!("config {} is not a known event", attr.config));
+                }
+                None
+            }
+            Errno::ENOENT
+                if attr.kind == PERF_TYPE_SOFTWARE
+                    && attr.config as usize >= SOFTWARE_EVENTS.len() =>
+            {
+                explain(format!(
+                    "config {} is not a software event this kernel knows",
+                    attr.config
+                ))
+            }
+            Errno::ENOENT | Errno::EOPNOTSUPP | Errno::ENODEV => explain(
+                "no PMU on this machine provides this event, virtual machines often expose no hardware counters"
+                    .to_owned(),
+            ),
+            Errno::E2BIG => explain(format!(
+                "attr.size is {}, this kernel expects at most its own perf_event_attr size and zeroes beyond it",
+                attr.size
+            )),
+            Errno::EMFILE => explain(
+                "the process has no file descriptors left, each event takes one".to_owned(),
+            ),
+            _ => None,
+        }
+    }
+}
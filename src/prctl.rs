@@ -0,0 +1,557 @@
+// decoding of the prctl operations, the seccomp ones are described by the seccomp module
+use crate::{
+    syscall_object::SyscallObject,
+    utilities::{capability_to_string, x86_signal_to_string},
+};
+use colored::{ColoredString, Colorize};
+use nix::unistd::Pid;
+
+const PR_SET_PDEATHSIG: u64 = 1;
+const PR_GET_PDEATHSIG: u64 = 2;
+const PR_GET_DUMPABLE: u64 = 3;
+const PR_SET_DUMPABLE: u64 = 4;
+const PR_GET_KEEPCAPS: u64 = 7;
+const PR_SET_KEEPCAPS: u64 = 8;
+const PR_GET_TIMING: u64 = 13;
+const PR_SET_TIMING: u64 = 14;
+const PR_SET_NAME: u64 = 15;
+const PR_GET_NAME: u64 = 16;
+const PR_GET_SECCOMP: u64 = 21;
+const PR_SET_SECCOMP: u64 = 22;
+const PR_CAPBSET_READ: u64 = 23;
+const PR_CAPBSET_DROP: u64 = 24;
+const PR_GET_TSC: u64 = 25;
+const PR_SET_TSC: u64 = 26;
+const PR_GET_SECUREBITS: u64 = 27;
+const PR_SET_SECUREBITS: u64 = 28;
+const PR_SET_TIMERSLACK: u64 = 29;
+const PR_GET_TIMERSLACK: u64 = 30;
+const PR_TASK_PERF_EVENTS_DISABLE: u64 = 31;
+const PR_TASK_PERF_EVENTS_ENABLE: u64 = 32;
+const PR_MCE_KILL: u64 = 33;
+const PR_MCE_KILL_GET: u64 = 34;
+const PR_SET_MM: u64 = 35;
+const PR_SET_CHILD_SUBREAPER: u64 = 36;
+const PR_GET_CHILD_SUBREAPER: u64 = 37;
+const PR_SET_NO_NEW_PRIVS: u64 = 38;
+const PR_GET_NO_NEW_PRIVS: u64 = 39;
+const PR_GET_TID_ADDRESS: u64 = 40;
+const PR_SET_THP_DISABLE: u64 = 41;
+const PR_GET_THP_DISABLE: u64 = 42;
+const PR_CAP_AMBIENT: u64 = 47;
+const PR_GET_SPECULATION_CTRL: u64 = 52;
+const PR_SET_SPECULATION_CTRL: u64 = 53;
+const PR_SET_IO_FLUSHER: u64 = 57;
+const PR_GET_IO_FLUSHER: u64 = 58;
+const PR_SET_SYSCALL_USER_DISPATCH: u64 = 59;
+const PR_SCHED_CORE: u64 = 62;
+const PR_SET_MDWE: u64 = 65;
+const PR_GET_MDWE: u64 = 66;
+const PR_GET_AUXV: u64 = 0x4155_5856;
+const PR_SET_MEMORY_MERGE: u64 = 67;
+const PR_GET_MEMORY_MERGE: u64 = 68;
+const PR_SET_VMA: u64 = 0x5356_4d41;
+const PR_SET_PTRACER: u64 = 0x5961_6d61;
+
+const PR_SET_VMA_ANON_NAME: u64 = 0;
+
+const PR_CAP_AMBIENT_IS_SET: u64 = 1;
+const PR_CAP_AMBIENT_RAISE: u64 = 2;
+const PR_CAP_AMBIENT_LOWER: u64 = 3;
+const PR_CAP_AMBIENT_CLEAR_ALL: u64 = 4;
+
+// the PR_SET_MM_* fields, from 1 up
+const MM_FIELDS: [&str; 15] = [
+    "start of the code segment",
+    "end of the code segment",
+    "start of the data segment",
+    "end of the data segment",
+    "start of the stack",
+    "start of the heap",
+    "current end of the heap",
+    "start of the command line arguments",
+    "end of the command line arguments",
+    "start of the environment",
+    "end of the environment",
+    "auxiliary vector",
+    "executable file, as seen in /proc/self/exe",
+    "memory map fields all at once",
+    "size of the prctl_mm_map structure",
+];
+const PR_SET_MM_EXE_FILE: u64 = 13;
+const PR_SET_MM_MAP_SIZE: u64 = 15;
+
+const SECUREBITS: [&str; 12] = [
+    "SECBIT_NOROOT",
+    "SECBIT_NOROOT_LOCKED",
+    "SECBIT_NO_SETUID_FIXUP",
+    "SECBIT_NO_SETUID_FIXUP_LOCKED",
+    "SECBIT_KEEP_CAPS",
+    "SECBIT_KEEP_CAPS_LOCKED",
+    "SECBIT_NO_CAP_AMBIENT_RAISE",
+    "SECBIT_NO_CAP_AMBIENT_RAISE_LOCKED",
+    "SECBIT_EXEC_RESTRICT_FILE",
+    "SECBIT_EXEC_RESTRICT_FILE_LOCKED",
+    "SECBIT_EXEC_DENY_INTERACTIVE",
+    "SECBIT_EXEC_DENY_INTERACTIVE_LOCKED",
+];
+
+const SPECULATION_MISFEATURES: [&str; 3] = [
+    "speculative store bypass",
+    "indirect branch speculation",
+    "L1 data cache flushing",
+];
+const SPECULATION_CONTROLS: [(u64, &str); 5] = [
+    (1 << 0, "controllable per thread"),
+    (1 << 1, "mitigation disabled"),
+    (1 << 2, "mitigation enabled"),
+    (1 << 3, "mitigation enabled and locked"),
+    (1 << 4, "mitigation enabled until the next execve"),
+];
+
+const MDWE_REFUSE_EXEC_GAIN: u64 = 1;
+const MDWE_NO_INHERIT: u64 = 2;
+
+// the comm of a thread holds at most 15 bytes and the terminating NUL
+const TASK_COMM_LEN: usize = 16;
+
+fn read_text(address: u64, child: Pid, limit: usize) -> String {
+    if address == 0 {
+        return String::new();
+    }
+    SyscallObject::read_bytes_specific_length(address as usize, child, limit)
+        .map(|bytes| {
+            let text = bytes.split(|byte| *byte == 0).next().unwrap_or_default();
+            String::from_utf8_lossy(text).into_owned()
+        })
+        .unwrap_or_default()
+}
+
+fn read_int(address: u64, child: Pid) -> Option<i32> {
+    SyscallObject::read_bytes::<4>(address as usize, child).map(i32::from_ne_bytes)
+}
+
+fn signal_name(signal: u64) -> String {
+    x86_signal_to_string(signal)
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("signal {signal}"))
+}
+
+fn capability_name(capability: u64) -> String {
+    capability_to_string(capability)
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("capability {capability}"))
+}
+
+fn on_off(enabled: bool) -> ColoredString {
+    if enabled {
+        "enabled".yellow()
+    } else {
+        "disabled".yellow()
+    }
+}
+
+fn securebits(bits: u64) -> String {
+    let set = SECUREBITS
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| (bits & (1 << bit)) != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<&str>>();
+    if set.is_empty() {
+        "none".to_owned()
+    } else {
+        set.join(", ")
+    }
+}
+
+fn speculation_controls(controls: u64) -> String {
+    let set = SPECULATION_CONTROLS
+        .iter()
+        .filter(|(bit, _)| (controls & bit) != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<&str>>();
+    if set.is_empty() {
+        "not affected".to_owned()
+    } else {
+        set.join(", ")
+    }
+}
+
+impl SyscallObject {
+    pub(crate) fn prctl_intent(&self) -> Vec<ColoredString> {
+        let child = self.child;
+        let [operation, arg2, arg3, arg4, arg5, ..] = self.args[..] else {
+            return vec![];
+        };
+        match operation {
+            PR_SET_PDEATHSIG if arg2 == 0 => {
+                vec!["stop being signalled when the parent thread dies".white()]
+            }
+            PR_SET_PDEATHSIG => vec![
+                "receive ".white(),
+                signal_name(arg2).yellow(),
+                " when the parent thread dies".white(),
+            ],
+            PR_GET_PDEATHSIG => vec!["get the signal sent when the parent thread dies".white()],
+            PR_GET_DUMPABLE => {
+                vec!["check whether the process can dump core and be ptrace attached".white()]
+            }
+            PR_SET_DUMPABLE if arg2 == 0 => vec![
+                "stop the process from dumping core and from being ptrace attached by unprivileged processes"
+                    .white(),
+            ],
+            PR_SET_DUMPABLE => {
+                vec!["allow the process to dump core and to be ptrace attached".white()]
+            }
+            PR_GET_KEEPCAPS => {
+                vec!["check whether capabilities are kept when switching away from uid 0".white()]
+            }
+            PR_SET_KEEPCAPS if arg2 == 0 => {
+                vec!["clear the permitted capabilities when switching away from uid 0".white()]
+            }
+            PR_SET_KEEPCAPS => {
+                vec!["keep the permitted capabilities when switching away from uid 0".white()]
+            }
+            PR_GET_TIMING | PR_SET_TIMING => {
+                vec!["get or set the process timing method, statistical only".white()]
+            }
+            PR_SET_NAME => vec![
+                "name this thread ".white(),
+                format!("\"{}\"", read_text(arg2, child, TASK_COMM_LEN - 1)).yellow(),
+            ],
+            PR_GET_NAME => vec!["get the name of this thread".white()],
+            PR_GET_SECCOMP | PR_SET_SECCOMP | PR_SET_NO_NEW_PRIVS | PR_GET_NO_NEW_PRIVS => {
+                self.seccomp_prctl_intent()
+            }
+            PR_CAPBSET_READ => vec![
+                "check whether ".white(),
+                capability_name(arg2).yellow(),
+                " is in the capability bounding set".white(),
+            ],
+            PR_CAPBSET_DROP => vec![
+                "drop ".white(),
+                capability_name(arg2).yellow(),
+                " from the capability bounding set, for good".white(),
+            ],
+            PR_GET_TSC => vec!["check whether the timestamp counter can be read".white()],
+            PR_SET_TSC if arg2 == 1 => vec!["allow reading the timestamp counter".white()],
+            PR_SET_TSC => {
+                vec!["make reading the timestamp counter raise SIGSEGV".white()]
+            }
+            PR_GET_SECUREBITS => vec!["get the securebits of the thread".white()],
+            PR_SET_SECUREBITS => vec![
+                "set the securebits of the thread to ".white(),
+                securebits(arg2).yellow(),
+            ],
+            PR_SET_TIMERSLACK if arg2 == 0 => {
+                vec!["reset the timer slack of the thread to its default".white()]
+            }
+            PR_SET_TIMERSLACK => vec![
+                "let the timers of the thread expire up to ".white(),
+                format!("{arg2} nanoseconds").yellow(),
+                " late".white(),
+            ],
+            PR_GET_TIMERSLACK => vec!["get the timer slack of the thread".white()],
+            PR_TASK_PERF_EVENTS_DISABLE => {
+                vec!["pause the performance counters attached to this process".white()]
+            }
+            PR_TASK_PERF_EVENTS_ENABLE => {
+                vec!["resume the performance counters attached to this process".white()]
+            }
+            PR_MCE_KILL => vec![
+                "set the policy for memory corruption machine check errors to ".white(),
+                match (arg2, arg3) {
+                    (0, _) => "the system default".yellow(),
+                    (_, 0) => "kill late, when the memory is accessed".yellow(),
+                    (_, 1) => "kill early, as soon as corruption is found".yellow(),
+                    _ => "the system default".yellow(),
+                },
+            ],
+            PR_MCE_KILL_GET => {
+                vec!["get the policy for memory corruption machine check errors".white()]
+            }
+            PR_SET_MM => {
+                let field = MM_FIELDS
+                    .get(arg2.wrapping_sub(1) as usize)
+                    .unwrap_or(&"an unknown memory map field");
+                let mut intent = vec!["set the ".white(), field.yellow()];
+                match arg2 {
+                    PR_SET_MM_EXE_FILE => {
+                        intent.push(" to ".white());
+                        intent.push(
+                            SyscallObject::style_file_descriptor(arg3, child)
+                                .unwrap_or_else(|| arg3.to_string())
+                                .yellow(),
+                        );
+                    }
+                    PR_SET_MM_MAP_SIZE => intent[0] = "get the ".white(),
+                    field if field < PR_SET_MM_EXE_FILE => {
+                        intent.push(" to ".white());
+                        intent.push(format!("{arg3:#x}").yellow());
+                    }
+                    _ => {}
+                }
+                intent.push(" of the process".white());
+                intent
+            }
+            PR_SET_CHILD_SUBREAPER if arg2 == 0 => {
+                vec!["stop adopting orphaned descendants".white()]
+            }
+            PR_SET_CHILD_SUBREAPER => vec![
+                "adopt orphaned descendants and reap them, like init does (child subreaper)"
+                    .white(),
+            ],
+            PR_GET_CHILD_SUBREAPER => {
+                vec!["check whether this process adopts orphaned descendants".white()]
+            }
+            PR_GET_TID_ADDRESS => {
+                vec!["get the address cleared when this thread exits".white()]
+            }
+            PR_SET_THP_DISABLE => vec![
+                "turn transparent huge pages ".white(),
+                if arg2 == 0 { "on" } else { "off" }.yellow(),
+                " for this process and its children".white(),
+            ],
+            PR_GET_THP_DISABLE => {
+                vec!["check whether transparent huge pages are disabled".white()]
+            }
+            PR_CAP_AMBIENT => match arg2 {
+                PR_CAP_AMBIENT_IS_SET => vec![
+                    "check whether ".white(),
+                    capability_name(arg3).yellow(),
+                    " is in the ambient capability set".white(),
+                ],
+                PR_CAP_AMBIENT_RAISE => vec![
+                    "add ".white(),
+                    capability_name(arg3).yellow(),
+                    " to the ambient capability set, keeping it across execve".white(),
+                ],
+                PR_CAP_AMBIENT_LOWER => vec![
+                    "remove ".white(),
+                    capability_name(arg3).yellow(),
+                    " from the ambient capability set".white(),
+                ],
+                PR_CAP_AMBIENT_CLEAR_ALL => vec!["clear the ambient capability set".white()],
+                _ => vec![
+                    "perform the unknown ambient capability operation ".white(),
+                    arg2.to_string().yellow(),
+                ],
+            },
+            PR_GET_SPECULATION_CTRL => vec![
+                "get the state of the mitigation for ".white(),
+                SPECULATION_MISFEATURES
+                    .get(arg2 as usize)
+                    .unwrap_or(&"an unknown speculation misfeature")
+                    .yellow(),
+            ],
+            PR_SET_SPECULATION_CTRL => vec![
+                "set the mitigation for ".white(),
+                SPECULATION_MISFEATURES
+                    .get(arg2 as usize)
+                    .unwrap_or(&"an unknown speculation misfeature")
+                    .yellow(),
+                " to ".white(),
+                speculation_controls(arg3).yellow(),
+            ],
+            PR_SET_IO_FLUSHER => vec![
+                "mark this process as ".white(),
+                if arg2 == 0 {
+                    "not an IO flusher"
+                } else {
+                    "an IO flusher, allocating memory without recursing into IO"
+                }
+                .yellow(),
+            ],
+            PR_GET_IO_FLUSHER => vec!["check whether this process is an IO flusher".white()],
+            PR_SET_SYSCALL_USER_DISPATCH if arg2 == 0 => {
+                vec!["stop dispatching syscalls to a user space signal handler".white()]
+            }
+            PR_SET_SYSCALL_USER_DISPATCH => vec![
+                "deliver SIGSYS for syscalls made outside of ".white(),
+                format!("{arg3:#x}..{:#x}", arg3.wrapping_add(arg4)).yellow(),
+                " while the selector byte at ".white(),
+                format!("{arg5:#x}").yellow(),
+                " says so".white(),
+            ],
+            PR_SCHED_CORE => {
+                let scope = match arg4 {
+                    0 => "thread",
+                    1 => "thread group",
+                    _ => "process group",
+                };
+                let target = match arg3 {
+                    0 => format!("this {scope}"),
+                    pid => format!("the {scope} of {pid}"),
+                };
+                match arg2 {
+                    0 => vec![
+                        "get the core scheduling cookie of ".white(),
+                        target.yellow(),
+                    ],
+                    1 => vec![
+                        "create a core scheduling cookie for ".white(),
+                        target.yellow(),
+                        ", so that it only shares CPU cores with tasks trusting it".white(),
+                    ],
+                    2 => vec![
+                        "give the core scheduling cookie of this thread to ".white(),
+                        target.yellow(),
+                    ],
+                    _ => vec![
+                        "take the core scheduling cookie of ".white(),
+                        target.yellow(),
+                    ],
+                }
+            }
+            PR_SET_MDWE => {
+                let mut intent = vec![];
+                if (arg2 & MDWE_REFUSE_EXEC_GAIN) != 0 {
+                    intent.push(
+                        "refuse to make memory executable once it was writable".white(),
+                    );
+                } else {
+                    intent.push("leave memory write and execute permissions alone".white());
+                }
+                if (arg2 & MDWE_NO_INHERIT) != 0 {
+                    intent.push(", not inherited by children".white());
+                }
+                intent
+            }
+            PR_GET_MDWE => {
+                vec!["get the memory deny write execute policy".white()]
+            }
+            PR_SET_MEMORY_MERGE => vec![
+                "turn same page merging (KSM) ".white(),
+                if arg2 == 0 { "off" } else { "on" }.yellow(),
+                " for this process".white(),
+            ],
+            PR_GET_MEMORY_MERGE => {
+                vec!["check whether same page merging (KSM) is on for this process".white()]
+            }
+            PR_GET_AUXV => vec!["get the auxiliary vector of the process".white()],
+            PR_SET_VMA if arg2 == PR_SET_VMA_ANON_NAME => {
+                let mut intent = vec![];
+                if arg5 == 0 {
+                    intent.push("clear the name of ".white());
+                } else {
+                    intent.push("name ".white());
+                }
+                intent.push(
+                    format!("{arg4} bytes of anonymous memory at {arg3:#x}").yellow(),
+                );
+                if arg5 != 0 {
+                    intent.push(" ".white());
+                    intent.push(
+                        format!("\"[anon:{}]\"", read_text(arg5, child, 80)).yellow(),
+                    );
+                }
+                intent
+            }
+            PR_SET_PTRACER => vec![
+                "allow ".white(),
+                match arg2 as i64 {
+                    0 => "no process other than ancestors".yellow(),
+                    -1 => "any process".yellow(),
+                    pid => format!("process {pid}").yellow(),
+                },
+                " to ptrace attach to this process".white(),
+            ],
+            operation => vec![
+                "perform the process operation ".white(),
+                format!("#{operation}").yellow(),
+            ],
+        }
+    }
+
+    pub(crate) fn prctl_result(&self) -> Vec<ColoredString> {
+        let child = self.child;
+        let result = self.result.0.unwrap_or(0);
+        let [operation, arg2, arg3, ..] = self.args[..] else {
+            return vec![];
+        };
+        match operation {
+            PR_GET_PDEATHSIG => match read_int(arg2, child) {
+                Some(0) => vec!["no signal".green()],
+                Some(signal) => vec![signal_name(signal as u64).yellow()],
+                None => vec!["successful".green()],
+            },
+            PR_GET_DUMPABLE => vec![match result {
+                0 => "not dumpable".green(),
+                1 => "dumpable".green(),
+                _ => "dumpable, readable by root only".green(),
+            }],
+            PR_GET_KEEPCAPS => vec![if result == 1 {
+                "capabilities are kept".green()
+            } else {
+                "capabilities are cleared".green()
+            }],
+            PR_GET_NAME => vec![format!("\"{}\"", read_text(arg2, child, TASK_COMM_LEN)).yellow()],
+            PR_GET_SECCOMP | PR_SET_SECCOMP | PR_SET_NO_NEW_PRIVS | PR_GET_NO_NEW_PRIVS => {
+                self.seccomp_prctl_result()
+            }
+            PR_CAPBSET_READ => vec![if result == 1 {
+                "in the bounding set".green()
+            } else {
+                "not in the bounding set".green()
+            }],
+            PR_CAPBSET_DROP => vec![
+                capability_name(arg2).yellow(),
+                " can no longer be gained, even through execve".green(),
+            ],
+            PR_GET_TSC => vec![match read_int(arg2, child) {
+                Some(1) => "readable".green(),
+                Some(_) => "raises SIGSEGV".green(),
+                None => "successful".green(),
+            }],
+            PR_GET_SECUREBITS => vec![securebits(result).yellow()],
+            PR_GET_TIMERSLACK => vec![format!("{result} nanoseconds").yellow()],
+            PR_MCE_KILL_GET => vec![match result {
+                0 => "kill late".green(),
+                1 => "kill early".green(),
+                _ => "the system default".green(),
+            }],
+            PR_SET_MM if arg2 == PR_SET_MM_MAP_SIZE => match read_int(arg3, child) {
+                Some(size) => vec![format!("{size} bytes").yellow()],
+                None => vec!["successful".green()],
+            },
+            PR_GET_CHILD_SUBREAPER => vec![match read_int(arg2, child) {
+                Some(0) => "not a child subreaper".green(),
+                Some(_) => "child subreaper".green(),
+                None => "successful".green(),
+            }],
+            PR_GET_TID_ADDRESS => match SyscallObject::read_bytes::<8>(arg2 as usize, child) {
+                Some(address) => vec![format!("{:#x}", u64::from_ne_bytes(address)).yellow()],
+                None => vec!["successful".green()],
+            },
+            PR_GET_THP_DISABLE => vec![if result == 1 {
+                "transparent huge pages are disabled".green()
+            } else {
+                "transparent huge pages are enabled".green()
+            }],
+            PR_CAP_AMBIENT if arg2 == PR_CAP_AMBIENT_IS_SET => vec![if result == 1 {
+                "in the ambient set".green()
+            } else {
+                "not in the ambient set".green()
+            }],
+            PR_GET_SPECULATION_CTRL => vec![speculation_controls(result).yellow()],
+            PR_GET_IO_FLUSHER => vec!["IO flusher: ".green(), on_off(result == 1)],
+            PR_SCHED_CORE if arg2 == 0 => {
+                match SyscallObject::read_bytes::<8>(self.args[4] as usize, child) {
+                    Some(cookie) => vec![
+                        "cookie ".green(),
+                        format!("{:#x}", u64::from_ne_bytes(cookie)).yellow(),
+                    ],
+                    None => vec!["successful".green()],
+                }
+            }
+            PR_GET_MDWE => vec![
+                "memory deny write execute: ".green(),
+                on_off((result & MDWE_REFUSE_EXEC_GAIN) != 0),
+            ],
+            PR_GET_MEMORY_MERGE => vec!["same page merging: ".green(), on_off(result == 1)],
+            PR_GET_AUXV => vec![format!("{result} bytes").yellow()],
+            _ => vec!["successful".green()],
+        }
+    }
+}
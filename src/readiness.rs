@@ -0,0 +1,176 @@
+// the file descriptors poll, select and epoll_wait reported as ready, named and with their events
+// epoll only hands back the user data, it is matched to a file descriptor through the epoll fdinfo
+use crate::syscall_object::SyscallObject;
+use colored::{ColoredString, Colorize};
+use nix::unistd::Pid;
+use syscalls::Sysno;
+
+// poll revents and epoll events share the same bits
+const EVENT_BITS: [(u32, &str); 11] = [
+    (0x001, "readable"),
+    (0x002, "urgent data"),
+    (0x004, "writable"),
+    (0x008, "error"),
+    (0x010, "hung up"),
+    (0x020, "not an open file descriptor"),
+    (0x040, "readable"),
+    (0x080, "priority data"),
+    (0x100, "writable"),
+    (0x200, "priority writable"),
+    (0x2000, "peer closed its writing end"),
+];
+
+const POLLFD_SIZE: usize = 8;
+
+// struct epoll_event is packed on x86_64
+#[cfg(target_arch = "x86_64")]
+pub(crate) const EPOLL_EVENT_SIZE: usize = 12;
+#[cfg(not(target_arch = "x86_64"))]
+pub(crate) const EPOLL_EVENT_SIZE: usize = 16;
+
+pub(crate) fn describe_events(events: u32) -> String {
+    let mut described: Vec<&str> = vec![];
+    for (bit, description) in EVENT_BITS {
+        if (events & bit) != 0 && !described.contains(&description) {
+            described.push(description);
+        }
+    }
+    described.join(", ")
+}
+
+fn describe_fd(fd: i32, child: Pid) -> ColoredString {
+    match SyscallObject::style_file_descriptor(fd as u64, child) {
+        Some(styled) if !styled.is_empty() => format!("fd {styled}").normal(),
+        _ => format!("fd {fd}").bright_blue(),
+    }
+}
+
+// (target fd, data) pairs of the interest list of an epoll instance, from lines like
+// tfd:        5 events:       19 data:                5  pos:0 ino:2 sdev:8
+pub(crate) fn epoll_interest(epfd: i32, child: Pid) -> Vec<(i32, u64)> {
+    let Ok(fdinfo) = std::fs::read_to_string(format!("/proc/{child}/fdinfo/{epfd}")) else {
+        return vec![];
+    };
+    fdinfo
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            if fields.next()? != "tfd:" {
+                return None;
+            }
+            let tfd = fields.next()?.parse().ok()?;
+            let data = fields.skip_while(|field| *field != "data:").nth(1)?;
+            Some((tfd, u64::from_str_radix(data, 16).ok()?))
+        })
+        .collect()
+}
+
+impl SyscallObject {
+    fn ready_polled(&self) -> Vec<(ColoredString, u32)> {
+        let Some(pollfds) = SyscallObject::read_bytes_specific_length(
+            self.args[0] as usize,
+            self.child,
+            self.args[1] as usize * POLLFD_SIZE,
+        ) else {
+            return vec![];
+        };
+        pollfds
+            .chunks(POLLFD_SIZE)
+            .filter_map(|pollfd| {
+                let fd = i32::from_ne_bytes(pollfd[..4].try_into().ok()?);
+                let revents = u16::from_ne_bytes(pollfd[6..].try_into().ok()?) as u32;
+                (revents != 0).then(|| (describe_fd(fd, self.child), revents))
+            })
+            .collect()
+    }
+
+    // the kernel rewrites each fd_set to only hold the ready file descriptors
+    fn ready_selected(&self) -> Vec<(ColoredString, u32)> {
+        let highest_fd = self.args[0] as usize;
+        let sets = [
+            (self.args[1], 0x001),
+            (self.args[2], 0x004),
+            (self.args[3], 0x002),
+        ];
+        let mut ready: Vec<(i32, u32)> = vec![];
+        for (address, event) in sets {
+            if address == 0 {
+                continue;
+            }
+            let Some(set) = SyscallObject::read_bytes_specific_length(
+                address as usize,
+                self.child,
+                highest_fd.div_ceil(64) * 8,
+            ) else {
+                continue;
+            };
+            for fd in 0..highest_fd {
+                if (set[fd / 8] & (1 << (fd % 8))) == 0 {
+                    continue;
+                }
+                match ready
+                    .iter_mut()
+                    .find(|(ready_fd, _)| *ready_fd == fd as i32)
+                {
+                    Some((_, events)) => *events |= event,
+                    None => ready.push((fd as i32, event)),
+                }
+            }
+        }
+        ready.sort_by_key(|(fd, _)| *fd);
+        ready
+            .into_iter()
+            .map(|(fd, events)| (describe_fd(fd, self.child), events))
+            .collect()
+    }
+
+    fn ready_epolled(&self, count: usize) -> Vec<(ColoredString, u32)> {
+        let Some(events) = SyscallObject::read_bytes_specific_length(
+            self.args[1] as usize,
+            self.child,
+            count * EPOLL_EVENT_SIZE,
+        ) else {
+            return vec![];
+        };
+        let interest = epoll_interest(self.args[0] as i32, self.child);
+        events
+            .chunks(EPOLL_EVENT_SIZE)
+            .filter_map(|event| {
+                let events = u32::from_ne_bytes(event[..4].try_into().ok()?);
+                let data = u64::from_ne_bytes(event[EPOLL_EVENT_SIZE - 8..].try_into().ok()?);
+                // several fds registered with the same data cannot be told apart
+                let mut owners = interest
+                    .iter()
+                    .filter(|(_, registered)| *registered == data);
+                let fd = match (owners.next(), owners.next()) {
+                    (Some((fd, _)), None) => describe_fd(*fd, self.child),
+                    _ => format!("data {data:#x}").yellow(),
+                };
+                Some((fd, events))
+            })
+            .collect()
+    }
+
+    // "fd 7 -> 10.0.0.3:443 readable; fd 9 -> Unix Pipe hung up" after a successful wait
+    pub(crate) fn ready_fds(&self) -> Vec<ColoredString> {
+        let Some(count) = self.result.0 else {
+            return vec![];
+        };
+        let ready = match self.sysno {
+            Sysno::poll | Sysno::ppoll => self.ready_polled(),
+            Sysno::select | Sysno::pselect6 => self.ready_selected(),
+            Sysno::epoll_wait | Sysno::epoll_pwait | Sysno::epoll_pwait2 => {
+                self.ready_epolled(count as usize)
+            }
+            _ => return vec![],
+        };
+        let mut line = vec![];
+        for (index, (fd, events)) in ready.into_iter().enumerate() {
+            line.push(if index == 0 { ": " } else { "; " }.white());
+            line.push(fd);
+            line.push(" ".white());
+            line.push(describe_events(events).yellow());
+        }
+        line
+    }
+}
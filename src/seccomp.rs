@@ -0,0 +1,617 @@
+// decoding of the classic BPF filters installed with seccomp(SECCOMP_SET_MODE_FILTER) or prctl(PR_SET_SECCOMP)
+// the filters are kept per process so that later failures can be traced back to them
+use crate::{syscall_object::SyscallObject, types::SockFilter, utilities::SECCOMP_FILTERS};
+use colored::{ColoredString, Colorize};
+use nix::{errno::Errno, unistd::Pid};
+use std::collections::BTreeMap;
+use syscalls::Sysno;
+
+const SECCOMP_SET_MODE_STRICT: u64 = 0;
+const SECCOMP_SET_MODE_FILTER: u64 = 1;
+const SECCOMP_GET_ACTION_AVAIL: u64 = 2;
+const SECCOMP_GET_NOTIF_SIZES: u64 = 3;
+
+const SECCOMP_FILTER_FLAG_TSYNC: u64 = 1;
+
+const SECCOMP_FILTER_FLAGS: [(u64, &str); 6] = [
+    (SECCOMP_FILTER_FLAG_TSYNC, "apply to all threads"),
+    (1 << 1, "log the actions taken"),
+    (1 << 2, "allow speculative store bypass"),
+    (1 << 3, "return a notification file descriptor"),
+    (
+        1 << 4,
+        "report the failing thread on synchronization errors",
+    ),
+    (1 << 5, "wait killable for notification replies"),
+];
+
+const PR_GET_SECCOMP: u64 = 21;
+const PR_SET_SECCOMP: u64 = 22;
+const PR_SET_NO_NEW_PRIVS: u64 = 38;
+const PR_GET_NO_NEW_PRIVS: u64 = 39;
+
+const SECCOMP_RET_KILL_PROCESS: u32 = 0x8000_0000;
+const SECCOMP_RET_KILL_THREAD: u32 = 0x0000_0000;
+const SECCOMP_RET_TRAP: u32 = 0x0003_0000;
+const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+const SECCOMP_RET_USER_NOTIF: u32 = 0x7FC0_0000;
+const SECCOMP_RET_TRACE: u32 = 0x7FF0_0000;
+const SECCOMP_RET_LOG: u32 = 0x7FFC_0000;
+const SECCOMP_RET_ALLOW: u32 = 0x7FFF_0000;
+const SECCOMP_RET_ACTION_FULL: u32 = 0xFFFF_0000;
+const SECCOMP_RET_DATA: u32 = 0x0000_FFFF;
+
+const AUDIT_ARCHES: [(u32, &str); 5] = [
+    (0xC000_003E, "ARCH_X86_64"),
+    (0x4000_0003, "ARCH_I386"),
+    (0xC000_00B7, "ARCH_AARCH64"),
+    (0x4000_0028, "ARCH_ARM"),
+    (0xC000_00F3, "ARCH_RISCV64"),
+];
+const AUDIT_ARCH_X86_64: u32 = 0xC000_003E;
+
+// kernel's limit on instructions in a single filter
+const BPF_MAXINSNS: usize = 4096;
+
+// groups of syscalls sharing an action larger than this are counted instead of listed
+const LISTED_SYSCALLS: usize = 6;
+
+// where each field of `struct seccomp_data` lives, the only memory a filter can load from
+fn seccomp_data_field(offset: u32) -> String {
+    match offset {
+        0 => "sys_number".to_owned(),
+        4 => "arch".to_owned(),
+        8 => "instruction_pointer".to_owned(),
+        12 => "instruction_pointer >> 32".to_owned(),
+        16..=63 if (offset & 3) == 0 => {
+            let argument = (offset - 16) / 8;
+            if (offset & 7) == 0 {
+                format!("args[{argument}]")
+            } else {
+                format!("args[{argument}] >> 32")
+            }
+        }
+        _ => format!("data[{offset}]"),
+    }
+}
+
+fn errno_name(errno: u32) -> String {
+    let errno = Errno::from_raw(errno as i32);
+    if errno == Errno::UnknownErrno {
+        "ERRNO(0)".to_owned()
+    } else {
+        format!("{errno:?}")
+    }
+}
+
+// the short form used in the disassembly and next to listed syscalls
+fn action_name(value: u32) -> String {
+    match value & SECCOMP_RET_ACTION_FULL {
+        SECCOMP_RET_KILL_PROCESS => "KILL_PROCESS".to_owned(),
+        SECCOMP_RET_KILL_THREAD => "KILL_THREAD".to_owned(),
+        SECCOMP_RET_TRAP => "SIGSYS".to_owned(),
+        SECCOMP_RET_ERRNO => errno_name(value & SECCOMP_RET_DATA),
+        SECCOMP_RET_USER_NOTIF => "USER_NOTIF".to_owned(),
+        SECCOMP_RET_TRACE => format!("TRACE({})", value & SECCOMP_RET_DATA),
+        SECCOMP_RET_LOG => "LOG".to_owned(),
+        SECCOMP_RET_ALLOW => "ALLOW".to_owned(),
+        _ => format!("{value:#x}"),
+    }
+}
+
+// the verb used when the action applies to a whole group of syscalls
+fn action_verb(value: u32) -> String {
+    match value & SECCOMP_RET_ACTION_FULL {
+        SECCOMP_RET_KILL_PROCESS => "kill the process".to_owned(),
+        SECCOMP_RET_KILL_THREAD => "kill the thread".to_owned(),
+        SECCOMP_RET_TRAP => "send SIGSYS".to_owned(),
+        SECCOMP_RET_ERRNO => format!("fail with {}", errno_name(value & SECCOMP_RET_DATA)),
+        SECCOMP_RET_USER_NOTIF => "ask the supervisor".to_owned(),
+        SECCOMP_RET_TRACE => "notify the tracer".to_owned(),
+        SECCOMP_RET_LOG => "log and allow".to_owned(),
+        SECCOMP_RET_ALLOW => "allow".to_owned(),
+        _ => format!("return {value:#x}"),
+    }
+}
+
+// the kernel runs every filter and keeps the action with the highest precedence
+fn precedence(value: u32) -> i32 {
+    (value & SECCOMP_RET_ACTION_FULL) as i32
+}
+
+fn syscall_name(number: u32) -> String {
+    match Sysno::new(number as usize) {
+        Some(sysno) => sysno.name().to_owned(),
+        None => number.to_string(),
+    }
+}
+
+fn arch_name(value: u32) -> String {
+    match AUDIT_ARCHES.iter().find(|(arch, _)| *arch == value) {
+        Some((_, name)) => name.to_string(),
+        None => format!("{value:#x}"),
+    }
+}
+
+// renders the filter in the A/X register notation of the kernel's bpf_dbg and seccomp-tools
+fn disassemble(filter: &[SockFilter]) -> Vec<String> {
+    let mut lines = vec![];
+    // what the accumulator was last loaded from, to name the constants it is compared with
+    let mut loaded = None;
+    for (index, instruction) in filter.iter().enumerate() {
+        let SockFilter { code, jt, jf, k } = *instruction;
+        let operand = if (code & 0x08) == 0x08 {
+            "X".to_owned()
+        } else {
+            match loaded {
+                Some(0) => syscall_name(k),
+                Some(4) => arch_name(k),
+                _ => format!("{k:#x}"),
+            }
+        };
+        let text = match code & 0x07 {
+            // BPF_LD
+            0x00 => match code & 0xE0 {
+                0x20 => {
+                    loaded = Some(k);
+                    format!("A = {}", seccomp_data_field(k))
+                }
+                0x00 => {
+                    loaded = None;
+                    format!("A = {k:#x}")
+                }
+                0x60 => {
+                    loaded = None;
+                    format!("A = mem[{k}]")
+                }
+                0x80 => {
+                    loaded = None;
+                    "A = sizeof(seccomp_data)".to_owned()
+                }
+                _ => format!("unsupported load {code:#06x}"),
+            },
+            // BPF_LDX
+            0x01 => match code & 0xE0 {
+                0x00 => format!("X = {k:#x}"),
+                0x60 => format!("X = mem[{k}]"),
+                0x80 => "X = sizeof(seccomp_data)".to_owned(),
+                _ => format!("unsupported load {code:#06x}"),
+            },
+            0x02 => format!("mem[{k}] = A"),
+            0x03 => format!("mem[{k}] = X"),
+            // BPF_ALU
+            0x04 => {
+                let operand = if (code & 0x08) == 0x08 {
+                    "X".to_owned()
+                } else {
+                    format!("{k:#x}")
+                };
+                loaded = None;
+                match code & 0xF0 {
+                    0x00 => format!("A += {operand}"),
+                    0x10 => format!("A -= {operand}"),
+                    0x20 => format!("A *= {operand}"),
+                    0x30 => format!("A /= {operand}"),
+                    0x40 => format!("A |= {operand}"),
+                    0x50 => format!("A &= {operand}"),
+                    0x60 => format!("A <<= {operand}"),
+                    0x70 => format!("A >>= {operand}"),
+                    0x80 => "A = -A".to_owned(),
+                    0x90 => format!("A %= {operand}"),
+                    0xA0 => format!("A ^= {operand}"),
+                    _ => format!("unsupported operation {code:#06x}"),
+                }
+            }
+            // BPF_JMP, targets are shown as absolute instruction numbers
+            0x05 => {
+                let taken = index + 1 + jt as usize;
+                let not_taken = index + 1 + jf as usize;
+                let (operator, negated) = match code & 0xF0 {
+                    0x00 => ("", ""),
+                    0x10 => ("==", "!="),
+                    0x20 => (">", "<="),
+                    0x30 => (">=", "<"),
+                    0x40 => ("&", "!&"),
+                    _ => ("?", "!?"),
+                };
+                if operator.is_empty() {
+                    format!("goto {:04}", index + 1 + k as usize)
+                } else if jf == 0 {
+                    format!("if (A {operator} {operand}) goto {taken:04}")
+                } else if jt == 0 {
+                    format!("if (A {negated} {operand}) goto {not_taken:04}")
+                } else {
+                    format!("if (A {operator} {operand}) goto {taken:04} else goto {not_taken:04}")
+                }
+            }
+            // BPF_RET
+            0x06 => match code & 0x18 {
+                0x10 => "return A".to_owned(),
+                0x08 => "return X".to_owned(),
+                _ => format!("return {}", action_name(k)),
+            },
+            // BPF_MISC
+            _ => {
+                loaded = None;
+                if (code & 0xF8) == 0x80 {
+                    "A = X".to_owned()
+                } else {
+                    "X = A".to_owned()
+                }
+            }
+        };
+        lines.push(text);
+    }
+    lines
+}
+
+// runs the filter for a syscall, arguments that are not given make the result unknown when the filter inspects them
+fn evaluate(filter: &[SockFilter], number: u32, args: Option<&[u64]>) -> Option<u32> {
+    let field = |offset: u32| -> Option<u32> {
+        match offset {
+            0 => Some(number),
+            4 => Some(AUDIT_ARCH_X86_64),
+            16..=63 if (offset & 3) == 0 => {
+                let argument = args?.get(((offset - 16) / 8) as usize)?;
+                if (offset & 7) == 0 {
+                    Some(*argument as u32)
+                } else {
+                    Some((*argument >> 32) as u32)
+                }
+            }
+            _ => None,
+        }
+    };
+    let mut accumulator = 0u32;
+    let mut index_register = 0u32;
+    let mut memory = [0u32; 16];
+    let mut index = 0;
+    while let Some(instruction) = filter.get(index) {
+        let SockFilter { code, jt, jf, k } = *instruction;
+        let operand = if (code & 0x08) == 0x08 {
+            index_register
+        } else {
+            k
+        };
+        index += 1;
+        match code & 0x07 {
+            0x00 => {
+                accumulator = match code & 0xE0 {
+                    0x20 => field(k)?,
+                    0x00 => k,
+                    0x60 => *memory.get(k as usize)?,
+                    0x80 => 64,
+                    _ => return None,
+                }
+            }
+            0x01 => {
+                index_register = match code & 0xE0 {
+                    0x00 => k,
+                    0x60 => *memory.get(k as usize)?,
+                    0x80 => 64,
+                    _ => return None,
+                }
+            }
+            0x02 => *memory.get_mut(k as usize)? = accumulator,
+            0x03 => *memory.get_mut(k as usize)? = index_register,
+            0x04 => {
+                accumulator = match code & 0xF0 {
+                    0x00 => accumulator.wrapping_add(operand),
+                    0x10 => accumulator.wrapping_sub(operand),
+                    0x20 => accumulator.wrapping_mul(operand),
+                    0x30 => accumulator.checked_div(operand)?,
+                    0x40 => accumulator | operand,
+                    0x50 => accumulator & operand,
+                    0x60 => accumulator.checked_shl(operand).unwrap_or(0),
+                    0x70 => accumulator.checked_shr(operand).unwrap_or(0),
+                    0x80 => accumulator.wrapping_neg(),
+                    0x90 => accumulator.checked_rem(operand)?,
+                    0xA0 => accumulator ^ operand,
+                    _ => return None,
+                }
+            }
+            0x05 => {
+                let taken = match code & 0xF0 {
+                    0x00 => {
+                        index += k as usize;
+                        continue;
+                    }
+                    0x10 => accumulator == operand,
+                    0x20 => accumulator > operand,
+                    0x30 => accumulator >= operand,
+                    0x40 => (accumulator & operand) != 0,
+                    _ => return None,
+                };
+                index += if taken { jt as usize } else { jf as usize };
+            }
+            0x06 => {
+                return Some(match code & 0x18 {
+                    0x10 => accumulator,
+                    0x08 => index_register,
+                    _ => k,
+                })
+            }
+            _ => {
+                if (code & 0xF8) == 0x80 {
+                    accumulator = index_register;
+                } else {
+                    index_register = accumulator;
+                }
+            }
+        }
+    }
+    None
+}
+
+// groups every syscall of the architecture by what the filter does with it
+fn summarize(filter: &[SockFilter]) -> String {
+    let mut groups: BTreeMap<Option<u32>, Vec<&'static str>> = BTreeMap::new();
+    for sysno in Sysno::iter() {
+        groups
+            .entry(evaluate(filter, sysno.id() as u32, None))
+            .or_default()
+            .push(sysno.name());
+    }
+    let Some(default) = groups
+        .iter()
+        .max_by_key(|(_, syscalls)| syscalls.len())
+        .map(|(action, _)| *action)
+    else {
+        return String::new();
+    };
+    let describe = |action: &Option<u32>| match action {
+        Some(value) => action_verb(*value),
+        None => "decide by the arguments".to_owned(),
+    };
+    let mut counted = vec![];
+    let mut listed = vec![];
+    for (action, syscalls) in &groups {
+        if *action == default {
+            continue;
+        }
+        if syscalls.len() > LISTED_SYSCALLS {
+            counted.push(format!("{} {} syscalls", describe(action), syscalls.len()));
+        } else {
+            let outcome = match action {
+                Some(value) => action_name(*value),
+                None => "depends on the arguments".to_owned(),
+            };
+            listed.push(format!("{} -> {outcome}", syscalls.join(", ")));
+        }
+    }
+    let everything_else = if groups.len() == 1 {
+        format!("{} every syscall", describe(&default))
+    } else if default == Some(SECCOMP_RET_ALLOW) {
+        "allow everything else".to_owned()
+    } else {
+        format!("{} on everything else", describe(&default))
+    };
+    counted.push(everything_else);
+    let mut summary = counted.join(", ");
+    for listed in listed {
+        summary.push_str("; ");
+        summary.push_str(&listed);
+    }
+    summary
+}
+
+fn read_filter(address: u64, child: Pid) -> Option<Vec<SockFilter>> {
+    // struct sock_fprog { unsigned short len; struct sock_filter *filter; }
+    let program = SyscallObject::read_bytes_specific_length(address as usize, child, 16)?;
+    let length = u16::from_ne_bytes([program[0], program[1]]) as usize;
+    let base = u64::from_ne_bytes(program[8..16].try_into().unwrap());
+    if length == 0 || length > BPF_MAXINSNS {
+        return None;
+    }
+    let instructions = SyscallObject::read_bytes_specific_length(base as usize, child, length * 8)?;
+    Some(
+        instructions
+            .chunks_exact(8)
+            .map(|instruction| SockFilter {
+                code: u16::from_ne_bytes([instruction[0], instruction[1]]),
+                jt: instruction[2],
+                jf: instruction[3],
+                k: u32::from_ne_bytes(instruction[4..8].try_into().unwrap()),
+            })
+            .collect(),
+    )
+}
+
+// the sock_fprog argument, if this syscall installs a filter
+fn filter_address(syscall: &SyscallObject) -> Option<u64> {
+    match syscall.sysno {
+        Sysno::seccomp if syscall.args[0] == SECCOMP_SET_MODE_FILTER => Some(syscall.args[2]),
+        Sysno::prctl if syscall.args[0] == PR_SET_SECCOMP && syscall.args[1] == 2 => {
+            Some(syscall.args[2])
+        }
+        _ => None,
+    }
+}
+
+pub fn track_seccomp(syscall: &SyscallObject) {
+    if syscall.errno.is_some() {
+        return;
+    }
+    // threads and processes start with the filters of the thread that created them
+    if let Sysno::fork | Sysno::vfork | Sysno::clone | Sysno::clone3 = syscall.sysno {
+        match syscall.result.0 {
+            Some(0) | None => {}
+            Some(child) => inherit_filters(syscall.child, Pid::from_raw(child as i32)),
+        }
+        return;
+    }
+    let Some(address) = filter_address(syscall) else {
+        return;
+    };
+    // a failed synchronization returns the id of the thread that could not be synchronized
+    let synchronized =
+        syscall.sysno == Sysno::seccomp && (syscall.args[1] & SECCOMP_FILTER_FLAG_TSYNC) != 0;
+    if synchronized && syscall.result.0 != Some(0) {
+        return;
+    }
+    if let Some(filter) = read_filter(address, syscall.child) {
+        SECCOMP_FILTERS
+            .with_borrow_mut(|filters| filters.entry(syscall.child).or_default().push(filter));
+    }
+    if synchronized {
+        synchronize_filters(syscall.child);
+    }
+}
+
+fn inherit_filters(parent: Pid, child: Pid) {
+    SECCOMP_FILTERS.with_borrow_mut(|filters| {
+        let Some(inherited) = filters.get(&parent).cloned() else {
+            return;
+        };
+        // filters the child installed before the parent's return was seen stack on top
+        let own = filters.remove(&child).unwrap_or_default();
+        filters.insert(child, inherited.into_iter().chain(own).collect());
+    });
+}
+
+// SECCOMP_FILTER_FLAG_TSYNC gives every thread of the process the filters of the installing thread
+fn synchronize_filters(tid: Pid) {
+    let Ok(tasks) = procfs::process::Process::new(tid.as_raw()).and_then(|process| process.tasks())
+    else {
+        return;
+    };
+    SECCOMP_FILTERS.with_borrow_mut(|filters| {
+        let Some(synchronized) = filters.get(&tid).cloned() else {
+            return;
+        };
+        for task in tasks.flatten() {
+            filters.insert(Pid::from_raw(task.tid), synchronized.clone());
+        }
+    });
+}
+
+impl SyscallObject {
+    fn seccomp_filter_description(&self, address: u64, flags: &[&str]) -> Vec<ColoredString> {
+        let Some(filter) = read_filter(address, self.child) else {
+            return vec!["install a seccomp filter".white()];
+        };
+        let mut description = vec![
+            "install a seccomp filter of ".white(),
+            filter.len().to_string().yellow(),
+            " instructions (".white(),
+            summarize(&filter).yellow(),
+            ")".white(),
+        ];
+        if !flags.is_empty() {
+            description.push(" (".white());
+            description.push(flags.join(", ").yellow());
+            description.push(")".white());
+        }
+        for (index, line) in disassemble(&filter).into_iter().enumerate() {
+            description.push(format!("\n\t{index:04}: ").dimmed());
+            description.push(line.bright_white());
+        }
+        description
+    }
+
+    pub(crate) fn seccomp_intent(&self) -> Vec<ColoredString> {
+        match self.args[0] {
+            SECCOMP_SET_MODE_STRICT => vec![
+                "only allow read, write, _exit and sigreturn from now on (strict mode)".white(),
+            ],
+            SECCOMP_SET_MODE_FILTER => {
+                let flags = SECCOMP_FILTER_FLAGS
+                    .iter()
+                    .filter(|(flag, _)| (self.args[1] & flag) == *flag)
+                    .map(|(_, description)| *description)
+                    .collect::<Vec<&str>>();
+                self.seccomp_filter_description(self.args[2], &flags)
+            }
+            SECCOMP_GET_ACTION_AVAIL => {
+                let action = SyscallObject::read_bytes::<4>(self.args[2] as usize, self.child)
+                    .map(|bytes| action_name(u32::from_ne_bytes(bytes)))
+                    .unwrap_or_default();
+                vec![
+                    "check whether the seccomp action ".white(),
+                    action.yellow(),
+                    " is supported".white(),
+                ]
+            }
+            SECCOMP_GET_NOTIF_SIZES => {
+                vec!["get the sizes of the seccomp notification structures".white()]
+            }
+            operation => vec![
+                "perform the seccomp operation ".white(),
+                format!("#{operation}").yellow(),
+            ],
+        }
+    }
+
+    pub(crate) fn seccomp_result(&self) -> Vec<ColoredString> {
+        match self.args[0] {
+            SECCOMP_SET_MODE_STRICT => vec!["strict mode enabled".green()],
+            SECCOMP_SET_MODE_FILTER => match self.result.0 {
+                Some(fd) if (self.args[1] & (1 << 3)) != 0 => vec![
+                    "filter installed, notification file descriptor: ".green(),
+                    fd.to_string().yellow(),
+                ],
+                _ => vec!["filter installed".green()],
+            },
+            SECCOMP_GET_ACTION_AVAIL => vec!["supported".green()],
+            _ => vec!["successful".green()],
+        }
+    }
+
+    // the prctl operations related to seccomp and no_new_privs
+    pub(crate) fn seccomp_prctl_intent(&self) -> Vec<ColoredString> {
+        match self.args[0] {
+            PR_SET_SECCOMP if self.args[1] == 2 => {
+                self.seccomp_filter_description(self.args[2], &[])
+            }
+            PR_SET_SECCOMP => vec![
+                "only allow read, write, _exit and sigreturn from now on (strict mode)".white(),
+            ],
+            PR_GET_SECCOMP => vec!["get the seccomp mode of the thread".white()],
+            PR_SET_NO_NEW_PRIVS => {
+                vec!["stop execve from granting privileges to this thread and its children".white()]
+            }
+            _ => vec!["check whether execve can grant privileges".white()],
+        }
+    }
+
+    pub(crate) fn seccomp_prctl_result(&self) -> Vec<ColoredString> {
+        let result = self.result.0.unwrap_or(0);
+        match self.args[0] {
+            PR_SET_SECCOMP if self.args[1] == 2 => vec!["filter installed".green()],
+            PR_SET_SECCOMP => vec!["strict mode enabled".green()],
+            PR_GET_SECCOMP => vec![match result {
+                0 => "seccomp is disabled".green(),
+                1 => "strict mode".green(),
+                _ => "filter mode".green(),
+            }],
+            PR_GET_NO_NEW_PRIVS => vec![if result == 1 {
+                "execve cannot grant privileges".green()
+            } else {
+                "execve can grant privileges".green()
+            }],
+            _ => vec!["successful".green()],
+        }
+    }
+
+    // a failure the installed filters would have produced for exactly this syscall and its arguments
+    pub(crate) fn seccomp_denial(&self) -> Option<Vec<ColoredString>> {
+        let errno = self.errno?;
+        let action = SECCOMP_FILTERS.with_borrow(|filters| {
+            filters
+                .get(&self.child)?
+                .iter()
+                .filter_map(|filter| evaluate(filter, self.sysno.id() as u32, Some(&self.args)))
+                .min_by_key(|action| precedence(*action))
+        })?;
+        if (action & SECCOMP_RET_ACTION_FULL) != SECCOMP_RET_ERRNO
+            || (action & SECCOMP_RET_DATA) as i32 != errno as i32
+        {
+            return None;
+        }
+        Some(vec![
+            " (".white(),
+            format!("{errno:?}").yellow(),
+            " was returned by the seccomp filter installed earlier, not by the kernel)".white(),
+        ])
+    }
+}
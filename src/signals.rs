@@ -0,0 +1,301 @@
+// decoding of rt_sigaction and rt_sigprocmask, and the signal dispositions of every process kept from them
+// when a signal arrives the table tells what will handle it, /proc fills in for handlers installed before tracing
+use crate::{
+    error_explainer::flush_probe_run,
+    symbols::symbolize,
+    syscall_object::SyscallObject,
+    threads::{thread_group, thread_label},
+    types::SignalDisposition,
+    utilities::{x86_signal_to_string, BLOCKED_SIGNALS, SIGNAL_DISPOSITIONS},
+};
+use colored::{ColoredString, Colorize};
+use nix::unistd::Pid;
+use syscalls::Sysno;
+
+const SIG_DFL: u64 = 0;
+const SIG_IGN: u64 = 1;
+
+const SIGKILL: i32 = 9;
+const SIGSTOP: i32 = 19;
+const SIGRTMIN: i32 = 34;
+
+const SA_RESETHAND: u64 = 0x8000_0000;
+
+// SA_RESTORER is left out, libc sets it on every handler for the return trampoline
+const SA_FLAGS: [(u64, &str); 7] = [
+    (0x0000_0001, "no SIGCHLD when children stop"),
+    (0x0000_0002, "children reaped automatically"),
+    (0x0000_0004, "passing siginfo"),
+    (0x0000_0800, "with the tag bits of fault addresses"),
+    (0x0800_0000, "on the alternate signal stack"),
+    (0x1000_0000, "restarting interrupted syscalls"),
+    (0x4000_0000, "without blocking the signal while it runs"),
+];
+
+// struct sigaction as the kernel takes it on x86_64, the mask is a single word
+const KERNEL_SIGACTION_SIZE: usize = 32;
+
+fn signal_name(signal: i32) -> String {
+    match signal {
+        SIGRTMIN..=64 => format!("SIGRTMIN+{}", signal - SIGRTMIN),
+        _ => x86_signal_to_string(signal as u64)
+            .map(|name| name.to_owned())
+            .unwrap_or_else(|| format!("signal {signal}")),
+    }
+}
+
+fn default_action(signal: i32) -> &'static str {
+    match signal {
+        3..=8 | 11 | 24 | 25 | 31 => "terminate the process and dump core",
+        17 | 23 | 28 => "ignore it",
+        18 => "continue the process if it is stopped",
+        19..=22 => "stop the process",
+        _ => "terminate the process",
+    }
+}
+
+// "SIGINT, SIGTERM" for the signals in a sigset
+fn signal_names(set: u64) -> String {
+    (1..=64)
+        .filter(|signal| (set & (1 << (signal - 1))) != 0)
+        .map(signal_name)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn read_sigaction(address: u64, child: Pid) -> Option<SignalDisposition> {
+    if address == 0 {
+        return None;
+    }
+    let bytes = SyscallObject::read_bytes::<KERNEL_SIGACTION_SIZE>(address as usize, child)?;
+    let word = |offset: usize| u64::from_ne_bytes(bytes[offset..offset + 8].try_into().unwrap());
+    let handler = word(0);
+    Some(SignalDisposition {
+        handler,
+        symbol: match handler {
+            SIG_DFL | SIG_IGN => String::new(),
+            handler => symbolize(child, handler),
+        },
+        flags: word(8),
+        mask: word(24),
+    })
+}
+
+fn describe_disposition(signal: i32, disposition: &SignalDisposition) -> Vec<ColoredString> {
+    match disposition.handler {
+        SIG_DFL => vec![
+            "the default action, ".white(),
+            default_action(signal).yellow(),
+        ],
+        SIG_IGN => vec!["ignoring it".yellow()],
+        _ => vec!["the handler at ".white(), disposition.symbol.yellow()],
+    }
+}
+
+pub fn track_signals(syscall: &SyscallObject) {
+    if syscall.errno.is_some() {
+        return;
+    }
+    match syscall.sysno {
+        Sysno::rt_sigaction => {
+            if let Some(disposition) = read_sigaction(syscall.args[1], syscall.child) {
+                SIGNAL_DISPOSITIONS.with_borrow_mut(|dispositions| {
+                    dispositions
+                        .entry(thread_group(syscall.child))
+                        .or_default()
+                        .insert(syscall.args[0], disposition)
+                });
+            }
+        }
+        // the kernel already applied how, the resulting mask is read back instead of recomputed
+        Sysno::rt_sigprocmask if syscall.args[1] != 0 => {
+            if let Ok(status) = procfs::process::Process::new(syscall.child.as_raw())
+                .and_then(|process| process.status())
+            {
+                BLOCKED_SIGNALS
+                    .with_borrow_mut(|blocked| blocked.insert(syscall.child, status.sigblk));
+            }
+        }
+        // exec resets handled signals to their default action, ignored ones stay ignored
+        Sysno::execve | Sysno::execveat => {
+            SIGNAL_DISPOSITIONS.with_borrow_mut(|dispositions| {
+                if let Some(process) = dispositions.get_mut(&thread_group(syscall.child)) {
+                    process.retain(|_, disposition| disposition.handler == SIG_IGN);
+                }
+            });
+        }
+        // a new process starts with a copy of the dispositions of its parent
+        Sysno::fork | Sysno::vfork | Sysno::clone | Sysno::clone3 => {
+            let Some(child) = syscall.result.0 else {
+                return;
+            };
+            if child == 0
+                || (syscall.sysno != Sysno::fork
+                    && syscall.sysno != Sysno::vfork
+                    && syscall.creates_thread())
+            {
+                return;
+            }
+            SIGNAL_DISPOSITIONS.with_borrow_mut(|dispositions| {
+                if let Some(parent) = dispositions.get(&thread_group(syscall.child)).cloned() {
+                    dispositions.insert(Pid::from_raw(child as i32), parent);
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+impl SyscallObject {
+    // "handle SIGTERM with the handler at my_app::on_term (restarting interrupted syscalls)"
+    pub(crate) fn sigaction_intent(&self) -> Option<Vec<ColoredString>> {
+        let signal = self.args[0] as i32;
+        let disposition = read_sigaction(self.args[1], self.child)?;
+        let mut intent = vec![];
+        match disposition.handler {
+            SIG_DFL => {
+                intent.push("restore the default action for ".white());
+                intent.push(signal_name(signal).yellow());
+                intent.push(" (".white());
+                intent.push(default_action(signal).yellow());
+                intent.push(")".white());
+                return Some(intent);
+            }
+            SIG_IGN => {
+                intent.push("ignore ".white());
+                intent.push(signal_name(signal).yellow());
+                return Some(intent);
+            }
+            _ => {
+                intent.push("handle ".white());
+                intent.push(signal_name(signal).yellow());
+                intent.push(" with the handler at ".white());
+                intent.push(disposition.symbol.yellow());
+            }
+        }
+        let mut details = SA_FLAGS
+            .iter()
+            .filter(|(flag, _)| (disposition.flags & flag) != 0)
+            .map(|(_, description)| description.to_string())
+            .collect::<Vec<String>>();
+        if (disposition.flags & SA_RESETHAND) != 0 {
+            details.push("only once, then back to the default action".to_owned());
+        }
+        if disposition.mask != 0 {
+            details.push(format!(
+                "blocking {} while it runs",
+                signal_names(disposition.mask)
+            ));
+        }
+        if !details.is_empty() {
+            intent.push(" (".white());
+            intent.push(details.join(", ").yellow());
+            intent.push(")".white());
+        }
+        Some(intent)
+    }
+
+    // ", previously: ignoring it" when the old action was asked for
+    pub(crate) fn previous_sigaction(&self) -> Vec<ColoredString> {
+        let Some(disposition) = read_sigaction(self.args[2], self.child) else {
+            return vec![];
+        };
+        let mut previous = vec![", previously: ".green()];
+        previous.extend(describe_disposition(self.args[0] as i32, &disposition));
+        previous
+    }
+
+    // the signals of the set rt_sigprocmask was given
+    pub(crate) fn sigprocmask_set(&self) -> Vec<ColoredString> {
+        match SyscallObject::read_bytes::<8>(self.args[1] as usize, self.child) {
+            Some(set) if u64::from_ne_bytes(set) != 0 => vec![
+                " (".white(),
+                signal_names(u64::from_ne_bytes(set)).yellow(),
+                ")".white(),
+            ],
+            Some(_) => vec![" (".white(), "no signals".yellow(), ")".white()],
+            None => vec![],
+        }
+    }
+
+    pub(crate) fn blocked_signals(&self) -> Vec<ColoredString> {
+        match BLOCKED_SIGNALS.with_borrow(|blocked| blocked.get(&self.child).copied()) {
+            Some(0) => vec![", nothing is blocked now".green()],
+            Some(blocked) => vec![", blocked now: ".green(), signal_names(blocked).yellow()],
+            None => vec![],
+        }
+    }
+}
+
+// what is going to happen to a signal that is being delivered to a thread
+fn signal_handling(tid: Pid, signal: i32) -> Vec<ColoredString> {
+    if signal == SIGKILL || signal == SIGSTOP {
+        return vec![
+            "cannot be caught or ignored, the kernel will ".white(),
+            default_action(signal).yellow(),
+        ];
+    }
+    let disposition = SIGNAL_DISPOSITIONS.with_borrow(|dispositions| {
+        dispositions
+            .get(&thread_group(tid))?
+            .get(&(signal as u64))
+            .cloned()
+    });
+    match disposition {
+        Some(disposition) => {
+            let mut handling = match disposition.handler {
+                SIG_DFL => vec![
+                    "is not handled, the default action is to ".white(),
+                    default_action(signal).yellow(),
+                ],
+                SIG_IGN => vec!["is ignored".yellow()],
+                _ => vec![
+                    "is handled by the handler at ".white(),
+                    disposition.symbol.yellow(),
+                ],
+            };
+            if disposition.handler > SIG_IGN && (disposition.flags & SA_RESETHAND) != 0 {
+                handling.push(", which then resets to the default action".white());
+                SIGNAL_DISPOSITIONS.with_borrow_mut(|dispositions| {
+                    if let Some(process) = dispositions.get_mut(&thread_group(tid)) {
+                        process.remove(&(signal as u64));
+                    }
+                });
+            }
+            handling
+        }
+        None => {
+            let bit = 1 << (signal - 1);
+            match procfs::process::Process::new(tid.as_raw()).and_then(|process| process.status()) {
+                Ok(status) if (status.sigcgt & bit) != 0 => {
+                    vec!["is handled by a handler installed before tracing started".white()]
+                }
+                Ok(status) if (status.sigign & bit) != 0 => vec!["is ignored".yellow()],
+                _ => vec![
+                    "is not handled, the default action is to ".white(),
+                    default_action(signal).yellow(),
+                ],
+            }
+        }
+    }
+}
+
+pub fn signal_arrived(tid: Pid, signal: i32) {
+    flush_probe_run();
+    let (id, name) = thread_label(tid);
+    let pid = format!(" {id} {name} ").on_black();
+    let arrived = format!(" {} ", signal_name(signal)).on_bright_magenta();
+    print!("\n\n {pid}{arrived} ");
+    for part in signal_handling(tid, signal) {
+        print!("{part}");
+    }
+    println!();
+}
+
+pub fn killed_by_signal(tid: Pid, signal: i32) {
+    flush_probe_run();
+    let (id, name) = thread_label(tid);
+    let pid = format!(" {id} {name} ").on_black();
+    let killed = format!(" KILLED BY {} ", signal_name(signal)).on_bright_red();
+    print!("\n\n {pid}{killed}\n");
+}
@@ -0,0 +1,234 @@
+// symbolization of code addresses in the tracee against the symbol tables of the ELF files it has mapped
+// only 64 bit little endian ELF files are read, legacy rust and c++ names are demangled
+// the symbol tables are parsed once per file and kept until the file changes
+// offsets and sizes come from whatever file the tracee mapped, an entry or section that does not fit is skipped
+use crate::{
+    types::ElfSymbols,
+    utilities::{where_in_childs_memory, ELF_SYMBOLS},
+};
+use nix::unistd::Pid;
+use procfs::process::MMapPath;
+use std::{os::unix::fs::MetadataExt, path::Path, rc::Rc};
+
+const PT_LOAD: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+const SHT_DYNSYM: u32 = 11;
+const STT_FUNC: u8 = 2;
+
+const PROGRAM_HEADER_SIZE: usize = 56;
+const SECTION_HEADER_SIZE: usize = 64;
+const SYMBOL_SIZE: usize = 24;
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset.checked_add(2)?)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        bytes.get(offset..offset.checked_add(8)?)?.try_into().ok()?,
+    ))
+}
+
+// the offset, size and virtual address of every loadable segment
+fn loadable_segments(elf: &[u8]) -> Vec<(u64, u64, u64)> {
+    let (Some(program_headers), Some(count)) = (u64_at(elf, 0x20), u16_at(elf, 0x38)) else {
+        return vec![];
+    };
+    (0..count as usize)
+        .filter_map(|index| {
+            let header =
+                (program_headers as usize).checked_add(index.checked_mul(PROGRAM_HEADER_SIZE)?)?;
+            if u32_at(elf, header)? != PT_LOAD {
+                return None;
+            }
+            Some((
+                u64_at(elf, header.checked_add(8)?)?,
+                u64_at(elf, header.checked_add(32)?)?,
+                u64_at(elf, header.checked_add(16)?)?,
+            ))
+        })
+        .collect()
+}
+
+fn function_symbol(elf: &[u8], strings: usize, symbol: usize) -> Option<(u64, u64, String)> {
+    if (elf.get(symbol.checked_add(4)?)? & 0xf) != STT_FUNC {
+        return None;
+    }
+    let value = u64_at(elf, symbol.checked_add(8)?)?;
+    let length = u64_at(elf, symbol.checked_add(16)?)?;
+    let name = elf.get(strings.checked_add(u32_at(elf, symbol)? as usize)?..)?;
+    let name = &name[..name.iter().position(|byte| *byte == 0)?];
+    Some((
+        value,
+        value.checked_add(length.max(1))?,
+        String::from_utf8_lossy(name).into_owned(),
+    ))
+}
+
+// the symbols of a SHT_SYMTAB or SHT_DYNSYM section, with the names from its linked string table
+fn section_functions(
+    elf: &[u8],
+    section_headers: usize,
+    header: usize,
+) -> Option<Vec<(u64, u64, String)>> {
+    let symbols = u64_at(elf, header.checked_add(24)?)? as usize;
+    let size = u64_at(elf, header.checked_add(32)?)? as usize;
+    let link = u32_at(elf, header.checked_add(40)?)? as usize;
+    let strings_header = section_headers.checked_add(link.checked_mul(SECTION_HEADER_SIZE)?)?;
+    let strings = u64_at(elf, strings_header.checked_add(24)?)? as usize;
+    let end = symbols.checked_add(size)?.min(elf.len());
+    Some(
+        (symbols..end)
+            .step_by(SYMBOL_SIZE)
+            .filter_map(|symbol| function_symbol(elf, strings, symbol))
+            .collect(),
+    )
+}
+
+// the function symbols of the full symbol table, then those of the dynamic one stripped files still have
+fn function_symbols(elf: &[u8]) -> Vec<(u64, u64, String)> {
+    let (Some(section_headers), Some(count)) = (u64_at(elf, 0x28), u16_at(elf, 0x3C)) else {
+        return vec![];
+    };
+    let section_headers = section_headers as usize;
+    let mut functions = vec![];
+    for wanted in [SHT_SYMTAB, SHT_DYNSYM] {
+        for index in 0..count as usize {
+            let Some(header) = index
+                .checked_mul(SECTION_HEADER_SIZE)
+                .and_then(|offset| section_headers.checked_add(offset))
+            else {
+                continue;
+            };
+            let kind = header.checked_add(4).and_then(|kind| u32_at(elf, kind));
+            if kind != Some(wanted) {
+                continue;
+            }
+            if let Some(section) = section_functions(elf, section_headers, header) {
+                functions.extend(section);
+            }
+        }
+    }
+    functions
+}
+
+fn parse_symbols(path: &Path, identity: (u64, i64, i64)) -> ElfSymbols {
+    let empty = ElfSymbols {
+        identity,
+        ..Default::default()
+    };
+    let Ok(elf) = std::fs::read(path) else {
+        return empty;
+    };
+    if !elf.starts_with(b"\x7fELF") {
+        return empty;
+    }
+    ElfSymbols {
+        identity,
+        segments: loadable_segments(&elf),
+        functions: function_symbols(&elf),
+    }
+}
+
+// a file is parsed again once it is replaced or rewritten, files that are not ELF are remembered as empty
+fn elf_symbols(path: &Path) -> Rc<ElfSymbols> {
+    let identity = std::fs::metadata(path)
+        .map(|metadata| (metadata.ino(), metadata.mtime(), metadata.mtime_nsec()))
+        .unwrap_or_default();
+    let cached = ELF_SYMBOLS.with_borrow(|cache| {
+        cache
+            .get(path)
+            .filter(|symbols| symbols.identity == identity)
+            .cloned()
+    });
+    if let Some(symbols) = cached {
+        return symbols;
+    }
+    let symbols = Rc::new(parse_symbols(path, identity));
+    ELF_SYMBOLS.with_borrow_mut(|cache| cache.insert(path.to_path_buf(), symbols.clone()));
+    symbols
+}
+
+// the function covering an offset into the file, through the loadable segment holding it
+fn function_at(symbols: &ElfSymbols, file_offset: u64) -> Option<&str> {
+    let address = symbols
+        .segments
+        .iter()
+        .find_map(|(offset, size, address)| {
+            let into = file_offset
+                .checked_sub(*offset)
+                .filter(|into| into < size)?;
+            address.checked_add(into)
+        })?;
+    symbols
+        .functions
+        .iter()
+        .find(|(start, end, _)| (*start..*end).contains(&address))
+        .map(|(_, _, name)| name.as_str())
+}
+
+// _ZN6my_app7on_term17h0123456789abcdefE is my_app::on_term
+fn demangle(name: &str) -> String {
+    let Some(mut rest) = name
+        .strip_prefix("_ZN")
+        .and_then(|rest| rest.strip_suffix('E'))
+    else {
+        return name.to_owned();
+    };
+    let mut segments = vec![];
+    while !rest.is_empty() {
+        let digits = rest
+            .chars()
+            .take_while(|char| char.is_ascii_digit())
+            .count();
+        let Some(length) = rest[..digits].parse::<usize>().ok() else {
+            return name.to_owned();
+        };
+        let Some(segment) = digits
+            .checked_add(length)
+            .and_then(|end| rest.get(digits..end))
+        else {
+            return name.to_owned();
+        };
+        segments.push(segment);
+        rest = &rest[digits + segment.len()..];
+    }
+    // rust appends a hash of the crate to every path
+    if let Some(hash) = segments.last().and_then(|last| last.strip_prefix('h')) {
+        if hash.len() == 16 && hash.chars().all(|char| char.is_ascii_hexdigit()) {
+            segments.pop();
+        }
+    }
+    segments
+        .join("::")
+        .replace("..", "::")
+        .replace("$LT$", "<")
+        .replace("$GT$", ">")
+        .replace("$RF$", "&")
+        .replace("$C$", ",")
+        .replace("$u20$", " ")
+}
+
+// "my_app::on_term", or the file and offset of the address when no symbol covers it
+pub fn symbolize(child: Pid, address: u64) -> String {
+    let Some(map) = where_in_childs_memory(child, address) else {
+        return format!("0x{address:x}");
+    };
+    let MMapPath::Path(path) = map.pathname else {
+        return format!("0x{address:x}");
+    };
+    let file_offset = address - map.address.0 + map.offset;
+    let symbols = elf_symbols(&path);
+    match function_at(&symbols, file_offset) {
+        Some(symbol) => demangle(symbol),
+        None => format!("0x{address:x} ({}+0x{file_offset:x})", path.display()),
+    }
+}
@@ -1,6 +1,8 @@
 #![allow(unused_variables)]
 use crate::{
+    buffers::format_buffer,
     one_line_formatter::handle_path_file,
+    threads::thread_label,
     types::{
         mlock2, Annotation, ArgContainer, Bytes, BytesPagesRelevant, Category, Flag,
         LandlockCreateFlags, LandlockRuleTypeFlags, SysArg, SysReturn,
@@ -107,15 +109,19 @@ impl SyscallObject {
                 let mut output = vec![];
                 output.push("\n".dimmed());
                 let eph_return = self.parse_return_value(1);
+                let (id, name) = thread_label(self.child);
                 if FOLLOW_FORKS.get() {
-                    output.push(self.child.to_string().bright_blue());
+                    output.push(id.bright_blue());
                 } else {
                     if eph_return.is_ok() {
-                        output.push(self.child.to_string().blue());
+                        output.push(id.blue());
                     } else {
-                        output.push(self.child.to_string().red());
+                        output.push(id.red());
                     }
                 }
+                if !name.is_empty() {
+                    output.push(format!(" {name}").cyan());
+                }
                 output.extend(vec![
                     " ".dimmed(),
                     SyscallObject::colorize_syscall_name(&self.sysno, &self.category),
@@ -323,22 +329,15 @@ impl SyscallObject {
                     }
 
                     Pointer_To_Text(text) => {
-                        if text.len() > 20 {
-                            let portion = &text[..20];
-                            format!("{:?}", format!("{}...", portion)).purple()
-                        } else if text.len() == 0 {
+                        if text.len() == 0 {
                             format!("\"\"").bright_yellow()
                         } else {
-                            format!("{:?}", format!("{}", text)).purple()
+                            format_buffer(text.as_bytes(), text.len()).purple()
                         }
                     }
 
                     Pointer_To_Path(text) => {
-                        if text.len() > 20 {
-                            let portion = &text[..];
-
-                            format!("{:?}", format!("{}...", portion)).purple()
-                        } else if text.len() == 0 {
+                        if text.len() == 0 {
                             format!("\"\"").bright_yellow()
                         } else {
                             format!("{:?}", format!("{}", text)).purple()
@@ -1180,7 +1179,7 @@ impl SyscallObject {
         };
         syscall
     }
-    fn style_file_descriptor(register_value: u64, child: Pid) -> Option<String> {
+    pub(crate) fn style_file_descriptor(register_value: u64, child: Pid) -> Option<String> {
         let fd = register_value as RawFd;
         let mut string = Vec::new();
         if fd < 0 {
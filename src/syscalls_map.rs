@@ -165,7 +165,8 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
                     (["fd", "file descriptor of the file to be read from"], Normal(File_Descriptor(""))),
                     (["iovec", "array of iovec structs containing pointer-length pairs of scattered regions to be written"], Normal(Array_Of_Struct)),
                     (["count", "number of iovec structs in the iovec array"], Normal(Unsigned_Numeric)),
-                    (["offset", "amount of bytes of offset from the beginning of the file"], Normal(Length_Of_Bytes_Specific)),
+                    (["offset", "amount of bytes of offset from the beginning of the file, -1 for the current file offset"], Normal(Length_Of_Bytes_Specific)),
+                    (["offset_high", "upper half of the offset on 32 bit systems, unused on 64 bit systems"], Normal(Unsigned_Numeric)),
                     (["flags", "custom falgs for specific write behaviour"], Normal(General_Flag(P_RW_V2_Flags))),
                 ],
                 (["return value", "on success returns number of bytes written, -1 On error and errno is modified"],Length_Of_Bytes_Specific_Or_Errno)
@@ -181,7 +182,8 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
                     (["fd", "file descriptor of the file to be written into"], Normal(File_Descriptor(""))),
                     (["iovec", "array of iovec structs containing pointer-length pairs of scattered regions to be written"], Normal(Array_Of_Struct)),
                     (["count", "number of iovec structs in the iovec array"], Normal(Unsigned_Numeric)),
-                    (["offset", "amount of bytes of offset from the beginning of the file"], Normal(Length_Of_Bytes_Specific)),
+                    (["offset", "amount of bytes of offset from the beginning of the file, -1 for the current file offset"], Normal(Length_Of_Bytes_Specific)),
+                    (["offset_high", "upper half of the offset on 32 bit systems, unused on 64 bit systems"], Normal(Unsigned_Numeric)),
                     (["flags", "custom falgs for specific write behaviour"], Normal(General_Flag(P_RW_V2_Flags))),
                 ],
                 (["return value", "on success returns number of bytes written, -1 On error and errno is modified"],Length_Of_Bytes_Specific_Or_Errno)
@@ -738,24 +740,62 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
                 (["return value", "0 success. -1 for error and errno modified"], Numeric_Or_Errno)
             )
         ),
-    //      (
-    //          Sysno::mount,
-    //      ),
-    //      (
-    //          Sysno::umount2,
-    //      ),
+        (
+            Sysno::mount,
+            (
+                FileOp,
+                "attach a filesystem, bind a directory tree or change the flags and propagation of an existing mount",
+                &[
+                    (["source", "the device, directory or pseudo filesystem name to mount, can be NULL"], Normal(Address)),
+                    (["target", "the directory to mount onto"], Normal(Pointer_To_Path(""))),
+                    (["filesystemtype", "the type of the filesystem, like ext4, proc or tmpfs, can be NULL"], Normal(Address)),
+                    (["mountflags", "MS_* flags selecting the operation and the mount's flags"], Normal(Unsigned_Numeric)),
+                    (["data", "comma separated filesystem specific options, can be NULL"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
+        (
+            Sysno::umount2,
+            (
+                FileOp,
+                "detach the filesystem mounted at a directory",
+                &[
+                    (["target", "the mount point to detach"], Normal(Pointer_To_Path(""))),
+                    (["flags", "MNT_* flags controlling how the unmount is done"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
     //      (
     //          Sysno::swapon,
     //      ),
     //      (
     //          Sysno::swapoff,
     //      ),
-    //      (
-    //          Sysno::pivot_root,
-    //      ),
-    //      (
-    //          Sysno::chroot,
-    //      ),
+        (
+            Sysno::pivot_root,
+            (
+                FileOp,
+                "make a mount the new root mount of the mount namespace, moving the old root mount under it",
+                &[
+                    (["new_root", "the mount to become the new root"], Normal(Pointer_To_Path(""))),
+                    (["put_old", "the directory under new_root to receive the old root"], Normal(Pointer_To_Path(""))),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
+        (
+            Sysno::chroot,
+            (
+                FileOp,
+                "change the root directory used to resolve absolute paths for the calling process",
+                &[
+                    (["path", "the directory to become the new root directory"], Normal(Pointer_To_Path(""))),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         (
             // file must be writable.
             Sysno::truncate,
@@ -1528,7 +1568,8 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
                 "send a message to another socket",
                 &[
                     (["sockfd", "file descriptor of the sending socket"], Normal(File_Descriptor(""))),
-                    (["buf", "pointer to a buffer containing the message to be sent and the length of the message"], Normal(Pointer_To_Struct)),
+                    (["buf", "pointer to a buffer containing the message to be sent"], Normal(Address)),
+                    (["len", "size of the message in bytes"], Normal(Length_Of_Bytes_Specific)),
                     (["flags", "flags to customize syscall behaviour"], Normal(General_Flag(SocketMessageFlag))),
                     // WILL BE USED if connection-less (like UDP)
                     // WILL BE IGNORED if connection-mode (like TCP, or SEQ) and must be null or 0
@@ -1637,9 +1678,21 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
 
             )
         ),
-        // (
-        //     Sysno::prctl,
-        // ),
+        (
+            Sysno::prctl,
+            (
+                Process,
+                "perform an operation on the calling process or thread",
+                &[
+                    (["option", "the operation to perform"], Normal(Unsigned_Numeric)),
+                    (["arg2", "argument of the operation"], Normal(Unsigned_Numeric)),
+                    (["arg3", "argument of the operation"], Normal(Unsigned_Numeric)),
+                    (["arg4", "argument of the operation"], Normal(Unsigned_Numeric)),
+                    (["arg5", "argument of the operation"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 or a value depending on the operation on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         (
             Sysno::arch_prctl,
             (
@@ -2474,42 +2527,113 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
         // (
         //     Sysno::setsid,
         // ),
-        // (
-        //     Sysno::setreuid,
-        // ),
-        // (
-        //     Sysno::setregid,
-        // ),
+        (
+            Sysno::setreuid,
+            (
+                Process,
+                "set the real and effective user ids of the calling process",
+                &[
+                    (["ruid", "the new real user id, -1 to leave it unchanged"], Normal(User_Group)),
+                    (["euid", "the new effective user id, -1 to leave it unchanged"], Normal(User_Group)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
+        (
+            Sysno::setregid,
+            (
+                Process,
+                "set the real and effective group ids of the calling process",
+                &[
+                    (["rgid", "the new real group id, -1 to leave it unchanged"], Normal(User_Group)),
+                    (["egid", "the new effective group id, -1 to leave it unchanged"], Normal(User_Group)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         // (
         //     Sysno::getgroups,
         // ),
-        // (
-        //     Sysno::setgroups,
-        // ),
-        // (
-        //     Sysno::setresuid,
-        // ),
+        (
+            Sysno::setgroups,
+            (
+                Process,
+                "set the supplementary group ids of the calling process",
+                &[
+                    (["size", "number of groups in the list"], Normal(Unsigned_Numeric)),
+                    (["list", "pointer to the array of group ids"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
+        (
+            Sysno::setresuid,
+            (
+                Process,
+                "set the real, effective and saved user ids of the calling process",
+                &[
+                    (["ruid", "the new real user id, -1 to leave it unchanged"], Normal(User_Group)),
+                    (["euid", "the new effective user id, -1 to leave it unchanged"], Normal(User_Group)),
+                    (["suid", "the new saved set-user-id, -1 to leave it unchanged"], Normal(User_Group)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         // (
         //     Sysno::getresuid,
         // ),
-        // (
-        //     Sysno::setresgid,
-        // ),
+        (
+            Sysno::setresgid,
+            (
+                Process,
+                "set the real, effective and saved group ids of the calling process",
+                &[
+                    (["rgid", "the new real group id, -1 to leave it unchanged"], Normal(User_Group)),
+                    (["egid", "the new effective group id, -1 to leave it unchanged"], Normal(User_Group)),
+                    (["sgid", "the new saved set-group-id, -1 to leave it unchanged"], Normal(User_Group)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         // (
         //     Sysno::getresgid,
         // ),
-        // (
-        //     Sysno::setfsuid,
-        // ),
-        // (
-        //     Sysno::setfsgid,
-        // ),
+        (
+            Sysno::setfsuid,
+            (
+                Process,
+                "set the user id the kernel uses for filesystem permission checks of the calling process",
+                &[
+                    (["fsuid", "the new filesystem user id"], Normal(User_Group)),
+                ],
+                (["return value", "the previous filesystem user id, whether or not the call changed it"], Numeric_Or_Errno)
+            )
+        ),
+        (
+            Sysno::setfsgid,
+            (
+                Process,
+                "set the group id the kernel uses for filesystem permission checks of the calling process",
+                &[
+                    (["fsgid", "the new filesystem group id"], Normal(User_Group)),
+                ],
+                (["return value", "the previous filesystem group id, whether or not the call changed it"], Numeric_Or_Errno)
+            )
+        ),
         // (
         //     Sysno::getsid,
         // ),
-        // (
-        //     Sysno::unshare,
-        // ),
+        (
+            Sysno::unshare,
+            (
+                Process,
+                "move the calling process into new namespaces, or stop sharing parts of its context with other processes",
+                &[
+                    (["flags", "CLONE_* flags selecting what to unshare"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         (
             Sysno::execve,
             (
@@ -2547,33 +2671,102 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
         // (
         //     Sysno::kill,
         // ),
-        // (
-        //     Sysno::shmget,
-        // ),
-        // (
-        //     Sysno::shmat,
-        // ),
-        // (
-        //     Sysno::shmctl,
-        // ),
+        (
+            Sysno::shmget,
+            (
+                Memory,
+                "get or create a System V shared memory segment from its key",
+                &[
+                    (["key", "the key naming the segment, IPC_PRIVATE for a new private one"], Normal(Unsigned_Numeric)),
+                    (["size", "size of the segment when creating it"], Normal(Length_Of_Bytes_Specific)),
+                    (["shmflg", "IPC_CREAT, IPC_EXCL, SHM_HUGETLB and the permissions of a new segment"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "the id of the segment on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
+        (
+            Sysno::shmat,
+            (
+                Memory,
+                "attach a System V shared memory segment to the address space",
+                &[
+                    (["shmid", "the id of the segment"], Normal(Unsigned_Numeric)),
+                    (["shmaddr", "where to attach the segment, NULL lets the kernel choose"], Normal(Address)),
+                    (["shmflg", "SHM_RDONLY, SHM_EXEC, SHM_RND and SHM_REMAP"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "the address the segment was attached at on success, -1 on error, errno modified"], Address_Or_Errno(""))
+            )
+        ),
+        (
+            Sysno::shmctl,
+            (
+                Memory,
+                "control a System V shared memory segment: get or change its information, lock it, or remove it",
+                &[
+                    (["shmid", "the id of the segment"], Normal(Unsigned_Numeric)),
+                    (["cmd", "the IPC_* or SHM_* command"], Normal(Unsigned_Numeric)),
+                    (["buf", "pointer to the shmid_ds read or filled by the command"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 or an index for the information commands on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         // (
         //     Sysno::pause,
         // ),
-        // (
-        //     Sysno::getitimer,
-        // ),
-        // (
-        //     Sysno::alarm,
-        // ),
-        // (
-        //     Sysno::setitimer,
-        // ),
+        (
+            Sysno::getitimer,
+            (
+                Process,
+                "get the time left on one of the interval timers of the process",
+                &[
+                    (["which", "ITIMER_REAL, ITIMER_VIRTUAL or ITIMER_PROF"], Normal(Unsigned_Numeric)),
+                    (["curr_value", "pointer to the itimerval to fill with the time left and the interval"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
+        (
+            Sysno::alarm,
+            (
+                Process,
+                "deliver SIGALRM to the process after a number of seconds, replacing the pending alarm",
+                &[
+                    (["seconds", "seconds until the alarm, 0 cancels the pending alarm"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "the seconds left on the previous alarm, 0 if none was pending"], Always_Successful_Numeric)
+            )
+        ),
+        (
+            Sysno::setitimer,
+            (
+                Process,
+                "arm or disarm one of the interval timers of the process",
+                &[
+                    (["which", "ITIMER_REAL, ITIMER_VIRTUAL or ITIMER_PROF"], Normal(Unsigned_Numeric)),
+                    (["new_value", "pointer to the itimerval with the first expiry and the interval"], Normal(Pointer_To_Struct)),
+                    (["old_value", "pointer to the itimerval receiving the previous setting, can be NULL"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         // (
         //     Sysno::remap_file_pages,
         // ),
-        // (
-        //     Sysno::mq_timedsend,
-        // ),
+        (
+            Sysno::mq_timedsend,
+            (
+                Process,
+                "send a message to a POSIX message queue, giving up at a deadline",
+                &[
+                    (["mqdes", "the message queue descriptor"], Normal(File_Descriptor(""))),
+                    (["msg_ptr", "pointer to the message"], Normal(Address)),
+                    (["msg_len", "size of the message"], Normal(Length_Of_Bytes_Specific)),
+                    (["msg_prio", "priority of the message"], Normal(Unsigned_Numeric)),
+                    (["abs_timeout", "pointer to the absolute CLOCK_REALTIME deadline, NULL waits indefinitely"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
         // (
         //     Sysno::sendmmsg,
         // ),
@@ -2593,45 +2786,146 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
         //     Sysno::iopl,
         // )
 
-        // (
-        //     Sysno::seccomp,
-        // )
-
-        // (
-        //     Sysno::bpf,
-        // )
+        (
+            Sysno::seccomp,
+            (
+                Security,
+                "restrict the syscalls the calling thread can make, usually by installing a classic BPF filter that decides on each syscall",
+                &[
+                    (["operation", "the seccomp operation to perform"], Normal(Unsigned_Numeric)),
+                    (["flags", "flags modifying the operation"], Normal(Unsigned_Numeric)),
+                    (["args", "pointer to the sock_fprog filter program, or the action to check, depending on the operation"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success or a notification file descriptor, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-        // (
-        //     Sysno::semget,
-        // )
+        (
+            Sysno::bpf,
+            (
+                Security,
+                "perform an operation on eBPF maps, programs or links, the command decides what the attributes mean",
+                &[
+                    (["cmd", "the bpf command to perform"], Normal(Unsigned_Numeric)),
+                    (["attr", "pointer to the bpf_attr union holding the attributes of the command"], Normal(Pointer_To_Struct)),
+                    (["size", "size of the bpf_attr union"], Normal(Length_Of_Bytes_Specific)),
+                ],
+                (["return value", "a new file descriptor for commands creating or opening objects, 0 for the others, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-        // (
-        //     Sysno::semop,
-        // )
+        (
+            Sysno::semget,
+            (
+                Process,
+                "get or create a System V semaphore set from its key",
+                &[
+                    (["key", "the key naming the set, IPC_PRIVATE for a new private one"], Normal(Unsigned_Numeric)),
+                    (["nsems", "number of semaphores in the set"], Normal(Unsigned_Numeric)),
+                    (["semflg", "IPC_CREAT, IPC_EXCL and the permissions of a new set"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "the id of the set on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-        // (
-        //     Sysno::semctl,
-        // )
+        (
+            Sysno::semop,
+            (
+                Process,
+                "perform operations on the semaphores of a System V semaphore set, all at once",
+                &[
+                    (["semid", "the id of the set"], Normal(Unsigned_Numeric)),
+                    (["sops", "pointer to the array of sembuf operations"], Normal(Array_Of_Struct)),
+                    (["nsops", "number of operations"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-        // (
-        //     Sysno::shmdt,
-        // )
+        (
+            Sysno::semctl,
+            (
+                Process,
+                "control a System V semaphore set: get or set semaphore values, get or change its information, or remove it",
+                &[
+                    (["semid", "the id of the set"], Normal(Unsigned_Numeric)),
+                    (["semnum", "the semaphore the command is about"], Normal(Unsigned_Numeric)),
+                    (["cmd", "the IPC_* or semaphore command"], Normal(Unsigned_Numeric)),
+                    (["arg", "a value or a pointer depending on the command"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "the value asked for or 0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-        // (
-        //     Sysno::msgget,
-        // )
+        (
+            Sysno::shmdt,
+            (
+                Memory,
+                "detach a System V shared memory segment from the address space",
+                &[
+                    (["shmaddr", "the address the segment is attached at"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-        // (
-        //     Sysno::msgsnd,
-        // )
+        (
+            Sysno::msgget,
+            (
+                Process,
+                "get or create a System V message queue from its key",
+                &[
+                    (["key", "the key naming the queue, IPC_PRIVATE for a new private one"], Normal(Unsigned_Numeric)),
+                    (["msgflg", "IPC_CREAT, IPC_EXCL and the permissions of a new queue"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "the id of the queue on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-        // (
-        //     Sysno::msgrcv,
-        // )
+        (
+            Sysno::msgsnd,
+            (
+                Process,
+                "send a message to a System V message queue",
+                &[
+                    (["msqid", "the id of the queue"], Normal(Unsigned_Numeric)),
+                    (["msgp", "pointer to the message, its type followed by its text"], Normal(Pointer_To_Struct)),
+                    (["msgsz", "size of the text of the message"], Normal(Length_Of_Bytes_Specific)),
+                    (["msgflg", "IPC_NOWAIT to fail instead of waiting when the queue is full"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-        // (
-        //     Sysno::msgctl,
-        // )
+        (
+            Sysno::msgrcv,
+            (
+                Process,
+                "receive a message from a System V message queue",
+                &[
+                    (["msqid", "the id of the queue"], Normal(Unsigned_Numeric)),
+                    (["msgp", "pointer to the buffer receiving the type and the text of the message"], Normal(Pointer_To_Struct)),
+                    (["msgsz", "largest text size the buffer can hold"], Normal(Length_Of_Bytes_Specific)),
+                    (["msgtyp", "which message to receive: 0 for the first, a type, or the negated highest type"], Normal(Numeric)),
+                    (["msgflg", "IPC_NOWAIT, MSG_NOERROR, MSG_EXCEPT and MSG_COPY"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "the size of the received text on success, -1 on error, errno modified"], Length_Of_Bytes_Specific_Or_Errno)
+            )
+        ),
+
+        (
+            Sysno::msgctl,
+            (
+                Process,
+                "control a System V message queue: get or change its information, or remove it",
+                &[
+                    (["msqid", "the id of the queue"], Normal(Unsigned_Numeric)),
+                    (["cmd", "the IPC_* or MSG_* command"], Normal(Unsigned_Numeric)),
+                    (["buf", "pointer to the msqid_ds read or filled by the command"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 or an index for the information commands on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
         // (
         //     Sysno::flock,
@@ -2806,13 +3100,31 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
     //         Sysno::getdents64,
     //         )
 
-    //         (
-    //         Sysno::capget,
-    //         )
+        (
+            Sysno::capget,
+            (
+                Security,
+                "get the capability sets of a thread",
+                &[
+                    (["hdrp", "pointer to the header holding the capability version and the thread id"], Normal(Pointer_To_Struct)),
+                    (["datap", "pointer to the effective, permitted and inheritable sets to fill"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::capset,
-    //         )
+        (
+            Sysno::capset,
+            (
+                Security,
+                "set the capability sets of the calling thread",
+                &[
+                    (["hdrp", "pointer to the header holding the capability version and the thread id"], Normal(Pointer_To_Struct)),
+                    (["datap", "pointer to the new effective, permitted and inheritable sets"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
     //         (
     //         Sysno::utime,
@@ -2882,53 +3194,179 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
     //         Sysno::readahead,
     //         )
 
-    //         (
-    //         Sysno::setxattr,
-    //         )
+        (
+            Sysno::setxattr,
+            (
+                FileOp,
+                "set the value of an extended attribute of a file",
+                &[
+                    (["path", "path of the file"], Normal(Pointer_To_Text(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                    (["value", "the new value, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the value"], Normal(Length_Of_Bytes_Specific)),
+                    (["flags", "XATTR_CREATE to fail if the attribute exists, XATTR_REPLACE to fail if it does not"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::lsetxattr,
-    //         )
+        (
+            Sysno::lsetxattr,
+            (
+                FileOp,
+                "set the value of an extended attribute of a symlink itself",
+                &[
+                    (["path", "path of the file, a trailing symlink is not followed"], Normal(Pointer_To_Text(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                    (["value", "the new value, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the value"], Normal(Length_Of_Bytes_Specific)),
+                    (["flags", "XATTR_CREATE to fail if the attribute exists, XATTR_REPLACE to fail if it does not"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::fsetxattr,
-    //         )
+        (
+            Sysno::fsetxattr,
+            (
+                FileOp,
+                "set the value of an extended attribute of an open file",
+                &[
+                    (["fd", "file descriptor of the file"], Normal(File_Descriptor(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                    (["value", "the new value, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the value"], Normal(Length_Of_Bytes_Specific)),
+                    (["flags", "XATTR_CREATE to fail if the attribute exists, XATTR_REPLACE to fail if it does not"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::getxattr,
-    //         )
+        (
+            Sysno::getxattr,
+            (
+                FileOp,
+                "get the value of an extended attribute of a file",
+                &[
+                    (["path", "path of the file"], Normal(Pointer_To_Text(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                    (["value", "buffer to fill with the value, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the buffer, 0 only asks for the size of the value"], Normal(Length_Of_Bytes_Specific)),
+                ],
+                (["return value", "size of the value on success, -1 on error, errno modified"], Length_Of_Bytes_Specific_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::lgetxattr,
-    //         )
+        (
+            Sysno::lgetxattr,
+            (
+                FileOp,
+                "get the value of an extended attribute of a symlink itself",
+                &[
+                    (["path", "path of the file, a trailing symlink is not followed"], Normal(Pointer_To_Text(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                    (["value", "buffer to fill with the value, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the buffer, 0 only asks for the size of the value"], Normal(Length_Of_Bytes_Specific)),
+                ],
+                (["return value", "size of the value on success, -1 on error, errno modified"], Length_Of_Bytes_Specific_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::fgetxattr,
-    //         )
+        (
+            Sysno::fgetxattr,
+            (
+                FileOp,
+                "get the value of an extended attribute of an open file",
+                &[
+                    (["fd", "file descriptor of the file"], Normal(File_Descriptor(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                    (["value", "buffer to fill with the value, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the buffer, 0 only asks for the size of the value"], Normal(Length_Of_Bytes_Specific)),
+                ],
+                (["return value", "size of the value on success, -1 on error, errno modified"], Length_Of_Bytes_Specific_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::listxattr,
-    //         )
+        (
+            Sysno::listxattr,
+            (
+                FileOp,
+                "list the names of the extended attributes of a file",
+                &[
+                    (["path", "path of the file"], Normal(Pointer_To_Text(""))),
+                    (["list", "buffer to fill with the NUL separated names, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the buffer, 0 only asks for the size of the list"], Normal(Length_Of_Bytes_Specific)),
+                ],
+                (["return value", "size of the list on success, -1 on error, errno modified"], Length_Of_Bytes_Specific_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::llistxattr,
-    //         )
+        (
+            Sysno::llistxattr,
+            (
+                FileOp,
+                "list the names of the extended attributes of a symlink itself",
+                &[
+                    (["path", "path of the file, a trailing symlink is not followed"], Normal(Pointer_To_Text(""))),
+                    (["list", "buffer to fill with the NUL separated names, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the buffer, 0 only asks for the size of the list"], Normal(Length_Of_Bytes_Specific)),
+                ],
+                (["return value", "size of the list on success, -1 on error, errno modified"], Length_Of_Bytes_Specific_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::flistxattr,
-    //         )
+        (
+            Sysno::flistxattr,
+            (
+                FileOp,
+                "list the names of the extended attributes of an open file",
+                &[
+                    (["fd", "file descriptor of the file"], Normal(File_Descriptor(""))),
+                    (["list", "buffer to fill with the NUL separated names, can be NULL when size is 0"], Normal(Address)),
+                    (["size", "size of the buffer, 0 only asks for the size of the list"], Normal(Length_Of_Bytes_Specific)),
+                ],
+                (["return value", "size of the list on success, -1 on error, errno modified"], Length_Of_Bytes_Specific_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::removexattr,
-    //         )
+        (
+            Sysno::removexattr,
+            (
+                FileOp,
+                "remove an extended attribute of a file",
+                &[
+                    (["path", "path of the file"], Normal(Pointer_To_Text(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::lremovexattr,
-    //         )
+        (
+            Sysno::lremovexattr,
+            (
+                FileOp,
+                "remove an extended attribute of a symlink itself",
+                &[
+                    (["path", "path of the file, a trailing symlink is not followed"], Normal(Pointer_To_Text(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::fremovexattr,
-    //         )
+        (
+            Sysno::fremovexattr,
+            (
+                FileOp,
+                "remove an extended attribute of an open file",
+                &[
+                    (["fd", "file descriptor of the file"], Normal(File_Descriptor(""))),
+                    (["name", "name of the attribute, prefixed with its namespace like user. or security."], Normal(Pointer_To_Text(""))),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
     //         (
     //         Sysno::time,
@@ -2970,49 +3408,144 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
     //         Sysno::vmsplice,
     //         )
 
-    //         (
-    //         Sysno::semtimedop,
-    //         )
+        (
+            Sysno::semtimedop,
+            (
+                Process,
+                "perform operations on the semaphores of a System V semaphore set, giving up after a timeout",
+                &[
+                    (["semid", "the id of the set"], Normal(Unsigned_Numeric)),
+                    (["sops", "pointer to the array of sembuf operations"], Normal(Array_Of_Struct)),
+                    (["nsops", "number of operations"], Normal(Unsigned_Numeric)),
+                    (["timeout", "pointer to the relative timeout, NULL waits indefinitely"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
     //         (
     //         Sysno::fadvise64,
     //         )
 
-    //         (
-    //         Sysno::timer_create,
-    //         )
-
-    //         (
-    //         Sysno::timer_settime,
-    //         )
+        (
+            Sysno::timer_create,
+            (
+                Process,
+                "create a POSIX per-process timer",
+                &[
+                    (["clockid", "the clock the timer measures time on"], Normal(Numeric)),
+                    (["sevp", "pointer to the sigevent describing the notification, NULL for SIGALRM"], Normal(Address)),
+                    (["timerid", "pointer receiving the id of the new timer"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::timer_gettime,
-    //         )
+        (
+            Sysno::timer_settime,
+            (
+                Process,
+                "arm or disarm a POSIX per-process timer",
+                &[
+                    (["timerid", "the id of the timer"], Normal(Unsigned_Numeric)),
+                    (["flags", "TIMER_ABSTIME for an absolute first expiry"], Normal(Unsigned_Numeric)),
+                    (["new_value", "pointer to the itimerspec with the first expiry and the interval"], Normal(Pointer_To_Struct)),
+                    (["old_value", "pointer to the itimerspec receiving the previous setting, can be NULL"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::timer_getoverrun,
-    //         )
+        (
+            Sysno::timer_gettime,
+            (
+                Process,
+                "get the time left until a POSIX per-process timer fires, and its interval",
+                &[
+                    (["timerid", "the id of the timer"], Normal(Unsigned_Numeric)),
+                    (["curr_value", "pointer to the itimerspec to fill"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::timer_delete,
-    //         )
+        (
+            Sysno::timer_getoverrun,
+            (
+                Process,
+                "get how many expirations of a POSIX per-process timer were missed while its signal was pending",
+                &[
+                    (["timerid", "the id of the timer"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "the overrun count on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::clock_settime,
-    //         )
+        (
+            Sysno::timer_delete,
+            (
+                Process,
+                "delete a POSIX per-process timer",
+                &[
+                    (["timerid", "the id of the timer"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::clock_gettime,
-    //         )
+        (
+            Sysno::clock_settime,
+            (
+                Process,
+                "set the time of a clock",
+                &[
+                    (["clockid", "the clock to set"], Normal(Numeric)),
+                    (["tp", "pointer to the timespec with the new time"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::clock_getres,
-    //         )
+        (
+            Sysno::clock_gettime,
+            (
+                Process,
+                "get the time of a clock",
+                &[
+                    (["clockid", "the clock to read"], Normal(Numeric)),
+                    (["tp", "pointer to the timespec to fill"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::clock_nanosleep,
-    //         )
+        (
+            Sysno::clock_getres,
+            (
+                Process,
+                "get the resolution of a clock",
+                &[
+                    (["clockid", "the clock"], Normal(Numeric)),
+                    (["res", "pointer to the timespec to fill, can be NULL"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
+
+        (
+            Sysno::clock_nanosleep,
+            (
+                Process,
+                "suspend the calling thread for a duration or until a time, measured on a clock",
+                &[
+                    (["clockid", "the clock to measure the sleep on"], Normal(Numeric)),
+                    (["flags", "TIMER_ABSTIME to sleep until an absolute time"], Normal(Unsigned_Numeric)),
+                    (["request", "pointer to the timespec with the duration or the time"], Normal(Pointer_To_Struct)),
+                    (["remain", "pointer receiving the time left when interrupted, can be NULL"], Normal(Address)),
+                ],
+                (["return value", "0 on success, an error number on failure"], Numeric_Or_Errno)
+            )
+        ),
 
     //         (
     //         Sysno::utimes,
@@ -3030,37 +3563,114 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
     //         Sysno::get_mempolicy,
     //         )
 
-    //         (
-    //         Sysno::mq_open,
-    //         )
+        (
+            Sysno::mq_open,
+            (
+                Process,
+                "open or create a POSIX message queue",
+                &[
+                    (["name", "name of the queue, starting with a slash"], Normal(Pointer_To_Text(""))),
+                    (["oflag", "access mode and O_CREAT, O_EXCL and O_NONBLOCK"], Normal(Unsigned_Numeric)),
+                    (["mode", "permissions of a new queue"], Normal(Unsigned_Numeric)),
+                    (["attr", "pointer to the mq_attr limits of a new queue, NULL for the defaults"], Normal(Address)),
+                ],
+                (["return value", "a message queue descriptor on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    //         (
-    //         Sysno::mq_timedreceive,
-    //         )
+        (
+            Sysno::mq_timedreceive,
+            (
+                Process,
+                "receive the oldest message of the highest priority from a POSIX message queue, giving up at a deadline",
+                &[
+                    (["mqdes", "the message queue descriptor"], Normal(File_Descriptor(""))),
+                    (["msg_ptr", "pointer to the buffer receiving the message"], Normal(Address)),
+                    (["msg_len", "size of the buffer, at least the largest message size of the queue"], Normal(Length_Of_Bytes_Specific)),
+                    (["msg_prio", "pointer receiving the priority of the message, can be NULL"], Normal(Address)),
+                    (["abs_timeout", "pointer to the absolute CLOCK_REALTIME deadline, NULL waits indefinitely"], Normal(Address)),
+                ],
+                (["return value", "the size of the message on success, -1 on error, errno modified"], Length_Of_Bytes_Specific_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::mq_notify,
-    //         )
+        (
+            Sysno::mq_notify,
+            (
+                Process,
+                "register or unregister for a notification when a message arrives on an empty POSIX message queue",
+                &[
+                    (["mqdes", "the message queue descriptor"], Normal(File_Descriptor(""))),
+                    (["sevp", "pointer to the sigevent describing the notification, NULL to unregister"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::mq_getsetattr,
-    //         )
+        (
+            Sysno::mq_getsetattr,
+            (
+                Process,
+                "get the attributes of a POSIX message queue, and optionally change its flags",
+                &[
+                    (["mqdes", "the message queue descriptor"], Normal(File_Descriptor(""))),
+                    (["newattr", "pointer to the mq_attr with the new flags, can be NULL"], Normal(Address)),
+                    (["oldattr", "pointer to the mq_attr receiving the current attributes, can be NULL"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
     //         (
     //         Sysno::kexec_load,
     //         )
 
-    //         (
-    //         Sysno::add_key,
-    //         )
+        (
+            Sysno::add_key,
+            (
+                Security,
+                "create or update a key of the given type and description, and link it into a keyring",
+                &[
+                    (["type", "name of the key type, like user, logon or keyring"], Normal(Pointer_To_Text(""))),
+                    (["description", "description the key can be found by"], Normal(Pointer_To_Text(""))),
+                    (["payload", "data to instantiate the key with"], Normal(Address)),
+                    (["plen", "size of the payload"], Normal(Length_Of_Bytes_Specific)),
+                    (["keyring", "serial of the keyring to link the key into, or one of the KEY_SPEC_* special keyrings"], Normal(Numeric)),
+                ],
+                (["return value", "serial number of the key, -1 on error"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::request_key,
-    //         )
+        (
+            Sysno::request_key,
+            (
+                Security,
+                "search the keyrings of the process for a key, optionally asking user space to create it",
+                &[
+                    (["type", "name of the key type"], Normal(Pointer_To_Text(""))),
+                    (["description", "description of the key"], Normal(Pointer_To_Text(""))),
+                    (["callout_info", "text handed to /sbin/request-key to create a missing key, or NULL"], Normal(Address)),
+                    (["dest_keyring", "keyring to link the found key into, or 0"], Normal(Numeric)),
+                ],
+                (["return value", "serial number of the key, -1 on error"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::keyctl,
-    //         )
+        (
+            Sysno::keyctl,
+            (
+                Security,
+                "manipulate the kernel key management facility",
+                &[
+                    (["operation", "the KEYCTL_* operation to perform"], Normal(Numeric)),
+                    (["arg2", "argument depending on the operation"], Normal(Unsigned_Numeric)),
+                    (["arg3", "argument depending on the operation"], Normal(Unsigned_Numeric)),
+                    (["arg4", "argument depending on the operation"], Normal(Unsigned_Numeric)),
+                    (["arg5", "argument depending on the operation"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "depends on the operation, -1 on error"], Numeric_Or_Errno)
+            )
+        ),
 
     //         (
     //         Sysno::ioprio_set,
@@ -3070,13 +3680,32 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
     //         Sysno::ioprio_get,
     //         )
 
-    //         (
-    //         Sysno::inotify_add_watch,
-    //         )
+        (
+            Sysno::inotify_add_watch,
+            (
+                FileOp,
+                "add or change a watch on a file or directory of an inotify instance",
+                &[
+                    (["fd", "the inotify file descriptor"], Normal(File_Descriptor(""))),
+                    (["pathname", "path of the file or directory to watch"], Normal(Pointer_To_Text(""))),
+                    (["mask", "the IN_* events to watch for and the IN_* watch flags"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "a watch descriptor on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::inotify_rm_watch,
-    //         )
+        (
+            Sysno::inotify_rm_watch,
+            (
+                FileOp,
+                "remove a watch from an inotify instance",
+                &[
+                    (["fd", "the inotify file descriptor"], Normal(File_Descriptor(""))),
+                    (["wd", "the watch descriptor to remove"], Normal(Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
     //         (
     //         Sysno::migrate_pages,
@@ -3102,29 +3731,91 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
     //         Sysno::utimensat,
     //         )
 
-    //         (
-    //         Sysno::timerfd_create,
-    //         )
+        (
+            Sysno::timerfd_create,
+            (
+                Process,
+                "create a timer that notifies through a file descriptor",
+                &[
+                    (["clockid", "the clock the timer measures time on"], Normal(Numeric)),
+                    (["flags", "TFD_NONBLOCK and TFD_CLOEXEC"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "a new file descriptor on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    //         (
-    //         Sysno::timerfd_settime,
-    //         )
+        (
+            Sysno::timerfd_settime,
+            (
+                Process,
+                "arm or disarm a timer file descriptor",
+                &[
+                    (["fd", "the timer file descriptor"], Normal(File_Descriptor(""))),
+                    (["flags", "TFD_TIMER_ABSTIME and TFD_TIMER_CANCEL_ON_SET"], Normal(Unsigned_Numeric)),
+                    (["new_value", "pointer to the itimerspec with the first expiry and the interval"], Normal(Pointer_To_Struct)),
+                    (["old_value", "pointer to the itimerspec receiving the previous setting, can be NULL"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::timerfd_gettime,
-    //         )
+        (
+            Sysno::timerfd_gettime,
+            (
+                Process,
+                "get the time left until a timer file descriptor fires, and its interval",
+                &[
+                    (["fd", "the timer file descriptor"], Normal(File_Descriptor(""))),
+                    (["curr_value", "pointer to the itimerspec to fill"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::perf_event_open,
-    //         )
+        (
+            Sysno::perf_event_open,
+            (
+                CPU,
+                "open a performance monitoring event that counts or samples hardware, software or tracepoint events",
+                &[
+                    (["attr", "pointer to the perf_event_attr describing the event"], Normal(Address)),
+                    (["pid", "thread to watch, 0 for the calling thread, -1 for every process"], Normal(Numeric)),
+                    (["cpu", "CPU to watch, -1 for any CPU"], Normal(Numeric)),
+                    (["group_fd", "event fd leading the group this event joins, -1 to start a new group"], Normal(Numeric)),
+                    (["flags", "PERF_FLAG_* flags"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "file descriptor of the event, -1 on error"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    //         (
-    //         Sysno::fanotify_init,
-    //         )
+        (
+            Sysno::fanotify_init,
+            (
+                FileOp,
+                "create a fanotify group to be notified of, or decide on, accesses to files",
+                &[
+                    (["flags", "the FAN_CLASS_* class of the group and the FAN_* flags"], Normal(Unsigned_Numeric)),
+                    (["event_f_flags", "the open flags of the file descriptors handed out with events"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "a fanotify file descriptor on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    //         (
-    //         Sysno::fanotify_mark,
-    //         )
+        (
+            Sysno::fanotify_mark,
+            (
+                FileOp,
+                "add, remove or flush the marks of a fanotify group on an inode, a mount or a filesystem",
+                &[
+                    (["fanotify_fd", "the fanotify file descriptor"], Normal(File_Descriptor(""))),
+                    (["flags", "FAN_MARK_* operation and flags"], Normal(Unsigned_Numeric)),
+                    (["mask", "the FAN_* events to watch for or ignore"], Normal(Unsigned_Numeric)),
+                    (["dirfd", "anchor directory of a relative pathname, or the object itself when pathname is NULL"], Normal(Numeric)),
+                    (["pathname", "path of the object to mark, can be NULL"], Normal(Address)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
     //         (
     //         Sysno::name_to_handle_at,
@@ -3194,41 +3885,140 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
     //         Sysno::io_pgetevents,
     //         )
 
-    //         (
-    //         Sysno::io_uring_setup,
-    //         )
-
-    //         (
-    //         Sysno::io_uring_enter,
-    //         )
-
-    //         (
-    //         Sysno::io_uring_register,
-    //         )
+        (
+            // the submission and completion rings are shared with the kernel
+            // they are mapped afterwards by calling mmap on the returned file descriptor
+            Sysno::io_uring_setup,
+            (
+                AsyncIO,
+                "create an io_uring instance with a submission queue and a completion queue shared between the kernel and the process",
+                &[
+                    (["entries", "minimum number of entries in the submission queue"], Normal(Unsigned_Numeric)),
+                    // the kernel fills in the ring sizes and the offsets of the ring fields needed to map them
+                    (["p", "pointer to struct containing the setup flags, and filled by the kernel with the ring parameters"], Normal(Pointer_To_Struct)),
+                ],
+                (["return value", "file descriptor of the io_uring instance, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
+        (
+            Sysno::io_uring_enter,
+            (
+                AsyncIO,
+                "submit new operations from the submission queue, and/or wait for operations to complete",
+                &[
+                    (["fd", "file descriptor of the io_uring instance"], Normal(File_Descriptor(""))),
+                    (["to_submit", "number of operations to submit from the submission queue"], Normal(Unsigned_Numeric)),
+                    // only waited for if IORING_ENTER_GETEVENTS is set
+                    (["min_complete", "number of completions to wait for before returning"], Normal(Unsigned_Numeric)),
+                    (["flags", "flags modifying the behavior of the call"], Normal(Unsigned_Numeric)),
+                    // with IORING_ENTER_EXT_ARG this is a pointer to struct io_uring_getevents_arg instead
+                    (["arg", "signal mask to apply while waiting, or extended arguments"], Normal(Pointer_To_Struct)),
+                    (["argsz", "size of arg"], Normal(Length_Of_Bytes_Specific)),
+                ],
+                (["return value", "number of operations submitted, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
+        (
+            Sysno::io_uring_register,
+            (
+                AsyncIO,
+                "register resources (buffers, files, eventfds) with an io_uring instance, or change its settings",
+                &[
+                    (["fd", "file descriptor of the io_uring instance"], Normal(File_Descriptor(""))),
+                    (["opcode", "the registration operation to perform"], Normal(Unsigned_Numeric)),
+                    (["arg", "pointer to the resources being registered, depending on the opcode"], Normal(Pointer_To_Struct)),
+                    (["nr_args", "number of resources in arg"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 or a positive value depending on the opcode on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::open_tree,
-    //         )
+        (
+            Sysno::open_tree,
+            (
+                FileOp,
+                "open a mount, or clone it into a new detached mount",
+                &[
+                    (["dirfd", "file descriptor of the anchor directory"], Normal(File_Descriptor_openat(""))),
+                    (["pathname", "path of the mount"], Normal(Pointer_To_Path(""))),
+                    (["flags", "OPEN_TREE_* and AT_* flags"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "a file descriptor referring to the mount on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    //         (
-    //         Sysno::move_mount,
-    //         )
+        (
+            Sysno::move_mount,
+            (
+                FileOp,
+                "move a mount, or attach a detached mount, to a new place in the mount namespace",
+                &[
+                    (["from_dirfd", "file descriptor of the anchor directory of the mount to move"], Normal(File_Descriptor_openat(""))),
+                    (["from_pathname", "path of the mount to move"], Normal(Pointer_To_Path(""))),
+                    (["to_dirfd", "file descriptor of the anchor directory of the destination"], Normal(File_Descriptor_openat(""))),
+                    (["to_pathname", "path of the destination"], Normal(Pointer_To_Path(""))),
+                    (["flags", "MOVE_MOUNT_* flags"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::fsopen,
-    //         )
+        (
+            Sysno::fsopen,
+            (
+                FileOp,
+                "create a filesystem context to configure a new filesystem with fsconfig",
+                &[
+                    (["fsname", "the type of the filesystem"], Normal(Pointer_To_Text(""))),
+                    (["flags", "FSOPEN_* flags"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "a file descriptor referring to the filesystem context on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    //         (
-    //         Sysno::fsconfig,
-    //         )
+        (
+            Sysno::fsconfig,
+            (
+                FileOp,
+                "set an option of a filesystem context, or create or reconfigure its filesystem",
+                &[
+                    (["fd", "file descriptor of the filesystem context"], Normal(File_Descriptor(""))),
+                    (["cmd", "the FSCONFIG_* command"], Normal(Unsigned_Numeric)),
+                    (["key", "name of the option, NULL for the commands"], Normal(Address)),
+                    (["value", "value of the option, its type depends on the command"], Normal(Pointer_To_Struct)),
+                    (["aux", "extra argument, a file descriptor or an anchor directory depending on the command"], Normal(Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    //         (
-    //         Sysno::fsmount,
-    //         )
+        (
+            Sysno::fsmount,
+            (
+                FileOp,
+                "create a detached mount of the filesystem configured in a filesystem context",
+                &[
+                    (["fd", "file descriptor of the filesystem context"], Normal(File_Descriptor(""))),
+                    (["flags", "FSMOUNT_* flags"], Normal(Unsigned_Numeric)),
+                    (["attr_flags", "MOUNT_ATTR_* flags of the new mount"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "a file descriptor referring to the new mount on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    //         (
-    //         Sysno::fspick,
-    //         )
+        (
+            Sysno::fspick,
+            (
+                FileOp,
+                "create a filesystem context for reconfiguring the filesystem of an existing mount",
+                &[
+                    (["dirfd", "file descriptor of the anchor directory"], Normal(File_Descriptor_openat(""))),
+                    (["pathname", "path of the mount"], Normal(Pointer_To_Path(""))),
+                    (["flags", "FSPICK_* flags"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "a file descriptor referring to the filesystem context on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
     //         (
     //         Sysno::pidfd_open,
@@ -3321,21 +4111,53 @@ pub fn initialize_syscall_map() -> HashMap<Sysno, SysDetails> {
     //     Sysno::restart_syscall
     // )
 
-    // (
-    //     Sysno::mq_unlink
-    // )
+        (
+            Sysno::mq_unlink,
+            (
+                Process,
+                "remove a POSIX message queue name, the queue goes away once every descriptor is closed",
+                &[
+                    (["name", "name of the queue"], Normal(Pointer_To_Text(""))),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
-    // (
-    //     Sysno::inotify_init
-    // )
+        (
+            Sysno::inotify_init,
+            (
+                FileOp,
+                "create an inotify instance",
+                &[
+                ],
+                (["return value", "an inotify file descriptor on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    // (
-    //     Sysno::inotify_init1
-    // )
+        (
+            Sysno::inotify_init1,
+            (
+                FileOp,
+                "create an inotify instance",
+                &[
+                    (["flags", "IN_NONBLOCK and IN_CLOEXEC"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "an inotify file descriptor on success, -1 on error, errno modified"], File_Descriptor_Or_Errno(""))
+            )
+        ),
 
-    // (
-    //     Sysno::setns
-    // )
+        (
+            Sysno::setns,
+            (
+                Process,
+                "move the calling thread into an existing namespace, or into the namespaces of another process",
+                &[
+                    (["fd", "file descriptor of a /proc/<pid>/ns/ file or a pidfd"], Normal(File_Descriptor(""))),
+                    (["nstype", "CLONE_NEW* flags restricting which namespaces may be entered, 0 allows any"], Normal(Unsigned_Numeric)),
+                ],
+                (["return value", "0 on success, -1 on error, errno modified"], Numeric_Or_Errno)
+            )
+        ),
 
     // (
     //     Sysno::userfaultfd
@@ -0,0 +1,96 @@
+// names of the traced threads and of the processes they belong to, used to label the trace lines
+// looked up in /proc once per thread and forgotten when execve, PR_SET_NAME or an exit changes them
+use crate::{syscall_object::SyscallObject, types::ThreadName, utilities::THREAD_NAMES};
+use nix::unistd::Pid;
+use std::collections::hash_map::Entry;
+use syscalls::Sysno;
+
+const PR_SET_NAME: u64 = 15;
+
+fn read_comm(path: &str) -> Option<String> {
+    let comm = std::fs::read_to_string(path).ok()?;
+    Some(comm.trim_end_matches('\n').to_owned())
+}
+
+fn lookup(tid: Pid) -> Option<ThreadName> {
+    let tgid = procfs::process::Process::new(tid.as_raw())
+        .and_then(|process| process.status())
+        .ok()?
+        .tgid;
+    Some(ThreadName {
+        tgid: Pid::from_raw(tgid),
+        process: read_comm(&format!("/proc/{tgid}/comm"))?,
+        thread: read_comm(&format!("/proc/{tgid}/task/{tid}/comm"))?,
+    })
+}
+
+// the id column and the name tag of a thread, "tgid/tid" and "process/thread" when it is not the group leader
+// the thread part of the tag is left out while the thread still carries the name it inherited
+pub fn thread_label(tid: Pid) -> (String, String) {
+    let name = THREAD_NAMES.with_borrow_mut(|names| {
+        if let Entry::Vacant(entry) = names.entry(tid) {
+            entry.insert(lookup(tid)?);
+        }
+        names.get(&tid).cloned()
+    });
+    match name {
+        None => (tid.to_string(), String::new()),
+        Some(name) if name.tgid == tid => (tid.to_string(), name.process),
+        Some(name) if name.thread == name.process => (format!("{}/{tid}", name.tgid), name.process),
+        Some(name) => (
+            format!("{}/{tid}", name.tgid),
+            format!("{}/{}", name.process, name.thread),
+        ),
+    }
+}
+
+// the thread group leader of a thread, which is the process signal dispositions belong to
+pub fn thread_group(tid: Pid) -> Pid {
+    THREAD_NAMES
+        .with_borrow_mut(|names| {
+            if let Entry::Vacant(entry) = names.entry(tid) {
+                entry.insert(lookup(tid)?);
+            }
+            names.get(&tid).map(|name| name.tgid)
+        })
+        .unwrap_or(tid)
+}
+
+fn forget_group(tgid: Pid) {
+    THREAD_NAMES.with_borrow_mut(|names| names.retain(|_, name| name.tgid != tgid));
+}
+
+fn cached_tgid(tid: Pid) -> Pid {
+    THREAD_NAMES.with_borrow(|names| names.get(&tid).map(|name| name.tgid).unwrap_or(tid))
+}
+
+pub fn track_threads(syscall: &SyscallObject) {
+    if syscall.errno.is_some() {
+        return;
+    }
+    match syscall.sysno {
+        // every thread of the process is gone and the comm is now the name of the new program
+        Sysno::execve | Sysno::execveat => {
+            forget_group(cached_tgid(syscall.child));
+            THREAD_NAMES.with_borrow_mut(|names| names.remove(&syscall.child));
+        }
+        // renaming the group leader renames the process for all of its threads
+        Sysno::prctl if syscall.args[0] == PR_SET_NAME => {
+            let tgid = cached_tgid(syscall.child);
+            if tgid == syscall.child {
+                forget_group(tgid);
+            } else {
+                THREAD_NAMES.with_borrow_mut(|names| names.remove(&syscall.child));
+            }
+        }
+        _ => {}
+    }
+}
+
+// thread ids are reused, an exited thread must not lend its name to the next one
+pub fn forget_thread(syscall: &SyscallObject) {
+    if syscall.sysno == Sysno::exit_group {
+        forget_group(cached_tgid(syscall.child));
+    }
+    THREAD_NAMES.with_borrow_mut(|names| names.remove(&syscall.child));
+}
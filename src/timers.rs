@@ -0,0 +1,407 @@
+// decoding of POSIX timers, timerfds, interval timers, alarm and the clock syscalls
+// the clock and notification of a POSIX timer are read back from /proc/<pid>/timers
+use crate::{
+    one_line_formatter::{format_timespec, format_timespec_non_relative},
+    syscall_object::SyscallObject,
+    utilities::x86_signal_to_string,
+};
+use colored::{ColoredString, Colorize};
+use nix::{
+    libc::{itimerspec, itimerval, timespec, O_CLOEXEC, O_NONBLOCK},
+    unistd::Pid,
+};
+use syscalls::Sysno;
+
+const CLOCKS: [&str; 12] = [
+    "CLOCK_REALTIME",
+    "CLOCK_MONOTONIC",
+    "CLOCK_PROCESS_CPUTIME_ID",
+    "CLOCK_THREAD_CPUTIME_ID",
+    "CLOCK_MONOTONIC_RAW",
+    "CLOCK_REALTIME_COARSE",
+    "CLOCK_MONOTONIC_COARSE",
+    "CLOCK_BOOTTIME",
+    "CLOCK_REALTIME_ALARM",
+    "CLOCK_BOOTTIME_ALARM",
+    "CLOCK_SGI_CYCLE",
+    "CLOCK_TAI",
+];
+
+// dynamic clock ids encode a pid or a file descriptor, inverted and shifted past a 3 bit type
+const CLOCKFD: i32 = 3;
+const CPUCLOCK_PERTHREAD: i32 = 4;
+const CPUCLOCK_TYPES: [&str; 3] = ["profiling", "virtual", "scheduler"];
+
+const ITIMERS: [(&str, &str); 3] = [
+    ("ITIMER_REAL", "wall-clock time, notify via SIGALRM"),
+    ("ITIMER_VIRTUAL", "user CPU time, notify via SIGVTALRM"),
+    (
+        "ITIMER_PROF",
+        "user and system CPU time, notify via SIGPROF",
+    ),
+];
+
+// TIMER_ABSTIME and TFD_TIMER_ABSTIME
+const TIMER_ABSTIME: u64 = 1;
+const TFD_TIMER_CANCEL_ON_SET: u64 = 2;
+
+const SIGEV_SIGNAL: i32 = 0;
+const SIGEV_NONE: i32 = 1;
+const SIGEV_THREAD: i32 = 2;
+const SIGEV_THREAD_ID: i32 = 4;
+
+pub fn clock_name(clock: u64) -> String {
+    let clock = clock as i32;
+    if clock >= 0 {
+        return match CLOCKS.get(clock as usize) {
+            Some(name) => name.to_string(),
+            None => format!("the unknown clock {clock}"),
+        };
+    }
+    let owner = !(clock >> 3);
+    if (clock & 7) == CLOCKFD {
+        return format!("the clock of file descriptor {owner}");
+    }
+    let kind = CPUCLOCK_TYPES
+        .get((clock & 3) as usize)
+        .unwrap_or(&"unknown");
+    if (clock & CPUCLOCK_PERTHREAD) != 0 {
+        format!("the {kind} CPU clock of thread {owner}")
+    } else {
+        format!("the {kind} CPU clock of process {owner}")
+    }
+}
+
+fn signal_name(signal: i32) -> String {
+    x86_signal_to_string(signal as u64)
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("signal {signal}"))
+}
+
+// "first after 10000000 nanoseconds, then every 1 seconds"
+fn describe_expiry(
+    value: (i64, i64),
+    interval: (i64, i64),
+    absolute: bool,
+    description: &mut Vec<ColoredString>,
+) {
+    if value == (0, 0) {
+        description.push("disarm the timer".white());
+        return;
+    }
+    if interval == (0, 0) {
+        description.push("arm a one-shot timer: ".white());
+    } else {
+        description.push("arm a periodic timer: first ".white());
+    }
+    if absolute {
+        description.push("at ".white());
+        format_timespec_non_relative(value.0, value.1, description);
+        description.push(" on the clock".white());
+    } else {
+        format_timespec(value.0, value.1, description);
+    }
+    if interval != (0, 0) {
+        description.push(", then every ".white());
+        format_timespec_non_relative(interval.0, interval.1, description);
+    }
+}
+
+// what timer_gettime, timerfd_gettime and getitimer return
+fn describe_remaining(value: (i64, i64), interval: (i64, i64)) -> Vec<ColoredString> {
+    if value == (0, 0) {
+        return vec!["the timer is disarmed".green()];
+    }
+    let mut remaining = vec!["fires next ".green()];
+    format_timespec(value.0, value.1, &mut remaining);
+    if interval != (0, 0) {
+        remaining.push(", then every ".green());
+        format_timespec_non_relative(interval.0, interval.1, &mut remaining);
+    }
+    remaining
+}
+
+// the entry of /proc/<pid>/timers for the timer, as its clock and notification
+fn posix_timer(pid: Pid, timer: i32) -> Option<(String, String)> {
+    let timers = std::fs::read_to_string(format!("/proc/{pid}/timers")).ok()?;
+    let mut lines = timers
+        .lines()
+        .skip_while(|line| *line != format!("ID: {timer}"));
+    lines.next()?;
+    let mut signal = String::new();
+    let mut notify = String::new();
+    let mut clock = String::new();
+    for line in lines.take(3) {
+        if let Some(value) = line.strip_prefix("signal: ") {
+            signal = value.split('/').next().unwrap_or_default().to_owned();
+        } else if let Some(value) = line.strip_prefix("notify: ") {
+            notify = value.to_owned();
+        } else if let Some(value) = line.strip_prefix("ClockID: ") {
+            clock = value.to_owned();
+        }
+    }
+    let clock = clock_name(clock.parse::<i32>().ok()? as u64);
+    let signal = signal_name(signal.parse::<i32>().unwrap_or(0));
+    // "signal/tid.42": the kind of notification, then whether it targets a thread or the process
+    let (kind, target) = notify.split_once('/').unwrap_or((notify.as_str(), ""));
+    let notification = match (kind, target.split_once('.')) {
+        ("none", _) => "without notification".to_owned(),
+        ("thread", _) => "notify by running a function in a new thread".to_owned(),
+        (_, Some(("tid", thread))) => format!("notify thread {thread} via {signal}"),
+        _ => format!("notify via {signal}"),
+    };
+    Some((clock, notification))
+}
+
+impl SyscallObject {
+    fn read_itimerspec(&self, address: u64) -> Option<((i64, i64), (i64, i64))> {
+        let timer = self.read_struct::<itimerspec>(address)?;
+        Some((
+            (timer.it_value.tv_sec, timer.it_value.tv_nsec),
+            (timer.it_interval.tv_sec, timer.it_interval.tv_nsec),
+        ))
+    }
+
+    // interval timers count in microseconds, shown the same way as the nanosecond ones
+    fn read_itimerval(&self, address: u64) -> Option<((i64, i64), (i64, i64))> {
+        let timer = self.read_struct::<itimerval>(address)?;
+        Some((
+            (
+                timer.it_value.tv_sec,
+                timer.it_value.tv_usec.checked_mul(1_000)?,
+            ),
+            (
+                timer.it_interval.tv_sec,
+                timer.it_interval.tv_usec.checked_mul(1_000)?,
+            ),
+        ))
+    }
+
+    fn read_timespec(&self, address: u64) -> Option<(i64, i64)> {
+        let time = self.read_struct::<timespec>(address)?;
+        Some((time.tv_sec, time.tv_nsec))
+    }
+
+    // the notification of timer_create, a NULL sigevent means SIGALRM to the process
+    fn timer_notification(&self) -> String {
+        if self.args[1] == 0 {
+            return "notify via SIGALRM".to_owned();
+        }
+        let Some(event) = SyscallObject::read_bytes::<20>(self.args[1] as usize, self.child) else {
+            return "with an unreadable notification".to_owned();
+        };
+        let signal = i32::from_le_bytes(event[8..12].try_into().unwrap());
+        let notify = i32::from_le_bytes(event[12..16].try_into().unwrap());
+        let thread = i32::from_le_bytes(event[16..20].try_into().unwrap());
+        match notify {
+            SIGEV_NONE => "without notification".to_owned(),
+            SIGEV_SIGNAL => format!("notify via {}", signal_name(signal)),
+            SIGEV_THREAD => "notify by running a function in a new thread".to_owned(),
+            SIGEV_THREAD_ID => format!("notify thread {thread} via {}", signal_name(signal)),
+            _ => format!("with the unknown notification {notify}"),
+        }
+    }
+
+    fn timer_descriptor(&self) -> ColoredString {
+        SyscallObject::style_file_descriptor(self.args[0], self.child)
+            .unwrap_or_else(|| self.args[0].to_string())
+            .yellow()
+    }
+
+    fn itimer(&self) -> (&'static str, &'static str) {
+        *ITIMERS
+            .get(self.args[0] as usize)
+            .unwrap_or(&("an unknown interval timer", "an unknown clock"))
+    }
+
+    pub(crate) fn timer_intent(&self) -> Vec<ColoredString> {
+        let mut intent = vec![];
+        match self.sysno {
+            Sysno::timer_create => {
+                intent.push("create a timer on ".white());
+                intent.push(clock_name(self.args[0]).yellow());
+                intent.push(", ".white());
+                intent.push(self.timer_notification().yellow());
+            }
+            Sysno::timer_settime | Sysno::timerfd_settime => {
+                let absolute = (self.args[1] & TIMER_ABSTIME) != 0;
+                match self.read_itimerspec(self.args[2]) {
+                    Some((value, interval)) => {
+                        describe_expiry(value, interval, absolute, &mut intent)
+                    }
+                    None => intent.push("arm the timer with an unreadable setting".white()),
+                }
+                if self.sysno == Sysno::timerfd_settime {
+                    intent.push(" (".white());
+                    intent.push(self.timer_descriptor());
+                    intent.push(")".white());
+                    if (self.args[1] & TFD_TIMER_CANCEL_ON_SET) != 0 {
+                        intent.push(", cancelled if the realtime clock is set".white());
+                    }
+                } else {
+                    intent.push(" (timer ".white());
+                    intent.push(self.args[0].to_string().yellow());
+                    if let Some((clock, notification)) =
+                        posix_timer(self.child, self.args[0] as i32)
+                    {
+                        intent.push(" on ".white());
+                        intent.push(clock.yellow());
+                        intent.push(", ".white());
+                        intent.push(notification.yellow());
+                    }
+                    intent.push(")".white());
+                }
+            }
+            Sysno::timer_gettime => {
+                intent.push("get the time until the timer ".white());
+                intent.push(self.args[0].to_string().yellow());
+                intent.push(" fires next".white());
+            }
+            Sysno::timerfd_gettime => {
+                intent.push("get the time until the timer ".white());
+                intent.push(self.timer_descriptor());
+                intent.push(" fires next".white());
+            }
+            Sysno::timer_getoverrun => {
+                intent.push("get how many expirations of the timer ".white());
+                intent.push(self.args[0].to_string().yellow());
+                intent.push(" were missed while its last signal was pending".white());
+            }
+            Sysno::timer_delete => {
+                intent.push("delete the timer ".white());
+                intent.push(self.args[0].to_string().yellow());
+            }
+            Sysno::timerfd_create => {
+                intent.push("create a timer file descriptor on ".white());
+                intent.push(clock_name(self.args[0]).yellow());
+                let flags = self.args[1] as i32;
+                if (flags & O_NONBLOCK) != 0 {
+                    intent.push(", non-blocking".white());
+                }
+                if (flags & O_CLOEXEC) != 0 {
+                    intent.push(", closed on exec".white());
+                }
+            }
+            Sysno::clock_gettime => {
+                intent.push("get the time of ".white());
+                intent.push(clock_name(self.args[0]).yellow());
+            }
+            Sysno::clock_getres => {
+                intent.push("get the resolution of ".white());
+                intent.push(clock_name(self.args[0]).yellow());
+            }
+            Sysno::clock_settime => {
+                intent.push("set ".white());
+                intent.push(clock_name(self.args[0]).yellow());
+                if let Some((seconds, nanoseconds)) = self.read_timespec(self.args[1]) {
+                    intent.push(" to ".white());
+                    format_timespec_non_relative(seconds, nanoseconds, &mut intent);
+                }
+            }
+            Sysno::clock_nanosleep => {
+                let time = self.read_timespec(self.args[2]).unwrap_or((0, 0));
+                if (self.args[1] & TIMER_ABSTIME) != 0 {
+                    intent.push("sleep until ".white());
+                    format_timespec_non_relative(time.0, time.1, &mut intent);
+                    intent.push(" on ".white());
+                } else {
+                    intent.push("sleep for ".white());
+                    format_timespec_non_relative(time.0, time.1, &mut intent);
+                    intent.push(" measured on ".white());
+                }
+                intent.push(clock_name(self.args[0]).yellow());
+            }
+            Sysno::setitimer => {
+                let (name, clock) = self.itimer();
+                match self.read_itimerval(self.args[1]) {
+                    Some((value, interval)) => describe_expiry(value, interval, false, &mut intent),
+                    None => intent.push("arm the timer with an unreadable setting".white()),
+                }
+                intent.push(" (".white());
+                intent.push(name.yellow());
+                intent.push(": ".white());
+                intent.push(clock.yellow());
+                intent.push(")".white());
+            }
+            Sysno::getitimer => {
+                let (name, clock) = self.itimer();
+                intent.push("get the time until ".white());
+                intent.push(name.yellow());
+                intent.push(" fires next (".white());
+                intent.push(clock.yellow());
+                intent.push(")".white());
+            }
+            _ => {
+                if self.args[0] == 0 {
+                    intent.push("cancel the pending alarm".white());
+                } else {
+                    intent.push("deliver SIGALRM ".white());
+                    format_timespec(self.args[0] as i64, 0, &mut intent);
+                    intent.push(", replacing any pending alarm".white());
+                }
+            }
+        }
+        intent
+    }
+
+    pub(crate) fn timer_result(&self) -> Vec<ColoredString> {
+        let value = self.result.0.unwrap_or(0);
+        match self.sysno {
+            Sysno::timer_create => match self.read_struct::<i32>(self.args[2]) {
+                Some(timer) => vec!["timer ".green(), timer.to_string().yellow()],
+                None => vec!["successful".green()],
+            },
+            Sysno::timerfd_create => vec!["timer fd ".green(), value.to_string().yellow()],
+            Sysno::timer_settime | Sysno::timerfd_settime => {
+                match self.read_itimerspec(self.args[3]) {
+                    Some((value, interval)) if value != (0, 0) => {
+                        let mut result = vec!["successful, the previous setting ".green()];
+                        result.extend(describe_remaining(value, interval));
+                        result
+                    }
+                    _ => vec!["successful".green()],
+                }
+            }
+            Sysno::timer_gettime | Sysno::timerfd_gettime => {
+                match self.read_itimerspec(self.args[1]) {
+                    Some((value, interval)) => describe_remaining(value, interval),
+                    None => vec!["successful".green()],
+                }
+            }
+            Sysno::timer_getoverrun => {
+                vec![value.to_string().yellow(), " expirations missed".green()]
+            }
+            Sysno::clock_gettime | Sysno::clock_getres => match self.read_timespec(self.args[1]) {
+                Some((seconds, nanoseconds)) => {
+                    let mut result = vec![];
+                    format_timespec_non_relative(seconds, nanoseconds, &mut result);
+                    result
+                }
+                None => vec!["successful".green()],
+            },
+            Sysno::setitimer => match self.read_itimerval(self.args[2]) {
+                Some((value, interval)) if value != (0, 0) => {
+                    let mut result = vec!["successful, the previous setting ".green()];
+                    result.extend(describe_remaining(value, interval));
+                    result
+                }
+                _ => vec!["successful".green()],
+            },
+            Sysno::getitimer => match self.read_itimerval(self.args[1]) {
+                Some((value, interval)) => describe_remaining(value, interval),
+                None => vec!["successful".green()],
+            },
+            Sysno::alarm => {
+                if value == 0 {
+                    vec!["no alarm was pending".green()]
+                } else {
+                    let mut result = vec!["the previous alarm had ".green()];
+                    format_timespec_non_relative(value as i64, 0, &mut result);
+                    result.push(" left".green());
+                    result
+                }
+            }
+            _ => vec!["successful".green()],
+        }
+    }
+}
@@ -332,3 +332,159 @@ pub enum LandlockCreateFlags {
 pub enum LandlockRuleTypeFlags {
     LANDLOCK_RULE_PATH_BENEATH = 1,
 }
+
+// consecutive failed lookups of the same file name across different locations
+// (dynamic loader search paths, config lookups, PATH searches)
+#[derive(Clone, Debug)]
+pub struct ProbeRun {
+    pub pid: nix::unistd::Pid,
+    pub sysno: syscalls::Sysno,
+    pub category: Category,
+    pub file_name: String,
+    pub locations: Vec<std::path::PathBuf>,
+    // the rendered line of the first lookup, printed as is when the run has no followers
+    pub first_line: String,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IoSqringOffsets {
+    pub head: u32,
+    pub tail: u32,
+    pub ring_mask: u32,
+    pub ring_entries: u32,
+    pub flags: u32,
+    pub dropped: u32,
+    pub array: u32,
+    pub resv1: u32,
+    pub user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IoCqringOffsets {
+    pub head: u32,
+    pub tail: u32,
+    pub ring_mask: u32,
+    pub ring_entries: u32,
+    pub overflow: u32,
+    pub cqes: u32,
+    pub flags: u32,
+    pub resv1: u32,
+    pub user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IoUringParams {
+    pub sq_entries: u32,
+    pub cq_entries: u32,
+    pub flags: u32,
+    pub sq_thread_cpu: u32,
+    pub sq_thread_idle: u32,
+    pub features: u32,
+    pub wq_fd: u32,
+    pub resv: [u32; 3],
+    pub sq_off: IoSqringOffsets,
+    pub cq_off: IoCqringOffsets,
+}
+
+// an io_uring instance of a tracee, and where its rings were mapped in the tracee's memory
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IoUringRings {
+    pub params: IoUringParams,
+    pub sq_ring: Option<u64>,
+    pub cq_ring: Option<u64>,
+    pub sqes: Option<u64>,
+    // the completions the latest io_uring_enter found, everything before the tail is reported by it
+    pub unreported_cq_head: u32,
+    pub reported_cq_tail: u32,
+}
+
+// an instruction of a classic BPF program, seccomp filters are written in it
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SockFilter {
+    pub code: u16,
+    pub jt: u8,
+    pub jf: u8,
+    pub k: u32,
+}
+
+// a change the tracee made to its mount namespace, in the order they were made
+#[derive(Clone, Debug)]
+pub struct MountChange {
+    pub target: std::path::PathBuf,
+    pub description: String,
+}
+
+// a file descriptor, or the file it refers to, picked with --dump-read or --dump-write
+#[derive(Clone, Debug)]
+pub enum DumpTarget {
+    Fd(i32),
+    Path(std::path::PathBuf),
+}
+
+// a file descriptor in the interest set of an epoll instance, with the file it referred to when added
+// closed is set when the fd was closed while a duplicate kept the registration alive
+#[derive(Clone, Debug)]
+pub struct EpollRegistration {
+    pub target: String,
+    pub events: u32,
+    pub data: u64,
+    pub closed: bool,
+}
+
+// how a process handles a signal, as installed with rt_sigaction
+// handler is 0 for the default action and 1 for ignoring the signal, symbol names any other handler
+#[derive(Clone, Debug)]
+pub struct SignalDisposition {
+    pub handler: u64,
+    pub symbol: String,
+    pub flags: u64,
+    pub mask: u64,
+}
+
+// what symbolizing an address in an ELF file needs, parsed once per file
+// identity is the (inode, mtime seconds, mtime nanoseconds) the file had when it was parsed
+// segments are (file offset, size, virtual address), functions are (start, end, name) in lookup order
+#[derive(Clone, Debug, Default)]
+pub struct ElfSymbols {
+    pub identity: (u64, i64, i64),
+    pub segments: Vec<(u64, u64, u64)>,
+    pub functions: Vec<(u64, u64, String)>,
+}
+
+// the thread group a traced thread belongs to, with the comm of the group leader and of the thread
+#[derive(Clone, Debug)]
+pub struct ThreadName {
+    pub tgid: nix::unistd::Pid,
+    pub process: String,
+    pub thread: String,
+}
+
+// what a filesystem context or detached mount file descriptor will mount once attached
+#[derive(Clone, Debug, Default)]
+pub struct MountHandle {
+    pub description: String,
+    pub options: Vec<String>,
+}
+
+// the namespaces and cgroup a tracee was last seen in, read from /proc/<pid>/ns and /proc/<pid>/cgroup
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProcessNamespaces {
+    pub namespaces: Vec<(&'static str, String)>,
+    pub cgroup: String,
+}
+
+// the ids, groups and capabilities of a tracee, taken before a syscall that changes them
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Credentials {
+    // real, effective, saved and filesystem
+    pub uids: [u32; 4],
+    pub gids: [u32; 4],
+    pub groups: Vec<u32>,
+    pub effective: u64,
+    pub permitted: u64,
+    pub inheritable: u64,
+}
@@ -1,11 +1,21 @@
-use crate::{syscalls_map::initialize_syscall_map, types::SysDetails};
+use crate::{
+    buffers::{dump_target, HEX_ALL, HEX_NON_PRINTABLE},
+    syscalls_map::initialize_syscall_map,
+    types::{
+        Credentials, DumpTarget, ElfSymbols, EpollRegistration, IoUringRings, MountChange,
+        MountHandle, ProbeRun, ProcessNamespaces, SignalDisposition, SockFilter, SysDetails,
+        ThreadName,
+    },
+};
 use lazy_static::lazy_static;
 use nix::{errno::Errno, libc::__errno_location, unistd::Pid};
 use phf::phf_set;
 use procfs::process::{MMapPath, MemoryMap};
 use std::{
     cell::{Cell, RefCell},
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    rc::Rc,
     time::Duration,
 };
 use syscalls::Sysno;
@@ -40,12 +50,37 @@ thread_local! {
     pub static INTENT: Cell<bool> = Cell::new(true);
     pub static SUMMARY: Cell<bool> = Cell::new(false);
     pub static STRING_LIMIT: Cell<usize> = Cell::new(36);
+    pub static HEX_MODE: Cell<u8> = Cell::new(0);
     pub static FOLLOW_FORKS: Cell<bool> = Cell::new(false);
     pub static QUIET: Cell<bool> = Cell::new(false);
     pub static FAILED_ONLY: Cell<bool> = Cell::new(false);
+    pub static BPF_DISASSEMBLY: Cell<bool> = Cell::new(false);
+    pub static SHOW_KEY_PAYLOADS: Cell<bool> = Cell::new(false);
+    pub static EPOLL_STATE: Cell<bool> = Cell::new(false);
     pub static ATTACH: Cell<(bool,Option<usize>)> = Cell::new((false,None));
     pub static OUTPUT: RefCell<HashMap<Sysno, (usize, Duration)>> = RefCell::new(HashMap::new());
     pub static OUTPUT_FOLLOW_FORKS: RefCell<HashMap<Sysno, usize>> = RefCell::new(HashMap::new());
+    pub static LANDLOCK_POLICY: RefCell<Option<String>> = RefCell::new(None);
+    pub static LANDLOCK_ACCESSES: RefCell<BTreeMap<PathBuf, u64>> = RefCell::new(BTreeMap::new());
+    pub static LANDLOCK_EXISTING_FILES: RefCell<HashSet<Pid>> = RefCell::new(HashSet::new());
+    pub static PROBE_RUN: RefCell<Option<ProbeRun>> = RefCell::new(None);
+    pub static IO_URINGS: RefCell<HashMap<(Pid, i32), IoUringRings>> = RefCell::new(HashMap::new());
+    pub static SECCOMP_FILTERS: RefCell<HashMap<Pid, Vec<Vec<SockFilter>>>> = RefCell::new(HashMap::new());
+    pub static THREAD_NAMES: RefCell<HashMap<Pid, ThreadName>> = RefCell::new(HashMap::new());
+    pub static DUMP_READ: RefCell<Vec<DumpTarget>> = RefCell::new(Vec::new());
+    pub static DUMP_WRITE: RefCell<Vec<DumpTarget>> = RefCell::new(Vec::new());
+    pub static MOUNT_CHANGES: RefCell<Vec<MountChange>> = RefCell::new(Vec::new());
+    pub static MOUNT_HANDLES: RefCell<HashMap<(Pid, i32), MountHandle>> = RefCell::new(HashMap::new());
+    pub static NAMESPACES: RefCell<HashMap<Pid, ProcessNamespaces>> = RefCell::new(HashMap::new());
+    pub static NAMESPACE_TRANSITIONS: RefCell<HashMap<Pid, (Pid, ProcessNamespaces, ProcessNamespaces)>> = RefCell::new(HashMap::new());
+    pub static CGROUP_FILES: RefCell<HashSet<(Pid, i32)>> = RefCell::new(HashSet::new());
+    pub static CREDENTIALS: RefCell<HashMap<Pid, Credentials>> = RefCell::new(HashMap::new());
+    pub static EPOLL_INTEREST: RefCell<HashMap<(Pid, i32), BTreeMap<i32, EpollRegistration>>> = RefCell::new(HashMap::new());
+    pub static SIGNAL_DISPOSITIONS: RefCell<HashMap<Pid, BTreeMap<u64, SignalDisposition>>> = RefCell::new(HashMap::new());
+    pub static BLOCKED_SIGNALS: RefCell<HashMap<Pid, u64>> = RefCell::new(HashMap::new());
+    pub static ELF_SYMBOLS: RefCell<HashMap<PathBuf, Rc<ElfSymbols>>> = RefCell::new(HashMap::new());
+    pub static INOTIFY_WATCHES: RefCell<HashMap<(Pid, i32), HashMap<i32, PathBuf>>> = RefCell::new(HashMap::new());
+    pub static UNWATCHED_PATHS: RefCell<HashMap<(Pid, i32), HashMap<i32, PathBuf>>> = RefCell::new(HashMap::new());
     // TODO! Time blocks feature
     // pub static TIME_BLOCKS: Cell<bool> = Cell::new(false);
 }
@@ -79,6 +114,15 @@ Options:
   -f, --follow-forks                 trace child processes when traced programs create them
   -z, --failed-only                  only print failed syscalls	
   -q, --mute-stdout                  mute the traced program's std output
+  -s, --string-limit <n>             show at most <n> bytes of strings and data buffers (default 36)
+  -x                                 show strings holding non-printable bytes in hex
+  -xx                                show all strings in hex
+      --dump-read=<fd|path>          print a full hex and text dump of the data read from <fd> or <path>
+      --dump-write=<fd|path>         print a full hex and text dump of the data written to <fd> or <path>
+      --landlock-policy <file>       write a landlock ruleset allowing the observed file accesses to <file>
+      --bpf-disassembly              disassemble the eBPF programs loaded with the bpf syscall
+      --show-key-payloads            show the key payloads passed to and read from the keyring syscalls
+      --epoll-state                  print the interest set of every epoll instance at the end of tracing
   -h, --help                         print help
   -v, --version                      print version
                 ");
@@ -163,6 +207,64 @@ Options:
                 let _ = args.next().unwrap();
                 QUIET.set(true);
             }
+            "--landlock-policy" => {
+                let _ = args.next().unwrap();
+                match args.next() {
+                    Some(policy_path) => {
+                        LANDLOCK_POLICY.set(Some(policy_path));
+                    }
+                    None => {
+                        eprintln!("Usage: landlock policy file path is missing\n");
+                        std::process::exit(100);
+                    }
+                }
+            }
+            "-s" | "--string-limit" => {
+                let _ = args.next().unwrap();
+                match args.next().map(|limit| limit.parse::<usize>()) {
+                    Some(Ok(limit)) => {
+                        STRING_LIMIT.set(limit);
+                    }
+                    _ => {
+                        eprintln!("Usage: string limit is not a valid number\n");
+                        std::process::exit(100);
+                    }
+                }
+            }
+            "-x" => {
+                let _ = args.next().unwrap();
+                HEX_MODE.set(HEX_NON_PRINTABLE);
+            }
+            "-xx" => {
+                let _ = args.next().unwrap();
+                HEX_MODE.set(HEX_ALL);
+            }
+            dump if dump.starts_with("--dump-read=") || dump.starts_with("--dump-write=") => {
+                let dump = args.next().unwrap();
+                let (option, target) = dump.split_once('=').unwrap();
+                if target.is_empty() {
+                    eprintln!("Usage: {option} needs a file descriptor or a path\n");
+                    std::process::exit(100);
+                }
+                let targets = if option == "--dump-read" {
+                    &DUMP_READ
+                } else {
+                    &DUMP_WRITE
+                };
+                targets.with_borrow_mut(|targets| targets.push(dump_target(target)));
+            }
+            "--bpf-disassembly" => {
+                let _ = args.next().unwrap();
+                BPF_DISASSEMBLY.set(true);
+            }
+            "--show-key-payloads" => {
+                let _ = args.next().unwrap();
+                SHOW_KEY_PAYLOADS.set(true);
+            }
+            "--epoll-state" => {
+                let _ = args.next().unwrap();
+                EPOLL_STATE.set(true);
+            }
             _ => break,
         }
     }
@@ -194,6 +296,23 @@ pub fn where_in_childs_memory(child: Pid, address: u64) -> Option<MemoryMap> {
         .find(|x| (address >= x.address.0) && (address <= x.address.1))
 }
 
+// resolve a path the way the kernel would for the child, relative to its own cwd or to dirfd
+pub fn resolve_child_path(child: Pid, dirfd: i32, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    let anchor = if dirfd == nix::libc::AT_FDCWD {
+        std::fs::read_link(format!("/proc/{child}/cwd"))
+    } else {
+        std::fs::read_link(format!("/proc/{child}/fd/{dirfd}"))
+    };
+    match anchor {
+        Ok(anchor) => anchor.join(path),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
 pub fn get_child_memory_break(child: Pid) -> (usize, (u64, u64)) {
     let ptraced_process = procfs::process::Process::new(i32::from(child)).unwrap();
     let stat = ptraced_process.stat().unwrap();
@@ -276,6 +395,53 @@ pub fn x86_signal_to_string(signum: u64) -> Option<&'static str> {
         _ => Some("SIGSYS/SIGUNUSED"),
     }
 }
+pub fn capability_to_string(capability: u64) -> Option<&'static str> {
+    match capability {
+        0 => Some("CAP_CHOWN"),
+        1 => Some("CAP_DAC_OVERRIDE"),
+        2 => Some("CAP_DAC_READ_SEARCH"),
+        3 => Some("CAP_FOWNER"),
+        4 => Some("CAP_FSETID"),
+        5 => Some("CAP_KILL"),
+        6 => Some("CAP_SETGID"),
+        7 => Some("CAP_SETUID"),
+        8 => Some("CAP_SETPCAP"),
+        9 => Some("CAP_LINUX_IMMUTABLE"),
+        10 => Some("CAP_NET_BIND_SERVICE"),
+        11 => Some("CAP_NET_BROADCAST"),
+        12 => Some("CAP_NET_ADMIN"),
+        13 => Some("CAP_NET_RAW"),
+        14 => Some("CAP_IPC_LOCK"),
+        15 => Some("CAP_IPC_OWNER"),
+        16 => Some("CAP_SYS_MODULE"),
+        17 => Some("CAP_SYS_RAWIO"),
+        18 => Some("CAP_SYS_CHROOT"),
+        19 => Some("CAP_SYS_PTRACE"),
+        20 => Some("CAP_SYS_PACCT"),
+        21 => Some("CAP_SYS_ADMIN"),
+        22 => Some("CAP_SYS_BOOT"),
+        23 => Some("CAP_SYS_NICE"),
+        24 => Some("CAP_SYS_RESOURCE"),
+        25 => Some("CAP_SYS_TIME"),
+        26 => Some("CAP_SYS_TTY_CONFIG"),
+        27 => Some("CAP_MKNOD"),
+        28 => Some("CAP_LEASE"),
+        29 => Some("CAP_AUDIT_WRITE"),
+        30 => Some("CAP_AUDIT_CONTROL"),
+        31 => Some("CAP_SETFCAP"),
+        32 => Some("CAP_MAC_OVERRIDE"),
+        33 => Some("CAP_MAC_ADMIN"),
+        34 => Some("CAP_SYSLOG"),
+        35 => Some("CAP_WAKE_ALARM"),
+        36 => Some("CAP_BLOCK_SUSPEND"),
+        37 => Some("CAP_AUDIT_READ"),
+        38 => Some("CAP_PERFMON"),
+        39 => Some("CAP_BPF"),
+        40 => Some("CAP_CHECKPOINT_RESTORE"),
+        _ => None,
+    }
+}
+
 pub fn errno_to_string(errno: Errno) -> &'static str {
     match errno {
         Errno::EPERM => "Operation not permitted",
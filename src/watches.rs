@@ -0,0 +1,565 @@
+// decoding of inotify and fanotify: the watches added, and the events later read from their fds
+// the path of every inotify watch descriptor is recorded since the events only carry the descriptor
+use crate::{
+    syscall_object::SyscallObject,
+    threads::thread_group,
+    utilities::{resolve_child_path, INOTIFY_WATCHES, UNWATCHED_PATHS},
+};
+use colored::{ColoredString, Colorize};
+use nix::{
+    libc::{AT_FDCWD, O_CLOEXEC, O_NONBLOCK},
+    unistd::Pid,
+};
+use std::path::PathBuf;
+use syscalls::Sysno;
+
+// the event bits inotify and fanotify share, as what is watched for and as what happened
+const EVENTS: [(u64, &str, &str); 12] = [
+    (0x0000_0001, "reads", "was read"),
+    (0x0000_0002, "modifications", "was modified"),
+    (0x0000_0004, "metadata changes", "had its metadata changed"),
+    (
+        0x0000_0008,
+        "closes after writing",
+        "was closed after writing",
+    ),
+    (
+        0x0000_0010,
+        "closes without writing",
+        "was closed without writing",
+    ),
+    (0x0000_0020, "opens", "was opened"),
+    (0x0000_0040, "moves away", "was moved away"),
+    (0x0000_0080, "moves in", "was moved in"),
+    (0x0000_0100, "creations", "was created"),
+    (0x0000_0200, "deletions", "was deleted"),
+    (0x0000_0400, "its own deletion", "was deleted itself"),
+    (0x0000_0800, "its own move", "was moved itself"),
+];
+const ALL_EVENTS: u64 = 0xfff;
+
+const INOTIFY_ONLY_EVENTS: [(u64, &str, &str); 3] = [
+    (
+        0x0000_2000,
+        "the unmount of its filesystem",
+        "had its filesystem unmounted",
+    ),
+    (
+        0x0000_4000,
+        "queue overflows",
+        "lost events, the queue overflowed",
+    ),
+    (
+        0x0000_8000,
+        "the removal of the watch",
+        "is no longer watched",
+    ),
+];
+const IN_IGNORED: u64 = 0x0000_8000;
+const IN_ISDIR: u64 = 0x4000_0000;
+
+const INOTIFY_WATCH_FLAGS: [(u64, &str); 6] = [
+    (0x0100_0000, "only if it is a directory"),
+    (0x0200_0000, "without following a trailing symlink"),
+    (0x0400_0000, "ignoring children once they are unlinked"),
+    (0x1000_0000, "failing if it is already watched"),
+    (0x2000_0000, "adding to the events already watched"),
+    (0x8000_0000, "only for the next event"),
+];
+
+const FANOTIFY_ONLY_EVENTS: [(u64, &str, &str); 8] = [
+    (0x0000_1000, "executions", "was opened for execution"),
+    (
+        0x0000_4000,
+        "queue overflows",
+        "lost events, the queue overflowed",
+    ),
+    (0x0000_8000, "filesystem errors", "had a filesystem error"),
+    (
+        0x0001_0000,
+        "permission to open",
+        "asks for permission to be opened",
+    ),
+    (
+        0x0002_0000,
+        "permission to read",
+        "asks for permission to be read",
+    ),
+    (
+        0x0004_0000,
+        "permission to execute",
+        "asks for permission to be opened for execution",
+    ),
+    (0x0800_0000, "events on children", "had a child change"),
+    (0x1000_0000, "renames", "was renamed"),
+];
+const FAN_ONDIR: u64 = 0x4000_0000;
+
+const FANOTIFY_CLASSES: [(u64, &str); 3] = [
+    (0x0, "to be notified of file accesses"),
+    (
+        0x4,
+        "to decide on file accesses once the content is available",
+    ),
+    (
+        0x8,
+        "to decide on file accesses before the content is available",
+    ),
+];
+const FAN_CLASS_MASK: u64 = 0xc;
+
+const FANOTIFY_INIT_FLAGS: [(u64, &str); 10] = [
+    (0x0001, "closed on exec"),
+    (0x0002, "non-blocking"),
+    (0x0010, "with an unlimited queue"),
+    (0x0020, "with unlimited marks"),
+    (0x0040, "auditing permission decisions"),
+    (0x0080, "reporting pidfds"),
+    (0x0100, "reporting thread ids"),
+    (0x0200, "reporting file handles"),
+    (0x0400, "reporting directory handles"),
+    (0x0800, "reporting names"),
+];
+
+const FAN_MARK_ADD: u64 = 0x1;
+const FAN_MARK_REMOVE: u64 = 0x2;
+const FAN_MARK_FLUSH: u64 = 0x80;
+const FAN_MARK_MOUNT: u64 = 0x10;
+const FAN_MARK_FILESYSTEM: u64 = 0x100;
+const FAN_MARK_IGNORED_MASK: u64 = 0x20;
+const FAN_MARK_IGNORE: u64 = 0x400;
+const FANOTIFY_MARK_FLAGS: [(u64, &str); 4] = [
+    (0x04, "without following a trailing symlink"),
+    (0x08, "only if it is a directory"),
+    (0x40, "kept after modifications"),
+    (0x200, "evictable with the inode"),
+];
+
+// events read from a single read are cut after this many
+const SHOWN_EVENTS: usize = 16;
+
+// struct fanotify_event_metadata
+const FANOTIFY_METADATA_LENGTH: usize = 24;
+const FAN_NOFD: i32 = -1;
+
+fn read_text(address: u64, child: Pid) -> String {
+    if address == 0 {
+        return String::new();
+    }
+    SyscallObject::read_bytes_until_null(address as usize, child)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
+
+fn flag_words(flags: u64, table: &[(u64, &'static str)]) -> Vec<&'static str> {
+    table
+        .iter()
+        .filter(|(flag, _)| (flags & flag) != 0)
+        .map(|(_, word)| *word)
+        .collect()
+}
+
+fn watched_events(mask: u64, only: &[(u64, &'static str, &'static str)]) -> String {
+    let mut watched = vec![];
+    if (mask & ALL_EVENTS) == ALL_EVENTS {
+        watched.push("all events");
+    } else {
+        watched.extend(
+            EVENTS
+                .iter()
+                .filter(|(flag, _, _)| (mask & flag) != 0)
+                .map(|(_, noun, _)| *noun),
+        );
+    }
+    watched.extend(
+        only.iter()
+            .filter(|(flag, _, _)| (mask & flag) != 0)
+            .map(|(_, noun, _)| *noun),
+    );
+    if watched.is_empty() {
+        "no events".to_owned()
+    } else {
+        watched.join(", ")
+    }
+}
+
+fn happened(mask: u64, only: &[(u64, &'static str, &'static str)]) -> String {
+    let happened = EVENTS
+        .iter()
+        .chain(only)
+        .filter(|(flag, _, _)| (mask & flag) != 0)
+        .map(|(_, _, past)| *past)
+        .collect::<Vec<&str>>();
+    if happened.is_empty() {
+        format!("had the unknown event 0x{mask:x}")
+    } else {
+        happened.join(" and ")
+    }
+}
+
+fn watch_kind(pid: Pid, fd: u64) -> Option<&'static str> {
+    let link = std::fs::read_link(format!("/proc/{pid}/fd/{fd}")).ok()?;
+    match link.to_str()? {
+        "anon_inode:inotify" => Some("inotify"),
+        "anon_inode:[fanotify]" => Some("fanotify"),
+        _ => None,
+    }
+}
+
+// the descriptor, mask, cookie and name of every event in a buffer read from an inotify fd
+fn inotify_records(buffer: &[u8]) -> Vec<(i32, u64, u32, String)> {
+    let mut records = vec![];
+    let mut offset = 0;
+    while offset + 16 <= buffer.len() {
+        let field = |at: usize| {
+            u32::from_le_bytes(buffer[offset + at..offset + at + 4].try_into().unwrap())
+        };
+        let name_length = field(12) as usize;
+        let name = buffer
+            .get(offset + 16..offset + 16 + name_length)
+            .map(|name| {
+                let name = name.split(|byte| *byte == 0).next().unwrap_or_default();
+                String::from_utf8_lossy(name).into_owned()
+            })
+            .unwrap_or_default();
+        records.push((field(0) as i32, field(4) as u64, field(8), name));
+        offset += 16 + name_length;
+    }
+    records
+}
+
+// a removed watch is kept for the thread that removed it until its next syscall, so the line can still name it
+fn unwatch(tid: Pid, fd: i32, descriptor: i32) {
+    let Some(path) = INOTIFY_WATCHES.with_borrow_mut(|watches| {
+        watches
+            .get_mut(&(thread_group(tid), fd))?
+            .remove(&descriptor)
+    }) else {
+        return;
+    };
+    UNWATCHED_PATHS.with_borrow_mut(|unwatched| {
+        unwatched
+            .entry((tid, fd))
+            .or_default()
+            .insert(descriptor, path)
+    });
+}
+
+// watches belong to the process, threads share the inotify fd
+// an inotify fd number can be reused, so watches are dropped when it is closed or a new instance is created
+// a watch descriptor is gone once it is removed or its IN_IGNORED event is read
+pub fn track_watches(syscall: &SyscallObject) {
+    UNWATCHED_PATHS.with_borrow_mut(|unwatched| {
+        if !unwatched.is_empty() {
+            unwatched.retain(|(tid, _), _| *tid != syscall.child)
+        }
+    });
+    if syscall.errno.is_some() {
+        return;
+    }
+    let Some(result) = syscall.result.0 else {
+        return;
+    };
+    let process = thread_group(syscall.child);
+    match syscall.sysno {
+        Sysno::inotify_init | Sysno::inotify_init1 => {
+            INOTIFY_WATCHES.with_borrow_mut(|watches| watches.remove(&(process, result as i32)));
+        }
+        Sysno::close => {
+            INOTIFY_WATCHES
+                .with_borrow_mut(|watches| watches.remove(&(process, syscall.args[0] as i32)));
+        }
+        Sysno::inotify_add_watch => {
+            let path = resolve_child_path(
+                syscall.child,
+                AT_FDCWD,
+                &read_text(syscall.args[1], syscall.child),
+            );
+            INOTIFY_WATCHES.with_borrow_mut(|watches| {
+                watches
+                    .entry((process, syscall.args[0] as i32))
+                    .or_default()
+                    .insert(result as i32, path)
+            });
+        }
+        Sysno::inotify_rm_watch => {
+            unwatch(
+                syscall.child,
+                syscall.args[0] as i32,
+                syscall.args[1] as i32,
+            );
+        }
+        // only reads from an fd with watches recorded can carry IN_IGNORED events for them
+        Sysno::read => {
+            let fd = syscall.args[0] as i32;
+            if !INOTIFY_WATCHES.with_borrow(|watches| watches.contains_key(&(process, fd))) {
+                return;
+            }
+            let Some(buffer) = SyscallObject::read_bytes_specific_length(
+                syscall.args[1] as usize,
+                syscall.child,
+                result as usize,
+            ) else {
+                return;
+            };
+            for (descriptor, mask, _, _) in inotify_records(&buffer) {
+                if (mask & IN_IGNORED) != 0 {
+                    unwatch(syscall.child, fd, descriptor);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn watched_path(tid: Pid, fd: i32, descriptor: i32) -> Option<PathBuf> {
+    INOTIFY_WATCHES
+        .with_borrow(|watches| {
+            watches
+                .get(&(thread_group(tid), fd))?
+                .get(&descriptor)
+                .cloned()
+        })
+        .or_else(|| {
+            UNWATCHED_PATHS
+                .with_borrow(|unwatched| unwatched.get(&(tid, fd))?.get(&descriptor).cloned())
+        })
+}
+
+impl SyscallObject {
+    fn watch_fd(&self) -> ColoredString {
+        SyscallObject::style_file_descriptor(self.args[0], self.child)
+            .unwrap_or_else(|| self.args[0].to_string())
+            .yellow()
+    }
+
+    // the object a fanotify mark is placed on, pathname can be NULL to use dirfd itself
+    fn fanotify_target(&self) -> String {
+        let dirfd = self.args[3] as i32;
+        if self.args[4] == 0 {
+            return std::fs::read_link(format!("/proc/{}/fd/{dirfd}", self.child))
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| format!("file descriptor {dirfd}"));
+        }
+        resolve_child_path(self.child, dirfd, &read_text(self.args[4], self.child))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    pub(crate) fn watch_intent(&self) -> Vec<ColoredString> {
+        let mut intent = vec![];
+        match self.sysno {
+            Sysno::inotify_init | Sysno::inotify_init1 => {
+                intent.push("create an inotify instance".white());
+                let flags = flag_words(
+                    self.args[0],
+                    &[
+                        (O_NONBLOCK as u64, "non-blocking"),
+                        (O_CLOEXEC as u64, "closed on exec"),
+                    ],
+                );
+                if self.sysno == Sysno::inotify_init1 && !flags.is_empty() {
+                    intent.push(" (".white());
+                    intent.push(flags.join(", ").yellow());
+                    intent.push(")".white());
+                }
+            }
+            Sysno::inotify_add_watch => {
+                let path = read_text(self.args[1], self.child);
+                intent.push("watch ".white());
+                intent.push(path.yellow());
+                intent.push(" for ".white());
+                intent.push(watched_events(self.args[2], &INOTIFY_ONLY_EVENTS).yellow());
+                let flags = flag_words(self.args[2], &INOTIFY_WATCH_FLAGS);
+                if !flags.is_empty() {
+                    intent.push(" (".white());
+                    intent.push(flags.join(", ").yellow());
+                    intent.push(")".white());
+                }
+                intent.push(" through ".white());
+                intent.push(self.watch_fd());
+            }
+            Sysno::inotify_rm_watch => {
+                intent.push("stop watching ".white());
+                match watched_path(self.child, self.args[0] as i32, self.args[1] as i32) {
+                    Some(path) => intent.push(path.to_string_lossy().into_owned().yellow()),
+                    None => {
+                        intent.push("the watch descriptor ".white());
+                        intent.push(self.args[1].to_string().yellow());
+                    }
+                }
+                intent.push(" through ".white());
+                intent.push(self.watch_fd());
+            }
+            Sysno::fanotify_init => {
+                intent.push("create a fanotify group ".white());
+                let class = FANOTIFY_CLASSES
+                    .iter()
+                    .find(|(class, _)| (self.args[0] & FAN_CLASS_MASK) == *class)
+                    .map(|(_, class)| *class)
+                    .unwrap_or("of an unknown class");
+                intent.push(class.white());
+                let flags = flag_words(self.args[0], &FANOTIFY_INIT_FLAGS);
+                if !flags.is_empty() {
+                    intent.push(" (".white());
+                    intent.push(flags.join(", ").yellow());
+                    intent.push(")".white());
+                }
+            }
+            _ => {
+                let flags = self.args[1];
+                let object = if (flags & FAN_MARK_FILESYSTEM) != 0 {
+                    "the filesystem containing "
+                } else if (flags & FAN_MARK_MOUNT) != 0 {
+                    "the mount containing "
+                } else {
+                    ""
+                };
+                if (flags & FAN_MARK_FLUSH) != 0 {
+                    intent.push("remove every mark on ".white());
+                    intent.push(match object {
+                        "" => "inodes".yellow(),
+                        "the mount containing " => "mounts".yellow(),
+                        _ => "filesystems".yellow(),
+                    });
+                } else {
+                    let ignoring = (flags & (FAN_MARK_IGNORED_MASK | FAN_MARK_IGNORE)) != 0;
+                    intent.push(match (flags & (FAN_MARK_ADD | FAN_MARK_REMOVE), ignoring) {
+                        (FAN_MARK_REMOVE, false) => "stop watching ".white(),
+                        (FAN_MARK_REMOVE, true) => "stop ignoring ".white(),
+                        (_, false) => "watch ".white(),
+                        (_, true) => "ignore ".white(),
+                    });
+                    intent.push(object.white());
+                    intent.push(self.fanotify_target().yellow());
+                    intent.push(" for ".white());
+                    intent.push(watched_events(self.args[2], &FANOTIFY_ONLY_EVENTS).yellow());
+                    if (self.args[2] & FAN_ONDIR) != 0 {
+                        intent.push(", including directories".white());
+                    }
+                    let marks = flag_words(flags, &FANOTIFY_MARK_FLAGS);
+                    if !marks.is_empty() {
+                        intent.push(" (".white());
+                        intent.push(marks.join(", ").yellow());
+                        intent.push(")".white());
+                    }
+                }
+                intent.push(" through ".white());
+                intent.push(self.watch_fd());
+            }
+        }
+        intent
+    }
+
+    pub(crate) fn watch_result(&self) -> Vec<ColoredString> {
+        let value = self.result.0.unwrap_or(0);
+        match self.sysno {
+            Sysno::inotify_init | Sysno::inotify_init1 => {
+                vec!["inotify fd ".green(), value.to_string().yellow()]
+            }
+            Sysno::inotify_add_watch => {
+                vec!["watch descriptor ".green(), value.to_string().yellow()]
+            }
+            Sysno::fanotify_init => vec!["fanotify fd ".green(), value.to_string().yellow()],
+            _ => vec!["successful".green()],
+        }
+    }
+
+    // the events returned by a read on an inotify or fanotify fd, one per line
+    pub(crate) fn watch_events(&self) -> Vec<ColoredString> {
+        if self.sysno != Sysno::read {
+            return vec![];
+        }
+        let Some(kind) = watch_kind(self.child, self.args[0]) else {
+            return vec![];
+        };
+        let length = self.result.0.unwrap_or(0) as usize;
+        let Some(buffer) =
+            SyscallObject::read_bytes_specific_length(self.args[1] as usize, self.child, length)
+        else {
+            return vec![];
+        };
+        let events = if kind == "inotify" {
+            self.inotify_events(&buffer)
+        } else {
+            self.fanotify_events(&buffer)
+        };
+        let mut shown = vec![];
+        for event in events.iter().take(SHOWN_EVENTS) {
+            shown.push("\n\t".white());
+            shown.extend(event.iter().cloned());
+        }
+        if events.len() > SHOWN_EVENTS {
+            shown.push(format!("\n\t{} more events", events.len() - SHOWN_EVENTS).white());
+        }
+        shown
+    }
+
+    fn inotify_events(&self, buffer: &[u8]) -> Vec<Vec<ColoredString>> {
+        let mut events = vec![];
+        for (descriptor, mask, cookie, name) in inotify_records(buffer) {
+            let watched = watched_path(self.child, self.args[0] as i32, descriptor);
+            let path = match (watched, name.is_empty()) {
+                (Some(watched), true) => watched.to_string_lossy().into_owned(),
+                (Some(watched), false) => watched.join(&name).to_string_lossy().into_owned(),
+                (None, true) => format!("watch descriptor {descriptor}"),
+                (None, false) => format!("{name} (watch descriptor {descriptor})"),
+            };
+            let mut event = vec![];
+            if (mask & IN_ISDIR) != 0 {
+                event.push("the directory ".white());
+            }
+            event.push(path.yellow());
+            event.push(" ".white());
+            event.push(happened(mask, &INOTIFY_ONLY_EVENTS).bright_cyan());
+            if cookie != 0 {
+                event.push(" (move cookie ".white());
+                event.push(cookie.to_string().yellow());
+                event.push(")".white());
+            }
+            events.push(event);
+        }
+        events
+    }
+
+    fn fanotify_events(&self, buffer: &[u8]) -> Vec<Vec<ColoredString>> {
+        let mut events = vec![];
+        let mut offset = 0;
+        while offset + FANOTIFY_METADATA_LENGTH <= buffer.len() {
+            let metadata = &buffer[offset..offset + FANOTIFY_METADATA_LENGTH];
+            let event_length = u32::from_le_bytes(metadata[0..4].try_into().unwrap()) as usize;
+            let mask = u64::from_le_bytes(metadata[8..16].try_into().unwrap());
+            let fd = i32::from_le_bytes(metadata[16..20].try_into().unwrap());
+            let pid = i32::from_le_bytes(metadata[20..24].try_into().unwrap());
+            if event_length < FANOTIFY_METADATA_LENGTH {
+                break;
+            }
+            offset += event_length;
+
+            // the event fd is already open in the tracee when read returns
+            let path = if fd == FAN_NOFD {
+                "a file identified by its handle".to_owned()
+            } else {
+                std::fs::read_link(format!("/proc/{}/fd/{fd}", self.child))
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .unwrap_or_else(|_| format!("file descriptor {fd}"))
+            };
+            let mut event = vec![];
+            if (mask & FAN_ONDIR) != 0 {
+                event.push("the directory ".white());
+            }
+            event.push(path.yellow());
+            event.push(" ".white());
+            event.push(happened(mask, &FANOTIFY_ONLY_EVENTS).bright_cyan());
+            event.push(" by process ".white());
+            event.push(pid.to_string().yellow());
+            if fd >= 0 {
+                event.push(" (opened as fd ".white());
+                event.push(fd.to_string().yellow());
+                event.push(")".white());
+            }
+            events.push(event);
+        }
+        events
+    }
+}
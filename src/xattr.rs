@@ -0,0 +1,292 @@
+// decoding of the extended attribute syscalls, the path, l* and f* variants of set/get/list/remove
+// values of the attributes the kernel itself interprets are decoded, the rest shown as text or hex
+use crate::{
+    credentials::describe_capabilities,
+    error_explainer::{group_name, user_name},
+    one_line_formatter::handle_path_file,
+    syscall_object::SyscallObject,
+};
+use colored::{ColoredString, Colorize};
+use nix::unistd::Pid;
+use syscalls::Sysno;
+
+const XATTR_CREATE: u64 = 0x1;
+const XATTR_REPLACE: u64 = 0x2;
+
+// values longer than this are cut short in the output
+const SHOWN_VALUE_BYTES: usize = 64;
+
+// the largest value the kernel accepts, also the most read back from the tracee
+const XATTR_SIZE_MAX: usize = 65536;
+
+// vfs_cap_data, the revision lives in the top byte of magic_etc
+const VFS_CAP_REVISION_MASK: u32 = 0xFF00_0000;
+const VFS_CAP_REVISION_1: u32 = 0x0100_0000;
+const VFS_CAP_REVISION_2: u32 = 0x0200_0000;
+const VFS_CAP_REVISION_3: u32 = 0x0300_0000;
+const VFS_CAP_FLAGS_EFFECTIVE: u32 = 0x1;
+
+const POSIX_ACL_XATTR_VERSION: u32 = 0x0002;
+const ACL_USER_OBJ: u16 = 0x01;
+const ACL_USER: u16 = 0x02;
+const ACL_GROUP_OBJ: u16 = 0x04;
+const ACL_GROUP: u16 = 0x08;
+const ACL_MASK: u16 = 0x10;
+const ACL_OTHER: u16 = 0x20;
+
+fn read_text(address: u64, child: Pid) -> String {
+    if address == 0 {
+        return String::new();
+    }
+    SyscallObject::read_bytes_until_null(address as usize, child)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    let word = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(word.try_into().ok()?))
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    let half = bytes.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes(half.try_into().ok()?))
+}
+
+// the capability sets are split in a low and a high 32 bit half
+fn capability_set(bytes: &[u8], low: usize, high: Option<usize>) -> Option<u64> {
+    let low = u32_at(bytes, low)? as u64;
+    let high = match high {
+        Some(high) => u32_at(bytes, high)? as u64,
+        None => 0,
+    };
+    Some((high << 32) | low)
+}
+
+fn file_capabilities(bytes: &[u8]) -> Option<Vec<ColoredString>> {
+    let magic = u32_at(bytes, 0)?;
+    let (permitted, inheritable, root) = match magic & VFS_CAP_REVISION_MASK {
+        VFS_CAP_REVISION_1 => (
+            capability_set(bytes, 4, None)?,
+            capability_set(bytes, 8, None)?,
+            None,
+        ),
+        VFS_CAP_REVISION_2 => (
+            capability_set(bytes, 4, Some(12))?,
+            capability_set(bytes, 8, Some(16))?,
+            None,
+        ),
+        VFS_CAP_REVISION_3 => (
+            capability_set(bytes, 4, Some(12))?,
+            capability_set(bytes, 8, Some(16))?,
+            Some(u32_at(bytes, 20)?),
+        ),
+        _ => return None,
+    };
+    let mut capabilities = vec![
+        "file capabilities, permitted: ".white(),
+        describe_capabilities(permitted).yellow(),
+        ", inheritable: ".white(),
+        describe_capabilities(inheritable).yellow(),
+    ];
+    if (magic & VFS_CAP_FLAGS_EFFECTIVE) != 0 {
+        capabilities.push(" (raised as effective at exec)".white());
+    }
+    if let Some(root) = root {
+        capabilities.push(", only for the user namespace whose root is uid ".white());
+        capabilities.push(root.to_string().yellow());
+    }
+    Some(capabilities)
+}
+
+fn acl_permissions(permissions: u16) -> String {
+    [(4, 'r'), (2, 'w'), (1, 'x')]
+        .iter()
+        .map(|(bit, letter)| {
+            if (permissions & bit) != 0 {
+                *letter
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+// entries are written the way getfacl prints them
+fn acl_entries(bytes: &[u8]) -> Option<Vec<ColoredString>> {
+    if u32_at(bytes, 0)? != POSIX_ACL_XATTR_VERSION || ((bytes.len() - 4) & 7) != 0 {
+        return None;
+    }
+    let entries = bytes[4..]
+        .chunks(8)
+        .map(|entry| {
+            let tag = u16_at(entry, 0)?;
+            let permissions = acl_permissions(u16_at(entry, 2)?);
+            let id = u32_at(entry, 4)?;
+            Some(match tag {
+                ACL_USER_OBJ => format!("user::{permissions}"),
+                ACL_USER => format!("user:{}:{permissions}", user_name(id)),
+                ACL_GROUP_OBJ => format!("group::{permissions}"),
+                ACL_GROUP => format!("group:{}:{permissions}", group_name(id)),
+                ACL_MASK => format!("mask::{permissions}"),
+                ACL_OTHER => format!("other::{permissions}"),
+                _ => format!("unknown tag {tag}:{permissions}"),
+            })
+        })
+        .collect::<Option<Vec<String>>>()?;
+    Some(vec!["ACL entries: ".white(), entries.join(", ").yellow()])
+}
+
+fn is_text(bytes: &[u8]) -> bool {
+    !bytes.is_empty()
+        && bytes
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() || *byte == b' ' || *byte == b'\t')
+}
+
+fn raw_value(bytes: &[u8]) -> Vec<ColoredString> {
+    // values set from the shell usually carry their terminating NUL
+    let trimmed = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+    if trimmed.is_empty() {
+        return vec!["an empty value".white()];
+    }
+    let shown = &trimmed[..trimmed.len().min(SHOWN_VALUE_BYTES)];
+    let cut = if shown.len() < trimmed.len() {
+        "..."
+    } else {
+        ""
+    };
+    if is_text(trimmed) {
+        vec![format!("\"{}{cut}\"", String::from_utf8_lossy(shown)).yellow()]
+    } else {
+        let hex = shown
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        vec![
+            "hex ".white(),
+            format!("{hex}{cut}").yellow(),
+            format!(" ({} bytes)", trimmed.len()).white(),
+        ]
+    }
+}
+
+fn describe_value(name: &str, bytes: &[u8]) -> Vec<ColoredString> {
+    let decoded = match name {
+        "security.capability" => file_capabilities(bytes),
+        "system.posix_acl_access" | "system.posix_acl_default" => acl_entries(bytes),
+        "security.selinux" => {
+            let label =
+                String::from_utf8_lossy(bytes.strip_suffix(&[0]).unwrap_or(bytes)).into_owned();
+            Some(vec!["SELinux label: ".white(), label.yellow()])
+        }
+        _ => None,
+    };
+    decoded.unwrap_or_else(|| raw_value(bytes))
+}
+
+impl SyscallObject {
+    fn xattr_target(&self, xattr: &mut Vec<ColoredString>) {
+        handle_path_file(self.pavfol(0), xattr);
+        if matches!(
+            self.sysno,
+            Sysno::lsetxattr | Sysno::lgetxattr | Sysno::llistxattr | Sysno::lremovexattr
+        ) {
+            xattr.push(" (the symlink itself, not what it points to)".white());
+        }
+    }
+
+    fn xattr_value(&self, name: &str, address: u64, length: usize) -> Vec<ColoredString> {
+        match SyscallObject::read_bytes_specific_length(
+            address as usize,
+            self.child,
+            length.min(XATTR_SIZE_MAX),
+        ) {
+            Some(bytes) => describe_value(name, &bytes),
+            None => vec!["an unreadable value".white()],
+        }
+    }
+
+    pub(crate) fn xattr_intent(&self) -> Vec<ColoredString> {
+        let name = read_text(self.args[1], self.child);
+        let mut intent = vec![];
+        match self.sysno {
+            Sysno::setxattr | Sysno::lsetxattr | Sysno::fsetxattr => {
+                intent.push("set the extended attribute ".white());
+                intent.push(name.as_str().yellow());
+                intent.push(" of ".white());
+                self.xattr_target(&mut intent);
+                intent.push(" to ".white());
+                intent.extend(self.xattr_value(&name, self.args[2], self.args[3] as usize));
+                if (self.args[4] & XATTR_CREATE) != 0 {
+                    intent.push(" (fail if it already exists)".white());
+                } else if (self.args[4] & XATTR_REPLACE) != 0 {
+                    intent.push(" (fail if it does not exist yet)".white());
+                }
+            }
+            Sysno::getxattr | Sysno::lgetxattr | Sysno::fgetxattr => {
+                if self.args[3] == 0 {
+                    intent.push("get the size of the extended attribute ".white());
+                } else {
+                    intent.push("get the extended attribute ".white());
+                }
+                intent.push(name.as_str().yellow());
+                intent.push(" of ".white());
+                self.xattr_target(&mut intent);
+            }
+            Sysno::listxattr | Sysno::llistxattr | Sysno::flistxattr => {
+                if self.args[2] == 0 {
+                    intent.push("get the size of the extended attribute names of ".white());
+                } else {
+                    intent.push("list the extended attribute names of ".white());
+                }
+                self.xattr_target(&mut intent);
+            }
+            _ => {
+                intent.push("remove the extended attribute ".white());
+                intent.push(name.as_str().yellow());
+                intent.push(" from ".white());
+                self.xattr_target(&mut intent);
+            }
+        }
+        intent
+    }
+
+    pub(crate) fn xattr_result(&self) -> Vec<ColoredString> {
+        let length = self.result.0.unwrap_or(0) as usize;
+        match self.sysno {
+            Sysno::getxattr | Sysno::lgetxattr | Sysno::fgetxattr => {
+                if self.args[3] == 0 {
+                    vec![format!("{length} bytes needed").green()]
+                } else {
+                    let name = read_text(self.args[1], self.child);
+                    let mut result = vec!["got ".green()];
+                    result.extend(self.xattr_value(&name, self.args[2], length));
+                    result
+                }
+            }
+            Sysno::listxattr | Sysno::llistxattr | Sysno::flistxattr => {
+                if self.args[2] == 0 {
+                    return vec![format!("{length} bytes needed").green()];
+                }
+                let names = SyscallObject::read_bytes_specific_length(
+                    self.args[1] as usize,
+                    self.child,
+                    length.min(XATTR_SIZE_MAX),
+                )
+                .unwrap_or_default();
+                let names = names
+                    .split(|byte| *byte == 0)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| String::from_utf8_lossy(name).into_owned())
+                    .collect::<Vec<String>>();
+                if names.is_empty() {
+                    vec!["no extended attributes".green()]
+                } else {
+                    vec!["names: ".green(), names.join(", ").yellow()]
+                }
+            }
+            _ => vec!["successful".green()],
+        }
+    }
+}